@@ -1,7 +1,10 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
 
 use convergent_core::graph::IntentGraph;
-use convergent_core::models::{IntentNode, InterfaceKind, InterfaceSpec};
+use convergent_core::matching::{clear_signature_cache, signatures_compatible};
+use convergent_core::models::{Evidence, IntentNode, InterfaceKind, InterfaceSpec};
 
 fn make_intent(agent_id: &str, name: &str, provides: Vec<&str>, requires: Vec<&str>) -> IntentNode {
     IntentNode::new(agent_id, &format!("Implement {}", name))
@@ -123,11 +126,135 @@ fn bench_find_overlapping(c: &mut Criterion) {
     });
 }
 
+fn bench_find_all_conflicts(c: &mut Criterion) {
+    // A full conflict sweep resolves every intent against the rest of the
+    // graph, so each intent's stability is scored once per overlapping
+    // neighbour. Evidence makes those scores cost something to recompute.
+    let build = |graph: IntentGraph| {
+        for i in 0..100 {
+            let intent = make_intent(
+                &format!("agent_{}", i),
+                &format!("service_{}", i),
+                vec![Box::leak(format!("provide_{}", i % 10).into_boxed_str())],
+                vec![Box::leak(format!("require_{}", i % 5).into_boxed_str())],
+            )
+            .with_evidence(vec![
+                Evidence::code_committed("landed"),
+                Evidence::test_pass("unit"),
+                Evidence::test_pass("integration"),
+                Evidence::consumed_by("agent_0"),
+            ]);
+            graph.publish(&intent).unwrap();
+        }
+        graph
+    };
+
+    let mut group = c.benchmark_group("find_all_conflicts_100_intents");
+    group.sample_size(10);
+    let graph = build(IntentGraph::in_memory().unwrap());
+    group.bench_function("uncached", |b| {
+        b.iter(|| black_box(graph.conflict_count(black_box(0.0)).unwrap()));
+    });
+    // Larger than the graph, so after the first sweep every score is a hit
+    let graph = build(IntentGraph::in_memory().unwrap().with_stability_cache(1024));
+    group.bench_function("stability_cache", |b| {
+        b.iter(|| black_box(graph.conflict_count(black_box(0.0)).unwrap()));
+    });
+    group.finish();
+}
+
+fn bench_resolve_batch(c: &mut Criterion) {
+    // Ten alternative designs evaluated against a 500-intent graph
+    let graph = IntentGraph::in_memory().unwrap();
+    for i in 0..500 {
+        let intent = make_intent(
+            &format!("agent_{}", i),
+            &format!("service_{}", i),
+            vec![Box::leak(format!("provide_{}", i).into_boxed_str())],
+            vec![Box::leak(format!("require_{}", i % 5).into_boxed_str())],
+        );
+        graph.publish(&intent).unwrap();
+    }
+    let candidates: Vec<IntentNode> = (0..10)
+        .map(|i| {
+            make_intent(
+                "agent_new",
+                &format!("design_{}", i),
+                vec![Box::leak(format!("provide_{}", i * 7).into_boxed_str())],
+                vec!["require_0"],
+            )
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("resolve_10_candidates_500_existing");
+    group.bench_function("individual", |b| {
+        b.iter(|| {
+            for intent in &candidates {
+                black_box(graph.resolve(intent, 0.0).unwrap());
+            }
+        });
+    });
+    group.bench_function("batch", |b| {
+        b.iter(|| black_box(graph.resolve_batch(&candidates, 0.0).unwrap()));
+    });
+    group.finish();
+}
+
+fn bench_resolve_repeated_signatures(c: &mut Criterion) {
+    // 200 agents all providing the same model; resolving a consumer compares
+    // its requirement against that one signature 200 times.
+    let signature = "id: UUID, email: Optional[str], roles: List[str], \
+                     created_at: datetime, settings: Dict[str, Any]";
+    let spec = || InterfaceSpec::new("User", InterfaceKind::Model, signature);
+    let graph = IntentGraph::in_memory().unwrap();
+    for i in 0..200 {
+        let intent = IntentNode::new(&format!("agent_{}", i), &format!("Implement users_{}", i))
+            .with_provides(vec![spec()])
+            .with_stability(0.7);
+        graph.publish(&intent).unwrap();
+    }
+    let consumer = IntentNode::new("agent_new", "Implement profiles").with_requires(vec![spec()]);
+
+    let mut group = c.benchmark_group("resolve_200_repeated_signatures");
+    group.bench_function("cold_cache", |b| {
+        b.iter(|| {
+            clear_signature_cache();
+            black_box(graph.resolve(&consumer, 0.0).unwrap())
+        });
+    });
+    group.bench_function("warm_cache", |b| {
+        b.iter(|| black_box(graph.resolve(&consumer, 0.0).unwrap()));
+    });
+    group.finish();
+
+    // The per-comparison cost the cache saves inside one resolve
+    let mut group = c.benchmark_group("signatures_compatible_200_calls");
+    group.bench_function("uncached", |b| {
+        b.iter(|| {
+            for _ in 0..200 {
+                clear_signature_cache();
+                black_box(signatures_compatible(black_box(signature), signature));
+            }
+        });
+    });
+    group.bench_function("cached", |b| {
+        b.iter(|| {
+            for _ in 0..200 {
+                black_box(signatures_compatible(black_box(signature), signature));
+            }
+        });
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_publish,
     bench_query_all,
     bench_resolve,
-    bench_find_overlapping
+    bench_find_overlapping,
+    bench_find_all_conflicts,
+    bench_resolve_batch,
+    bench_resolve_repeated_signatures
 );
 criterion_main!(benches);
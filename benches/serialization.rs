@@ -0,0 +1,64 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use convergent_core::graph::IntentGraph;
+use convergent_core::models::{Evidence, IntentNode, InterfaceKind, InterfaceSpec};
+
+fn build_graph(n: usize) -> IntentGraph {
+    let graph = IntentGraph::in_memory().unwrap();
+    for i in 0..n {
+        let spec = |name: String| {
+            InterfaceSpec::new(&name, InterfaceKind::Function, "(x: str) -> str")
+                .with_tags(vec!["api", "benchmark"])
+        };
+        let intent = IntentNode::new(&format!("agent_{}", i), &format!("Implement service_{}", i))
+            .with_provides(vec![spec(format!("provide_{}", i))])
+            .with_requires(vec![spec(format!("require_{}", i % 5))])
+            .with_evidence(vec![Evidence::test_pass("passing")]);
+        graph.publish(&intent).unwrap();
+    }
+    graph
+}
+
+fn bench_export(c: &mut Criterion) {
+    let graph = build_graph(1000);
+
+    // Size is what this format exists for; report it alongside the timings.
+    let json = serde_json::to_vec(&graph.query_all(None).unwrap()).unwrap();
+    let bincode = graph.export_bincode().unwrap();
+    println!(
+        "1000 intents: json {} bytes, bincode {} bytes ({:.1}% smaller)",
+        json.len(),
+        bincode.len(),
+        100.0 * (1.0 - bincode.len() as f64 / json.len() as f64)
+    );
+
+    let mut group = c.benchmark_group("export_1000_intents");
+    group.bench_function("json", |b| {
+        b.iter(|| serde_json::to_vec(&graph.query_all(None).unwrap()).unwrap())
+    });
+    group.bench_function("bincode", |b| b.iter(|| graph.export_bincode().unwrap()));
+    group.finish();
+
+    let mut group = c.benchmark_group("import_1000_intents");
+    group.bench_function("json", |b| {
+        b.iter(|| {
+            let target = IntentGraph::in_memory().unwrap();
+            let intents: Vec<IntentNode> = serde_json::from_slice(black_box(&json)).unwrap();
+            for intent in &intents {
+                target.publish(intent).unwrap();
+            }
+        })
+    });
+    group.bench_function("bincode", |b| {
+        b.iter(|| {
+            let target = IntentGraph::in_memory().unwrap();
+            target.import_bincode(black_box(&bincode), false).unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_export);
+criterion_main!(benches);
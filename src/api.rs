@@ -0,0 +1,144 @@
+//! JSON entry points for pure-Rust consumers.
+//!
+//! These mirror the conversions the Python bindings perform on dicts, but work
+//! on `serde_json` strings so the crate can be embedded without PyO3. Only
+//! `agent_id` and `intent` are required when building an intent; every other
+//! field falls back to the same defaults as [`IntentNode::new`], except that a
+//! missing `id` stays empty so the graph assigns one on publish. Enum values use
+//! their Rust variant names (`"Model"`, `"TestPass"`), matching the on-disk format.
+
+use crate::graph::GraphSummary;
+use crate::models::{ConflictReport, IntentNode, InterfaceSpec, ResolutionResult};
+
+/// Result type for JSON conversions.
+pub type Result<T> = std::result::Result<T, serde_json::Error>;
+
+/// Build an intent from a JSON object.
+pub fn intent_from_json(json: &str) -> Result<IntentNode> {
+    let mut intent: IntentNode = serde_json::from_str(json)?;
+    intent.stability = intent.stability.clamp(0.0, 1.0);
+    Ok(intent)
+}
+
+/// Build a list of intents from a JSON array.
+pub fn intents_from_json(json: &str) -> Result<Vec<IntentNode>> {
+    let mut intents: Vec<IntentNode> = serde_json::from_str(json)?;
+    for intent in &mut intents {
+        intent.stability = intent.stability.clamp(0.0, 1.0);
+    }
+    Ok(intents)
+}
+
+/// Build a list of interface specs from a JSON array.
+pub fn specs_from_json(json: &str) -> Result<Vec<InterfaceSpec>> {
+    serde_json::from_str(json)
+}
+
+/// Serialize an intent to a JSON object.
+pub fn intent_to_json(intent: &IntentNode) -> Result<String> {
+    serde_json::to_string(intent)
+}
+
+/// Serialize a resolution result to a JSON object.
+pub fn resolution_to_json(result: &ResolutionResult) -> Result<String> {
+    serde_json::to_string(result)
+}
+
+/// Serialize a list of conflict reports to a JSON array.
+pub fn conflicts_to_json(conflicts: &[ConflictReport]) -> Result<String> {
+    serde_json::to_string(conflicts)
+}
+
+/// Serialize a graph summary to a JSON object.
+pub fn summary_to_json(summary: &GraphSummary) -> Result<String> {
+    serde_json::to_string(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::IntentGraph;
+    use crate::models::{AdjustmentKind, ConstraintSeverity, EvidenceKind, InterfaceKind};
+
+    #[test]
+    fn test_intent_from_minimal_json() {
+        let intent = intent_from_json(r#"{"agent_id": "agent-a", "intent": "Auth"}"#).unwrap();
+        assert_eq!(intent.agent_id, "agent-a");
+        assert_eq!(intent.intent, "Auth");
+        assert!(intent.id.is_empty());
+        assert!((intent.stability - 0.3).abs() < f64::EPSILON);
+        assert!(intent.provides.is_empty());
+        assert!(intent.parent_id.is_none());
+    }
+
+    #[test]
+    fn test_intent_from_full_json() {
+        let intent = intent_from_json(
+            r#"{
+                "id": "i-1",
+                "agent_id": "agent-a",
+                "intent": "Auth",
+                "provides": [{"name": "User", "kind": "Model", "signature": "id: UUID",
+                              "tags": ["user"]}],
+                "constraints": [{"target": "User model", "requirement": "must have email"}],
+                "evidence": [{"kind": "TestPass", "description": "unit"}],
+                "stability": 4.0,
+                "parent_id": "i-0"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(intent.id, "i-1");
+        assert_eq!(intent.provides[0].kind, InterfaceKind::Model);
+        assert_eq!(intent.provides[0].module_path, "");
+        assert_eq!(intent.constraints[0].severity, ConstraintSeverity::Required);
+        assert_eq!(intent.evidence[0].kind, EvidenceKind::TestPass);
+        assert!((intent.stability - 1.0).abs() < f64::EPSILON);
+        assert_eq!(intent.parent_id.as_deref(), Some("i-0"));
+    }
+
+    #[test]
+    fn test_intent_from_json_missing_agent() {
+        assert!(intent_from_json(r#"{"intent": "Auth"}"#).is_err());
+        assert!(intent_from_json(
+            r#"{"agent_id": "a", "intent": "x", "provides": [{"name": "U", "kind": "Nope",
+                "signature": ""}]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_intent_json_round_trip() {
+        let intent = intent_from_json(r#"{"agent_id": "agent-a", "intent": "Auth"}"#).unwrap();
+        let back = intent_from_json(&intent_to_json(&intent).unwrap()).unwrap();
+        assert_eq!(back.id, intent.id);
+        assert_eq!(back.timestamp, intent.timestamp);
+    }
+
+    #[test]
+    fn test_resolve_from_json_without_python() {
+        let graph = IntentGraph::in_memory().unwrap();
+        let intents = intents_from_json(
+            r#"[{"agent_id": "agent-a", "intent": "Auth",
+                 "provides": [{"name": "User", "kind": "Model", "signature": "id: UUID"}],
+                 "evidence": [{"kind": "CodeCommitted", "description": "landed"}]}]"#,
+        )
+        .unwrap();
+        graph.publish(&intents[0]).unwrap();
+
+        let mine = intent_from_json(
+            r#"{"agent_id": "agent-b", "intent": "Accounts",
+                "provides": [{"name": "UserModel", "kind": "Model", "signature": "id: UUID"}]}"#,
+        )
+        .unwrap();
+        let result = graph.resolve(&mine, 0.0).unwrap();
+        assert_eq!(result.adjustments[0].kind, AdjustmentKind::ConsumeInstead);
+
+        let json = resolution_to_json(&result).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["adjustments"][0]["kind"], "ConsumeInstead");
+
+        let summary: serde_json::Value =
+            serde_json::from_str(&summary_to_json(&graph.summary().unwrap()).unwrap()).unwrap();
+        assert_eq!(summary["total_intents"], 1);
+    }
+}
@@ -1,14 +1,44 @@
+use std::borrow::Cow;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result as SqlResult};
+use serde::Serialize;
 use serde_json;
 
+use crate::clock::{Clock, SystemClock};
+use crate::ids::{IdGenerator, UuidGenerator};
+use crate::matching::NameMatchMode;
 use crate::models::{
-    Adjustment, AdjustmentKind, ConflictReport, Constraint, IntentNode, InterfaceSpec,
-    ResolutionResult,
+    Adjustment, AdjustmentKind, AdoptPolicy, ConflictReport, Constraint, Evidence, IntentNode,
+    InterfaceKind, InterfaceSpec, OverlapReason, ResolutionResult, Role, SignatureAdaptation,
+    TagMatch, Winner,
 };
-use crate::stability::StabilityScorer;
+use crate::stability::{StabilityCache, StabilityScorer};
+
+/// Version of what `intent_interfaces` stores. Bump it whenever
+/// [`normalize_name`](crate::matching::normalize_name) changes, so existing
+/// graphs rebuild their index on open.
+///
+/// 1: names are singularized.
+/// 2: spec module paths are stored.
+/// 3: only `-tches` plurals drop `es` (`caches` → `cache`).
+const INDEX_VERSION: i64 = 3;
+
+/// Index hits for one resolving intent, sorted by the pair of roles each
+/// check in [`IntentGraph::check_overlaps`] compares.
+struct OverlapCandidates {
+    /// Intents providing something we provide
+    duplicates: std::collections::HashSet<String>,
+    /// Intents providing something we require
+    providers: std::collections::HashSet<String>,
+    /// Intents requiring something we require
+    requirers: std::collections::HashSet<String>,
+}
 
-/// The shared intent graph. Append-only, SQLite-backed.
+/// The shared intent graph. Append-only, SQLite-backed (evidence may be appended
+/// to published intents via [`add_evidence`](Self::add_evidence)).
 /// All agents read from and write to this structure.
 ///
 /// # Interior mutability
@@ -23,6 +53,91 @@ use crate::stability::StabilityScorer;
 pub struct IntentGraph {
     conn: Connection,
     scorer: StabilityScorer,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+    stability_cache: Option<Mutex<StabilityCache>>,
+    source: GraphSource,
+    publish_guards: Vec<PublishGuard>,
+    name_match: NameMatchMode,
+    normalize_agent_ids: bool,
+    limits: PublishLimits,
+    /// An (intent id, score) that [`stability_of`](Self::stability_of)
+    /// reports in place of scoring; see
+    /// [`with_pinned_stability`](Self::with_pinned_stability).
+    pinned_stability: std::cell::RefCell<Option<(String, f64)>>,
+}
+
+/// A policy check run on every intent before it is inserted; see
+/// [`IntentGraph::register_publish_guard`]. `Err` carries the reason.
+pub type PublishGuard = Box<dyn Fn(&IntentNode) -> Result<(), String> + Send + Sync>;
+
+/// Why a publish guard refused an intent. Publishing methods return it
+/// wrapped in [`rusqlite::Error::ToSqlConversionFailure`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PublishRejected {
+    pub intent_id: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for PublishRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "publish of {} rejected: {}", self.intent_id, self.reason)
+    }
+}
+
+impl std::error::Error for PublishRejected {}
+
+/// An exclusive lock on the graph's database, from
+/// [`IntentGraph::begin_exclusive`]. Derefs to the graph for reads.
+pub struct ExclusiveGuard<'a> {
+    graph: &'a IntentGraph,
+    /// A publish through the guard failed; roll back on drop.
+    failed: std::cell::Cell<bool>,
+    finished: bool,
+}
+
+impl ExclusiveGuard<'_> {
+    /// [`IntentGraph::publish`] inside the exclusive transaction. An error
+    /// marks the guard so that dropping it rolls back.
+    pub fn publish(&self, intent: &IntentNode) -> SqlResult<f64> {
+        let result = self.graph.publish(intent);
+        if result.is_err() {
+            self.failed.set(true);
+        }
+        result
+    }
+
+    /// Commit the transaction and release the lock, surfacing any error
+    /// that dropping the guard would swallow.
+    pub fn commit(mut self) -> SqlResult<()> {
+        self.finished = true;
+        self.graph.exclusive_end(true)
+    }
+
+    /// Discard everything published through the guard and release the lock.
+    pub fn rollback(mut self) -> SqlResult<()> {
+        self.finished = true;
+        self.graph.exclusive_end(false)
+    }
+}
+
+impl std::ops::Deref for ExclusiveGuard<'_> {
+    type Target = IntentGraph;
+
+    fn deref(&self) -> &IntentGraph {
+        self.graph
+    }
+}
+
+impl Drop for ExclusiveGuard<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let commit = !self.failed.get() && !std::thread::panicking();
+            if self.graph.exclusive_end(commit).is_err() && commit {
+                let _ = self.graph.exclusive_end(false);
+            }
+        }
+    }
 }
 
 impl IntentGraph {
@@ -32,23 +147,362 @@ impl IntentGraph {
         let graph = Self {
             conn,
             scorer: StabilityScorer::new(),
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidGenerator),
+            stability_cache: None,
+            source: GraphSource::InMemory,
+            publish_guards: Vec::new(),
+            name_match: NameMatchMode::default(),
+            normalize_agent_ids: false,
+            limits: PublishLimits::default(),
+            pinned_stability: std::cell::RefCell::new(None),
         };
         graph.init_schema()?;
         Ok(graph)
     }
 
     /// Create a new intent graph backed by a file-based SQLite database.
+    ///
+    /// SQLite falls back to read-only when the file is not writable; the
+    /// resulting mode is reported by [`source`](Self::source).
+    /// A read-only file written by an older version can't be migrated and
+    /// fails to open with `SQLITE_READONLY`; open it writable once first.
     pub fn persistent(path: &str) -> SqlResult<Self> {
         let conn = Connection::open(path)?;
+        let read_only = conn.is_readonly(rusqlite::MAIN_DB)?;
         let graph = Self {
             conn,
             scorer: StabilityScorer::new(),
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidGenerator),
+            stability_cache: None,
+            source: GraphSource::File {
+                path: path.to_string(),
+                read_only,
+            },
+            publish_guards: Vec::new(),
+            name_match: NameMatchMode::default(),
+            normalize_agent_ids: false,
+            limits: PublishLimits::default(),
+            pinned_stability: std::cell::RefCell::new(None),
         };
         graph.init_schema()?;
         Ok(graph)
     }
 
+    /// Like [`persistent`](Self::persistent), but scoring with `scorer`. Build
+    /// the scorer once and clone it into each graph to share one scoring
+    /// policy across databases. As with [`with_scorer`](Self::with_scorer),
+    /// intents already in the file keep their stored scores until rescored.
+    pub fn persistent_with_scorer(path: &str, scorer: StabilityScorer) -> SqlResult<Self> {
+        Ok(Self::persistent(path)?.with_scorer(scorer))
+    }
+
+    /// Replace the stability scorer used by this graph.
+    ///
+    /// Stored `computed_stability` values are not touched; call
+    /// [`recompute_all_stability`](Self::recompute_all_stability) to rescore
+    /// existing intents under the new weights.
+    pub fn with_scorer(mut self, scorer: StabilityScorer) -> Self {
+        self.scorer = scorer;
+        if let Some(cache) = &self.stability_cache {
+            lock_cache(cache).clear();
+        }
+        self
+    }
+
+    /// Cache up to `capacity` computed stability scores in memory.
+    ///
+    /// Off by default, so scoring stays stateless. When enabled, the overlap
+    /// checks in `resolve` reuse scores for intents whose evidence has not
+    /// changed; constraint sources report their stored score instead (see
+    /// [`find_applicable_constraints`](Self::find_applicable_constraints)).
+    /// The cache is invalidated by
+    /// [`add_evidence`](Self::add_evidence) and
+    /// [`recompute_all_stability`](Self::recompute_all_stability).
+    pub fn with_stability_cache(mut self, capacity: usize) -> Self {
+        self.stability_cache = Some(Mutex::new(StabilityCache::new(capacity)));
+        self
+    }
+
+    /// Score an intent as of the graph clock's "now", through the stability
+    /// cache when one is enabled. A scorer whose conflicts decay gives a
+    /// different answer as time passes, so it bypasses the cache.
+    fn stability_of(&self, intent: &IntentNode) -> f64 {
+        if let Some((id, pinned)) = &*self.pinned_stability.borrow() {
+            if *id == intent.id {
+                return *pinned;
+            }
+        }
+        match &self.stability_cache {
+            Some(cache) if self.scorer.conflict_half_life().is_none() => {
+                lock_cache(cache).get_or_compute(&self.scorer, intent)
+            }
+            _ => self.scorer.compute_at(intent, self.clock.now()),
+        }
+    }
+
+    /// How precisely interface names must match to count as overlapping, in
+    /// the index lookup and in the structural checks after it. Defaults to
+    /// [`NameMatchMode::Substring`].
+    pub fn with_name_match(mut self, mode: NameMatchMode) -> Self {
+        self.name_match = mode;
+        self
+    }
+
+    /// The name-match precision this graph uses.
+    pub fn name_match(&self) -> NameMatchMode {
+        self.name_match
+    }
+
+    /// Trim and lowercase agent ids, so `"Agent-A"`, `"agent-a "` and
+    /// `"agent-a"` are one agent. Applied to intents as they are published
+    /// and to agent ids passed to queries; intents published while it was
+    /// off keep their raw ids. Off by default.
+    pub fn with_normalize_agent_ids(mut self, normalize: bool) -> Self {
+        self.normalize_agent_ids = normalize;
+        self
+    }
+
+    /// Whether this graph normalizes agent ids.
+    pub fn normalize_agent_ids(&self) -> bool {
+        self.normalize_agent_ids
+    }
+
+    /// Size limits every published intent must respect; see [`PublishLimits`].
+    pub fn with_publish_limits(mut self, limits: PublishLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// The size limits this graph enforces on publish.
+    pub fn publish_limits(&self) -> &PublishLimits {
+        &self.limits
+    }
+
+    /// `agent_id` as this graph stores it.
+    fn agent_key<'a>(&self, agent_id: &'a str) -> Cow<'a, str> {
+        if self.normalize_agent_ids {
+            Cow::Owned(agent_id.trim().to_lowercase())
+        } else {
+            Cow::Borrowed(agent_id)
+        }
+    }
+
+    fn same_agent(&self, a: &str, b: &str) -> bool {
+        self.agent_key(a) == self.agent_key(b)
+    }
+
+    /// [`Constraint::binds_agent`] under this graph's agent-id normalization.
+    fn binds_agent(&self, constraint: &Constraint, agent_id: &str) -> bool {
+        if !self.normalize_agent_ids {
+            return constraint.binds_agent(agent_id);
+        }
+        let mut normalized = constraint.clone();
+        for agent in &mut normalized.applies_to_agents {
+            *agent = self.agent_key(agent).into_owned();
+        }
+        normalized.binds_agent(&self.agent_key(agent_id))
+    }
+
+    /// Replace the clock used for "now" (default intent timestamps, time-based
+    /// scoring). Defaults to [`SystemClock`].
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Replace the generator [`publish`](Self::publish) uses to assign ids to
+    /// intents that arrive with an empty one. Explicit ids are always kept.
+    /// Defaults to [`UuidGenerator`].
+    pub fn with_id_generator(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// The scorer this graph computes stability with. Clone it to give
+    /// another graph the same policy.
+    pub fn scorer(&self) -> &StabilityScorer {
+        &self.scorer
+    }
+
+    /// The id generator this graph assigns missing ids from.
+    pub fn id_generator(&self) -> &dyn IdGenerator {
+        self.ids.as_ref()
+    }
+
+    /// The current time according to this graph's clock.
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// The clock this graph uses for "now".
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    /// Where this graph is stored. Forks always report
+    /// [`GraphSource::InMemory`].
+    pub fn source(&self) -> &GraphSource {
+        &self.source
+    }
+
+    /// Fork the graph into an independent in-memory copy.
+    ///
+    /// Every intent and interface index row is copied into a fresh in-memory
+    /// database, so speculative publishes can be applied to the fork for
+    /// what-if analysis. Forks do not stay in sync: later publishes to either
+    /// graph are invisible to the other.
+    pub fn fork(&self) -> SqlResult<IntentGraph> {
+        let mut forked = IntentGraph::in_memory()?
+            .with_scorer(self.scorer.clone())
+            .with_clock(Arc::clone(&self.clock))
+            .with_id_generator(Arc::clone(&self.ids))
+            .with_name_match(self.name_match)
+            .with_normalize_agent_ids(self.normalize_agent_ids)
+            .with_publish_limits(self.limits.clone());
+        if let Some(cache) = &self.stability_cache {
+            forked = forked.with_stability_cache(lock_cache(cache).capacity());
+        }
+        let tx = forked.conn.unchecked_transaction()?;
+        for table in ["intents", "intent_interfaces", "intent_seq"] {
+            self.copy_table(&tx, table)?;
+        }
+        tx.commit()?;
+        Ok(forked)
+    }
+
+    /// Serialize every intent, oldest first, with its stored
+    /// `computed_stability`, as a compact bincode array.
+    ///
+    /// Meant for fast machine-to-machine transfer between graphs built from
+    /// the same crate version (bincode isn't self-describing, so the layout
+    /// follows the model structs); JSON via [`crate::api`] stays the
+    /// interoperable format. Load the bytes with
+    /// [`import_bincode`](Self::import_bincode).
+    #[cfg(feature = "bincode")]
+    pub fn export_bincode(&self) -> SqlResult<Vec<u8>> {
+        let intents = self.query_all(None)?;
+        let scored: Vec<(&IntentNode, Option<f64>)> =
+            intents.iter().map(|i| (i, i.computed_stability)).collect();
+        bincode::serde::encode_to_vec(&scored, bincode::config::standard())
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+    }
+
+    /// Publish every intent from an [`export_bincode`](Self::export_bincode)
+    /// payload in one transaction. Returns the number imported. Nothing is
+    /// imported if any intent fails to decode or publish, if the payload
+    /// has trailing bytes, or if its `parent_id` links close a cycle (see
+    /// [`validate_lineage`](Self::validate_lineage)).
+    ///
+    /// Each intent is rescored unless `trust_scores` is set, in which case
+    /// its exported score is stored through
+    /// [`publish_with_stability`](Self::publish_with_stability), with the
+    /// same risk: a score from other weights stands until rescored.
+    #[cfg(feature = "bincode")]
+    pub fn import_bincode(&self, bytes: &[u8], trust_scores: bool) -> SqlResult<usize> {
+        let (intents, read): (Vec<(IntentNode, Option<f64>)>, usize) =
+            bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        if read != bytes.len() {
+            return Err(rusqlite::Error::ToSqlConversionFailure(
+                format!(
+                    "{} trailing bytes after bincode payload",
+                    bytes.len() - read
+                )
+                .into(),
+            ));
+        }
+        let tx = self.conn.unchecked_transaction()?;
+        for (intent, score) in &intents {
+            let mut intent = intent.clone();
+            intent.stability = intent.stability.clamp(0.0, 1.0);
+            match score {
+                Some(score) if trust_scores => {
+                    self.publish_with_stability(&intent, *score)?;
+                }
+                _ => {
+                    self.publish(&intent)?;
+                }
+            }
+        }
+        let imported: std::collections::HashSet<&str> =
+            intents.iter().map(|(i, _)| i.id.as_str()).collect();
+        let cycles: Vec<Vec<String>> = self
+            .validate_lineage()?
+            .into_iter()
+            .filter(|cycle| cycle.iter().any(|id| imported.contains(id.as_str())))
+            .collect();
+        if !cycles.is_empty() {
+            return Err(rusqlite::Error::ToSqlConversionFailure(
+                format!("import would create lineage cycles: {:?}", cycles).into(),
+            ));
+        }
+        tx.commit()?;
+        Ok(intents.len())
+    }
+
+    /// Serialize the intents matching `filter`, oldest first, as a JSON array
+    /// in the format [`crate::api::intents_from_json`] reads.
+    ///
+    /// Ancestors of matching intents are included even when they don't match
+    /// themselves, so every `parent_id` in the export resolves within it and
+    /// the slice can be published into an empty graph.
+    pub fn export_json_filtered(&self, mut filter: ExportFilter) -> SqlResult<String> {
+        if let Some(agents) = &mut filter.agents {
+            for agent in agents.iter_mut() {
+                *agent = self.agent_key(agent).into_owned();
+            }
+        }
+        let all = self.query_all(None)?;
+        let by_id: std::collections::HashMap<&str, &IntentNode> =
+            all.iter().map(|i| (i.id.as_str(), i)).collect();
+
+        let mut selected: std::collections::HashSet<String> = std::collections::HashSet::new();
+        for intent in self.query_all(Some(filter.min_stability))? {
+            if !filter.matches(&intent) {
+                continue;
+            }
+            let mut next = Some(intent.id);
+            while let Some(id) = next {
+                next = by_id.get(id.as_str()).and_then(|i| i.parent_id.clone());
+                if !selected.insert(id) {
+                    break;
+                }
+            }
+        }
+
+        let slice: Vec<&IntentNode> = all.iter().filter(|i| selected.contains(&i.id)).collect();
+        serde_json::to_string(&slice)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+    }
+
+    /// Copy every row of `table` into the same table on `dest`, column for column.
+    fn copy_table(&self, dest: &Connection, table: &str) -> SqlResult<()> {
+        let mut stmt = self.conn.prepare(&format!("SELECT * FROM {}", table))?;
+        let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+        let placeholders: Vec<String> = (1..=columns.len()).map(|i| format!("?{}", i)).collect();
+        let mut insert = dest.prepare(&format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            table,
+            columns.join(", "),
+            placeholders.join(", ")
+        ))?;
+
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let values = (0..columns.len())
+                .map(|i| row.get::<_, rusqlite::types::Value>(i))
+                .collect::<SqlResult<Vec<_>>>()?;
+            insert.execute(rusqlite::params_from_iter(values))?;
+        }
+        Ok(())
+    }
+
     fn init_schema(&self) -> SqlResult<()> {
+        if self.conn.is_readonly(rusqlite::MAIN_DB)? {
+            return self.check_schema_current();
+        }
         self.conn.execute_batch(
             "
             CREATE TABLE IF NOT EXISTS intents (
@@ -63,6 +517,8 @@ impl IntentGraph {
                 evidence TEXT NOT NULL,      -- JSON array of Evidence
                 parent_id TEXT,
                 computed_stability REAL,
+                labels TEXT NOT NULL DEFAULT '[]', -- JSON array of strings
+                seq INTEGER,                 -- publish order, see query_after_seq
                 FOREIGN KEY (parent_id) REFERENCES intents(id)
             );
 
@@ -78,33 +534,416 @@ impl IntentGraph {
                 normalized_name TEXT NOT NULL,
                 role TEXT NOT NULL,           -- 'provides' or 'requires'
                 tags TEXT NOT NULL,           -- space-separated for FTS-like matching
+                kind TEXT NOT NULL DEFAULT '', -- InterfaceKind::as_str()
+                module_path TEXT NOT NULL DEFAULT '',
                 FOREIGN KEY (intent_id) REFERENCES intents(id)
             );
 
+            -- Last publish sequence number handed out, in a single row with
+            -- id 0. Kept apart from intents so clear() can't rewind it.
+            CREATE TABLE IF NOT EXISTS intent_seq (
+                id INTEGER PRIMARY KEY CHECK (id = 0),
+                last INTEGER NOT NULL
+            );
+
             CREATE INDEX IF NOT EXISTS idx_ifaces_name ON intent_interfaces(normalized_name);
             CREATE INDEX IF NOT EXISTS idx_ifaces_agent ON intent_interfaces(agent_id);
             CREATE INDEX IF NOT EXISTS idx_ifaces_intent ON intent_interfaces(intent_id);
             ",
         )?;
+        self.migrate_schema()?;
+        self.conn.execute_batch(
+            "CREATE INDEX IF NOT EXISTS idx_ifaces_kind ON intent_interfaces(kind);
+             CREATE INDEX IF NOT EXISTS idx_intents_seq ON intents(seq);",
+        )?;
+        Ok(())
+    }
+
+    /// Whether `table` has a column named `column`.
+    fn has_column(&self, table: &str, column: &str) -> SqlResult<bool> {
+        self.conn
+            .prepare("SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2")?
+            .exists([table, column])
+    }
+
+    /// Read-only stand-in for [`init_schema`](Self::init_schema): nothing can
+    /// be created or migrated, so a file missing any column the queries read
+    /// fails with `SQLITE_READONLY` instead of erroring on the first read.
+    /// A stale interface index or missing secondary index is tolerated.
+    fn check_schema_current(&self) -> SqlResult<()> {
+        for (table, column) in [
+            ("intents", "labels"),
+            ("intents", "seq"),
+            ("intent_interfaces", "kind"),
+            ("intent_interfaces", "module_path"),
+        ] {
+            if !self.has_column(table, column)? {
+                return Err(rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_READONLY),
+                    Some(format!(
+                        "database needs migration ({}.{} is missing); \
+                         open it writable once to upgrade",
+                        table, column
+                    )),
+                ));
+            }
+        }
         Ok(())
     }
 
+    /// Bring databases created by older versions up to the current schema.
+    /// Only called on writable files; see
+    /// [`check_schema_current`](Self::check_schema_current).
+    ///
+    /// Graphs created before intents carried `labels` get the column added,
+    /// empty for every existing intent. Graphs created before intents carried
+    /// a `seq` get one numbered in insertion order. Graphs created before the interface
+    /// index stored `kind` or `module_path` get the column added and the index
+    /// rebuilt from the stored intent JSON. The index is also rebuilt when it
+    /// was written under an older [`INDEX_VERSION`] (tracked in SQLite's
+    /// `user_version`).
+    fn migrate_schema(&self) -> SqlResult<()> {
+        if !self.has_column("intents", "labels")? {
+            self.conn.execute_batch(
+                "ALTER TABLE intents ADD COLUMN labels TEXT NOT NULL DEFAULT '[]';",
+            )?;
+        }
+        if !self.has_column("intents", "seq")? {
+            self.conn.execute_batch(
+                "ALTER TABLE intents ADD COLUMN seq INTEGER;
+                 UPDATE intents SET seq = rowid;",
+            )?;
+        }
+
+        let has_kind = self.has_column("intent_interfaces", "kind")?;
+        let has_module_path = self.has_column("intent_interfaces", "module_path")?;
+        let index_version: i64 = self
+            .conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if has_kind && has_module_path && index_version >= INDEX_VERSION {
+            return Ok(());
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        if !has_kind {
+            tx.execute_batch(
+                "ALTER TABLE intent_interfaces ADD COLUMN kind TEXT NOT NULL DEFAULT '';",
+            )?;
+        }
+        if !has_module_path {
+            tx.execute_batch(
+                "ALTER TABLE intent_interfaces ADD COLUMN module_path TEXT NOT NULL DEFAULT '';",
+            )?;
+        }
+        tx.execute_batch("DELETE FROM intent_interfaces;")?;
+        // Read only the columns the index needs, so a row that fails to load
+        // as an intent (a malformed timestamp, say) can't block the upgrade.
+        let rows: Vec<(String, String, String, String)> = {
+            let mut stmt = tx.prepare("SELECT id, agent_id, provides, requires FROM intents")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?;
+            rows.collect::<SqlResult<_>>()?
+        };
+        for (id, agent_id, provides, requires) in rows {
+            let provides: Vec<InterfaceSpec> = serde_json::from_str(&provides).unwrap_or_default();
+            let requires: Vec<InterfaceSpec> = serde_json::from_str(&requires).unwrap_or_default();
+            self.index_interfaces(&id, &agent_id, "provides", &provides)?;
+            self.index_interfaces(&id, &agent_id, "requires", &requires)?;
+        }
+        tx.pragma_update(None, "user_version", INDEX_VERSION)?;
+        tx.commit()
+    }
+
     /// Publish an intent to the graph. Append-only — once published, cannot be modified.
     /// Returns the computed stability score.
     ///
     /// Also populates the denormalized `intent_interfaces` table for fast
-    /// overlap queries (see [`find_overlapping`](Self::find_overlapping)).
+    /// overlap queries (see [`find_overlapping`](Self::find_overlapping)). An
+    /// intent with an empty `id` is stored under one from the graph's
+    /// [`id_generator`](Self::id_generator); use
+    /// [`publish_verbose`](Self::publish_verbose) to learn which.
     pub fn publish(&self, intent: &IntentNode) -> SqlResult<f64> {
-        let computed_stability = self.scorer.compute(intent);
+        Ok(self.publish_verbose(intent)?.stability)
+    }
+
+    /// Publish an intent and report how its interfaces collide with the graph
+    /// as it stood before the publish.
+    ///
+    /// Runs only the provision/requirement overlap checks from
+    /// [`resolve`](Self::resolve) — no constraint adoption — against intents at
+    /// or above `min_stability`. Returns the computed stability and any
+    /// conflicts; the intent is published either way.
+    pub fn publish_and_check(
+        &self,
+        intent: &IntentNode,
+        min_stability: f64,
+    ) -> SqlResult<(f64, Vec<ConflictReport>)> {
+        let mut adjustments = Vec::new();
+        let mut conflicts = Vec::new();
+        self.check_overlaps(
+            intent,
+            min_stability,
+            &mut adjustments,
+            &mut conflicts,
+            None,
+        )?;
+        let stability = self.publish(intent)?;
+        Ok((stability, conflicts))
+    }
+
+    /// The distinct other intents at or above `min_stability` that `intent`
+    /// would conflict with if published, oldest first.
+    ///
+    /// Runs only the provision/requirement overlap checks, as
+    /// [`publish_and_check`](Self::publish_and_check) does: constraint
+    /// clashes don't count, and an intent that should simply consume a more
+    /// stable provider gets an adjustment from [`resolve`](Self::resolve)
+    /// rather than a conflict, so it isn't listed. Nothing is written.
+    pub fn would_conflict_with(
+        &self,
+        intent: &IntentNode,
+        min_stability: f64,
+    ) -> SqlResult<Vec<IntentNode>> {
+        let mut adjustments = Vec::new();
+        let mut conflicts = Vec::new();
+        self.check_overlaps(
+            intent,
+            min_stability,
+            &mut adjustments,
+            &mut conflicts,
+            None,
+        )?;
+        let mut ids: Vec<&String> = conflicts.iter().map(|c| &c.their_intent_id).collect();
+        ids.sort();
+        ids.dedup();
+        self.load_intents(&ids)
+    }
+
+    /// Like [`publish`](Self::publish), but also reports non-fatal problems
+    /// with the intent. Currently this flags specs within one `provides` or
+    /// `requires` list whose names normalize identically (e.g. `User` and
+    /// `UserModel`), which leave ambiguous rows in the interface index.
+    pub fn publish_verbose(&self, intent: &IntentNode) -> SqlResult<PublishStats> {
+        let computed_stability = self.scorer.compute_at(intent, self.clock.now());
+        let mut warnings = name_collisions("provides", &intent.provides);
+        warnings.extend(name_collisions("requires", &intent.requires));
+
+        let id = self.insert_intent(intent, computed_stability)?;
+
+        Ok(PublishStats {
+            id,
+            stability: computed_stability,
+            warnings,
+        })
+    }
+
+    /// Check an intent for problems before publishing it. Returns every issue
+    /// found, errors and warnings alike; an empty list means the intent is clean.
+    ///
+    /// Errors are things [`publish`](Self::publish) rejects or that leave the
+    /// intent meaningless: a `parent_id` not in the graph, and blank agent ids,
+    /// intent text, spec names, or constraint targets/requirements. Warnings
+    /// degrade matching without blocking publish: signature parts
+    /// [`parse_signature`](crate::matching::parse_signature) can't read, and
+    /// specs whose names collide within one section (as in
+    /// [`publish_verbose`](Self::publish_verbose)).
+    pub fn validate_intent(&self, intent: &IntentNode) -> SqlResult<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+        let mut blank = |value: &str, what: String| {
+            if value.trim().is_empty() {
+                issues.push(ValidationIssue::error(
+                    IssueKind::EmptyField,
+                    format!("{} is empty", what),
+                ));
+            }
+        };
+        blank(&intent.agent_id, "agent_id".to_string());
+        blank(&intent.intent, "intent".to_string());
+        for (section, specs) in [
+            ("provides", &intent.provides),
+            ("requires", &intent.requires),
+        ] {
+            for (i, spec) in specs.iter().enumerate() {
+                blank(&spec.name, format!("{}[{}].name", section, i));
+            }
+        }
+        for (i, constraint) in intent.constraints.iter().enumerate() {
+            blank(&constraint.target, format!("constraints[{}].target", i));
+            blank(
+                &constraint.requirement,
+                format!("constraints[{}].requirement", i),
+            );
+        }
+
+        if let Some(parent_id) = &intent.parent_id {
+            if self.get(parent_id)?.is_none() {
+                issues.push(ValidationIssue::error(
+                    IssueKind::DanglingParent,
+                    format!("parent_id '{}' is not in the graph", parent_id),
+                ));
+            }
+        }
+
+        for (section, specs) in [
+            ("provides", &intent.provides),
+            ("requires", &intent.requires),
+        ] {
+            for spec in specs {
+                if spec.kind == InterfaceKind::Endpoint {
+                    if !spec.signature.trim().is_empty()
+                        && crate::matching::parse_endpoint_signature(&spec.signature).is_none()
+                    {
+                        issues.push(ValidationIssue::warning(
+                            IssueKind::UnparseableSignature,
+                            format!(
+                                "{} '{}': '{}' is not METHOD /path [(body: Type)] [-> Type]",
+                                section, spec.name, spec.signature
+                            ),
+                        ));
+                    }
+                    continue;
+                }
+                let unparsed = crate::matching::unparsed_signature_parts(&spec.signature);
+                if !unparsed.is_empty() {
+                    issues.push(ValidationIssue::warning(
+                        IssueKind::UnparseableSignature,
+                        format!(
+                            "{} '{}': can't read '{}' as field: type",
+                            section,
+                            spec.name,
+                            unparsed.join("', '")
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let collisions = name_collisions("provides", &intent.provides)
+            .into_iter()
+            .chain(name_collisions("requires", &intent.requires));
+        for collision in collisions {
+            issues.push(ValidationIssue::warning(
+                IssueKind::DuplicateName,
+                format!(
+                    "{} {} all normalize to '{}'",
+                    collision.section,
+                    collision.names.join(", "),
+                    collision.normalized_name
+                ),
+            ));
+        }
+
+        Ok(issues)
+    }
+
+    /// Publish an intent with a caller-supplied `computed_stability` (clamped
+    /// to [0.0, 1.0]) instead of running the scorer.
+    ///
+    /// Meant for importers re-publishing intents whose scores were already
+    /// computed elsewhere, such as `import_bincode` (with the `bincode`
+    /// feature) under `trust_scores`. The
+    /// stored value is trusted as-is: if it came from different weights or is
+    /// simply wrong, `min_stability` filtering and constraint sources will act
+    /// on it until [`recompute_all_stability`](Self::recompute_all_stability)
+    /// is run. Returns the id the intent was stored under. A non-finite
+    /// value fails with `ToSqlConversionFailure`: SQLite would store NaN as
+    /// NULL, hiding the intent from every stability filter.
+    pub fn publish_with_stability(&self, intent: &IntentNode, computed: f64) -> SqlResult<String> {
+        if !computed.is_finite() {
+            return Err(rusqlite::Error::ToSqlConversionFailure(
+                format!("computed stability must be finite, got {}", computed).into(),
+            ));
+        }
+        self.insert_intent(intent, computed.clamp(0.0, 1.0))
+    }
+
+    /// Add a policy check that every later publish must pass, e.g. "migrations
+    /// need manual approval". Guards run in registration order before the
+    /// intent is inserted; the first `Err` aborts the publish with a
+    /// [`PublishRejected`] and leaves the graph untouched. Guards apply to
+    /// every method that inserts intents, imports included, but are not
+    /// carried over by [`fork`](Self::fork).
+    pub fn register_publish_guard(&mut self, guard: PublishGuard) {
+        self.publish_guards.push(guard);
+    }
+
+    /// Take an exclusive lock on the database for the lifetime of the
+    /// returned guard, so an external coordinator can run a batch of
+    /// publishes that no other connection observes half-done.
+    ///
+    /// Issues `BEGIN EXCLUSIVE`; while the guard lives, other connections to
+    /// the same file wait (up to their busy timeout) and then fail with
+    /// `DatabaseBusy`. Publishes made through the guard commit when it is
+    /// dropped or [`commit`](ExclusiveGuard::commit)ted, and roll back if a
+    /// publish through it failed or the thread is panicking. Methods that open
+    /// their own transaction (e.g. [`resolve_and_apply`](Self::resolve_and_apply))
+    /// cannot nest inside the guard and fail.
+    pub fn begin_exclusive(&self) -> SqlResult<ExclusiveGuard<'_>> {
+        self.exclusive_begin()?;
+        Ok(ExclusiveGuard {
+            graph: self,
+            failed: std::cell::Cell::new(false),
+            finished: false,
+        })
+    }
 
+    /// Start the `BEGIN EXCLUSIVE` transaction behind
+    /// [`begin_exclusive`](Self::begin_exclusive). Split out for bindings
+    /// that can't hold a borrowing guard.
+    pub(crate) fn exclusive_begin(&self) -> SqlResult<()> {
+        self.conn.execute_batch("BEGIN EXCLUSIVE")
+    }
+
+    /// End the transaction started by
+    /// [`exclusive_begin`](Self::exclusive_begin).
+    pub(crate) fn exclusive_end(&self, commit: bool) -> SqlResult<()> {
+        self.conn
+            .execute_batch(if commit { "COMMIT" } else { "ROLLBACK" })
+    }
+
+    /// Insert the intent row and its interface index entries. Returns the id
+    /// it was stored under, generated if the intent had none.
+    fn insert_intent(&self, intent: &IntentNode, computed_stability: f64) -> SqlResult<String> {
+        let mut prepared: Option<IntentNode> = None;
+        if intent.id.is_empty() {
+            prepared.get_or_insert_with(|| intent.clone()).id = self.ids.next_id();
+        }
+        if let Cow::Owned(agent_id) = self.agent_key(&intent.agent_id) {
+            if agent_id != intent.agent_id {
+                prepared.get_or_insert_with(|| intent.clone()).agent_id = agent_id;
+            }
+        }
+        let intent = prepared.as_ref().unwrap_or(intent);
+        let rejected = |reason| {
+            rusqlite::Error::ToSqlConversionFailure(Box::new(PublishRejected {
+                intent_id: intent.id.clone(),
+                reason,
+            }))
+        };
+        self.limits.check(intent).map_err(rejected)?;
+        for guard in &self.publish_guards {
+            guard(intent).map_err(rejected)?;
+        }
+        // Never below the stored maximum, so rows copied or migrated in
+        // without touching the counter can't be renumbered over
+        let seq: i64 = self.conn.query_row(
+            "INSERT INTO intent_seq (id, last)
+             VALUES (0, (SELECT COALESCE(MAX(seq), 0) FROM intents) + 1)
+             ON CONFLICT (id) DO UPDATE
+                 SET last = MAX(last, (SELECT COALESCE(MAX(seq), 0) FROM intents)) + 1
+             RETURNING last",
+            [],
+            |row| row.get(0),
+        )?;
         self.conn.execute(
             "INSERT INTO intents (id, agent_id, timestamp, intent, provides, requires,
-             constraints, stability, evidence, parent_id, computed_stability)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+             constraints, stability, evidence, parent_id, computed_stability, labels, seq)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
                 intent.id,
                 intent.agent_id,
-                intent.timestamp.to_rfc3339(),
+                sql_timestamp(&intent.timestamp),
                 intent.intent,
                 serde_json::to_string(&intent.provides).unwrap_or_default(),
                 serde_json::to_string(&intent.requires).unwrap_or_default(),
@@ -113,68 +952,258 @@ impl IntentGraph {
                 serde_json::to_string(&intent.evidence).unwrap_or_default(),
                 intent.parent_id,
                 computed_stability,
+                serde_json::to_string(&intent.labels).unwrap_or_default(),
+                seq,
             ],
         )?;
 
         // Populate denormalized interface lookup table
-        self.index_interfaces(intent, "provides", &intent.provides)?;
-        self.index_interfaces(intent, "requires", &intent.requires)?;
-
-        Ok(computed_stability)
+        self.index_interfaces(&intent.id, &intent.agent_id, "provides", &intent.provides)?;
+        self.index_interfaces(&intent.id, &intent.agent_id, "requires", &intent.requires)?;
+        Ok(intent.id.clone())
     }
 
-    /// Insert denormalized interface entries for fast overlap lookup.
-    fn index_interfaces(
-        &self,
-        intent: &IntentNode,
-        role: &str,
-        specs: &[InterfaceSpec],
-    ) -> SqlResult<()> {
-        for spec in specs {
-            let normalized = crate::matching::normalize_name(&spec.name);
-            let tags_str = spec.tags.join(" ");
-            self.conn.execute(
-                "INSERT INTO intent_interfaces (intent_id, agent_id, normalized_name, role, tags)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![intent.id, intent.agent_id, normalized, role, tags_str],
-            )?;
-        }
-        Ok(())
-    }
+    /// Append evidence to an already-published intent.
+    ///
+    /// This is the one sanctioned mutation of a published intent: evidence
+    /// naturally accrues after publication (tests pass later, other agents
+    /// consume the interface), and forcing a full supersession for each new
+    /// result would bloat the graph. The intent's decision content — provides,
+    /// requires, constraints — stays immutable.
+    ///
+    /// Recomputes and persists stability, returning the new score. Fails with
+    /// `QueryReturnedNoRows` if no intent has the given id.
+    pub fn add_evidence(&self, id: &str, evidence: &[Evidence]) -> SqlResult<f64> {
+        let mut intent = self.get(id)?.ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+        intent.evidence.extend(evidence.iter().cloned());
+        let computed_stability = self.scorer.compute_at(&intent, self.clock.now());
 
-    /// Query all intents, optionally filtered by minimum stability.
-    pub fn query_all(&self, min_stability: Option<f64>) -> SqlResult<Vec<IntentNode>> {
-        let min_stab = min_stability.unwrap_or(0.0);
-        let mut stmt = self.conn.prepare(
-            "SELECT id, agent_id, timestamp, intent, provides, requires, 
-                    constraints, stability, evidence, parent_id, computed_stability
-             FROM intents
-             WHERE computed_stability >= ?1
-             ORDER BY timestamp ASC",
+        self.conn.execute(
+            "UPDATE intents SET evidence = ?1, computed_stability = ?2 WHERE id = ?3",
+            params![
+                serde_json::to_string(&intent.evidence).unwrap_or_default(),
+                computed_stability,
+                id,
+            ],
         )?;
+        if let Some(cache) = &self.stability_cache {
+            lock_cache(cache).invalidate(id);
+        }
 
-        let intents = stmt
-            .query_map(params![min_stab], |row| Ok(self.row_to_intent(row)))?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        Ok(intents)
+        Ok(computed_stability)
+    }
+
+    /// Re-resolve the intents whose outcome may have moved when intent `id`'s
+    /// stability changed from `previous_stability`, e.g. after
+    /// [`add_evidence`](Self::add_evidence): the intent itself, then every
+    /// other agent's intent at or above `min_stability` whose interfaces
+    /// overlap it, in timestamp order.
+    ///
+    /// Each is resolved twice, once as if `id` still scored
+    /// `previous_stability`, and only the `(intent id, result)` pairs whose
+    /// outcome differs are returned: a changed adjustment, conflict winner,
+    /// or adopted or rejected constraint. Stability figures alone don't count.
+    /// Fails with `QueryReturnedNoRows` if no intent has the given id.
+    pub fn reresolve_affected(
+        &self,
+        id: &str,
+        previous_stability: f64,
+        min_stability: f64,
+    ) -> SqlResult<Vec<(String, ResolutionResult)>> {
+        let intent = self.get(id)?.ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+        let specs: Vec<InterfaceSpec> = intent
+            .provides
+            .iter()
+            .chain(&intent.requires)
+            .cloned()
+            .collect();
+        let overlapping = self.find_overlapping(&specs, &intent.agent_id, min_stability)?;
+        let affected: Vec<&IntentNode> = std::iter::once(&intent).chain(&overlapping).collect();
+
+        let resolve_all = || {
+            affected
+                .iter()
+                .map(|i| self.resolve(i, min_stability))
+                .collect::<SqlResult<Vec<_>>>()
+        };
+        let before = self.with_pinned_stability(id, previous_stability, resolve_all)?;
+        let after = resolve_all()?;
+
+        Ok(affected
+            .iter()
+            .zip(before.iter().zip(after))
+            .filter(|(_, (before, after))| !same_outcome(before, after))
+            .map(|(intent, (_, after))| (intent.id.clone(), after))
+            .collect())
+    }
+
+    /// Run `f` as if intent `id` scored `stability`: in ranking and, inside a
+    /// savepoint rolled back afterwards, in the stored score the SQL
+    /// stability filters read.
+    fn with_pinned_stability<T>(
+        &self,
+        id: &str,
+        stability: f64,
+        f: impl FnOnce() -> SqlResult<T>,
+    ) -> SqlResult<T> {
+        self.conn.execute_batch("SAVEPOINT pinned_stability")?;
+        *self.pinned_stability.borrow_mut() = Some((id.to_string(), stability));
+        let result = self
+            .conn
+            .execute(
+                "UPDATE intents SET computed_stability = ?1 WHERE id = ?2",
+                params![stability, id],
+            )
+            .and_then(|_| f());
+        *self.pinned_stability.borrow_mut() = None;
+        self.conn
+            .execute_batch("ROLLBACK TO pinned_stability; RELEASE pinned_stability")?;
+        result
+    }
+
+    /// Re-run the scorer over every intent and persist the results.
+    ///
+    /// Use after changing scoring weights so that stability-filtered queries
+    /// see fresh values. Runs in a single transaction and returns the number of
+    /// intents rescored.
+    pub fn recompute_all_stability(&self) -> SqlResult<usize> {
+        self.recompute_stability_inner().map(|(total, _)| total)
+    }
+
+    /// Like [`recompute_all_stability`](Self::recompute_all_stability), but
+    /// returns the ids of intents whose stored `computed_stability` actually
+    /// changed, in timestamp order.
+    pub fn recompute_stability_changed(&self) -> SqlResult<Vec<String>> {
+        self.recompute_stability_inner().map(|(_, changed)| changed)
+    }
+
+    /// Rescore every intent, writing only changed values.
+    /// Returns (intents rescored, ids whose stored score changed).
+    fn recompute_stability_inner(&self) -> SqlResult<(usize, Vec<String>)> {
+        if let Some(cache) = &self.stability_cache {
+            lock_cache(cache).clear();
+        }
+        let tx = self.conn.unchecked_transaction()?;
+        let stored: std::collections::HashMap<String, Option<f64>> = {
+            let mut stmt = tx.prepare("SELECT id, computed_stability FROM intents")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<SqlResult<_>>()?
+        };
+
+        let intents = self.query_all(None)?;
+        let now = self.clock.now();
+        let mut changed = Vec::new();
+        for intent in &intents {
+            let computed = self.scorer.compute_at(intent, now);
+            let previous = stored.get(&intent.id).copied().flatten();
+            if previous.is_some_and(|p| (p - computed).abs() < f64::EPSILON) {
+                continue;
+            }
+            tx.execute(
+                "UPDATE intents SET computed_stability = ?1 WHERE id = ?2",
+                params![computed, intent.id],
+            )?;
+            changed.push(intent.id.clone());
+        }
+        tx.commit()?;
+        Ok((intents.len(), changed))
+    }
+
+    /// Insert denormalized interface entries for fast overlap lookup.
+    fn index_interfaces(
+        &self,
+        intent_id: &str,
+        agent_id: &str,
+        role: &str,
+        specs: &[InterfaceSpec],
+    ) -> SqlResult<()> {
+        for spec in specs {
+            let normalized = crate::matching::normalize_name(&spec.name);
+            let tags_str = spec.tags.join(" ");
+            self.conn.execute(
+                "INSERT INTO intent_interfaces
+                 (intent_id, agent_id, normalized_name, role, tags, kind, module_path)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    intent_id,
+                    agent_id,
+                    normalized,
+                    role,
+                    tags_str,
+                    spec.kind.as_str(),
+                    spec.module_path
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Query all intents, optionally filtered by minimum stability.
+    pub fn query_all(&self, min_stability: Option<f64>) -> SqlResult<Vec<IntentNode>> {
+        let min_stab = min_stability.unwrap_or(0.0);
+        let mut stmt = self.conn.prepare(
+            "SELECT id, agent_id, timestamp, intent, provides, requires, 
+                    constraints, stability, evidence, parent_id, computed_stability, labels
+             FROM intents
+             WHERE computed_stability >= ?1
+             ORDER BY timestamp ASC",
+        )?;
+
+        let intents = stmt
+            .query_map(params![min_stab], |row| self.row_to_intent(row))?
+            .collect::<SqlResult<_>>()?;
+
+        Ok(intents)
+    }
+
+    /// Fetch the given intents in timestamp order, the same order
+    /// [`query_all`](Self::query_all) returns them in.
+    fn load_intents(&self, ids: &[&String]) -> SqlResult<Vec<IntentNode>> {
+        let ids_json = serde_json::to_string(ids)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT id, agent_id, timestamp, intent, provides, requires,
+                    constraints, stability, evidence, parent_id, computed_stability, labels
+             FROM intents
+             WHERE id IN (SELECT value FROM json_each(?1))
+             ORDER BY timestamp ASC",
+        )?;
+
+        let intents = stmt
+            .query_map(params![ids_json], |row| self.row_to_intent(row))?
+            .collect::<SqlResult<_>>()?;
+
+        Ok(intents)
+    }
+
+    /// Fetch a single intent by id.
+    pub fn get(&self, id: &str) -> SqlResult<Option<IntentNode>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, agent_id, timestamp, intent, provides, requires,
+                    constraints, stability, evidence, parent_id, computed_stability, labels
+             FROM intents
+             WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query_map(params![id], |row| self.row_to_intent(row))?;
+        rows.next().transpose()
     }
 
     /// Query intents from a specific agent.
     pub fn query_by_agent(&self, agent_id: &str) -> SqlResult<Vec<IntentNode>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, agent_id, timestamp, intent, provides, requires,
-                    constraints, stability, evidence, parent_id, computed_stability
+                    constraints, stability, evidence, parent_id, computed_stability, labels
              FROM intents
              WHERE agent_id = ?1
              ORDER BY timestamp ASC",
         )?;
 
         let intents = stmt
-            .query_map(params![agent_id], |row| Ok(self.row_to_intent(row)))?
-            .filter_map(|r| r.ok())
-            .collect();
+            .query_map(params![self.agent_key(agent_id)], |row| {
+                self.row_to_intent(row)
+            })?
+            .collect::<SqlResult<_>>()?;
 
         Ok(intents)
     }
@@ -188,22 +1217,263 @@ impl IntentGraph {
         let min_stab = min_stability.unwrap_or(0.0);
         let mut stmt = self.conn.prepare(
             "SELECT id, agent_id, timestamp, intent, provides, requires,
-                    constraints, stability, evidence, parent_id, computed_stability
+                    constraints, stability, evidence, parent_id, computed_stability, labels
              FROM intents
              WHERE timestamp > ?1 AND computed_stability >= ?2
              ORDER BY timestamp ASC",
         )?;
 
         let intents = stmt
-            .query_map(params![since.to_rfc3339(), min_stab], |row| {
-                Ok(self.row_to_intent(row))
+            .query_map(params![sql_timestamp(&since), min_stab], |row| {
+                self.row_to_intent(row)
             })?
-            .filter_map(|r| r.ok())
-            .collect();
+            .collect::<SqlResult<_>>()?;
+
+        Ok(intents)
+    }
+
+    /// Intents published after sequence number `seq`, in publish order, and
+    /// the highest sequence number among them (`seq` itself if there are
+    /// none). Every publish takes the next number, so passing the returned
+    /// number back in reads each intent exactly once, however many share a
+    /// timestamp. Start a cursor at 0. Numbers are never reused, even after
+    /// [`clear`](Self::clear), so an old cursor stays valid.
+    pub fn query_after_seq(
+        &self,
+        seq: i64,
+        min_stability: Option<f64>,
+    ) -> SqlResult<(Vec<IntentNode>, i64)> {
+        let min_stab = min_stability.unwrap_or(0.0);
+        let mut stmt = self.conn.prepare(
+            "SELECT id, agent_id, timestamp, intent, provides, requires,
+                    constraints, stability, evidence, parent_id, computed_stability, labels, seq
+             FROM intents
+             WHERE seq > ?1 AND computed_stability >= ?2
+             ORDER BY seq ASC",
+        )?;
+
+        let mut last = seq;
+        let intents = stmt
+            .query_map(params![seq, min_stab], |row| {
+                Ok((self.row_to_intent(row)?, row.get::<_, i64>(12)?))
+            })?
+            .map(|row| {
+                let (intent, row_seq) = row?;
+                last = last.max(row_seq);
+                Ok(intent)
+            })
+            .collect::<SqlResult<_>>()?;
+
+        Ok((intents, last))
+    }
+
+    /// Query intents that provide or require an interface of the given kind.
+    ///
+    /// `role` optionally restricts the match to `"provides"` or `"requires"`
+    /// entries of the interface index.
+    pub fn query_by_kind(
+        &self,
+        kind: InterfaceKind,
+        role: Option<&str>,
+        min_stability: f64,
+    ) -> SqlResult<Vec<IntentNode>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, agent_id, timestamp, intent, provides, requires,
+                    constraints, stability, evidence, parent_id, computed_stability, labels
+             FROM intents
+             WHERE computed_stability >= ?1
+               AND id IN (SELECT intent_id FROM intent_interfaces
+                          WHERE kind = ?2 AND (?3 IS NULL OR role = ?3))
+             ORDER BY timestamp ASC",
+        )?;
+
+        let intents = stmt
+            .query_map(params![min_stability, kind.as_str(), role], |row| {
+                self.row_to_intent(row)
+            })?
+            .collect::<SqlResult<_>>()?;
+
+        Ok(intents)
+    }
+
+    /// Intents published strictly after `since` that provide a
+    /// [`Migration`](InterfaceKind::Migration), oldest first, so a
+    /// coordinating agent can replay pending schema changes in order.
+    pub fn migrations_since(&self, since: DateTime<Utc>) -> SqlResult<Vec<IntentNode>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, agent_id, timestamp, intent, provides, requires,
+                    constraints, stability, evidence, parent_id, computed_stability, labels
+             FROM intents
+             WHERE timestamp > ?1
+               AND id IN (SELECT intent_id FROM intent_interfaces
+                          WHERE kind = ?2 AND role = 'provides')
+             ORDER BY timestamp ASC",
+        )?;
+
+        let intents = stmt
+            .query_map(
+                params![sql_timestamp(&since), InterfaceKind::Migration.as_str()],
+                |row| self.row_to_intent(row),
+            )?
+            .collect::<SqlResult<_>>()?;
+
+        Ok(intents)
+    }
+
+    /// Query intents with a provided or required spec whose `module_path`
+    /// starts with `prefix` (case-sensitive, plain string prefix, so
+    /// `src/auth` also matches `src/authz`), oldest first. Specs without a
+    /// module path never match.
+    pub fn query_by_module_prefix(
+        &self,
+        prefix: &str,
+        min_stability: f64,
+    ) -> SqlResult<Vec<IntentNode>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, agent_id, timestamp, intent, provides, requires,
+                    constraints, stability, evidence, parent_id, computed_stability, labels
+             FROM intents
+             WHERE computed_stability >= ?1
+               AND id IN (SELECT intent_id FROM intent_interfaces
+                          WHERE module_path != ''
+                            AND substr(module_path, 1, length(?2)) = ?2)
+             ORDER BY timestamp ASC",
+        )?;
+
+        let intents = stmt
+            .query_map(params![min_stability, prefix], |row| {
+                self.row_to_intent(row)
+            })?
+            .collect::<SqlResult<_>>()?;
+
+        Ok(intents)
+    }
+
+    /// Query intents carrying `label` (exact, case-sensitive) among their
+    /// [`labels`](IntentNode::labels), oldest first.
+    pub fn query_by_label(&self, label: &str, min_stability: f64) -> SqlResult<Vec<IntentNode>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, agent_id, timestamp, intent, provides, requires,
+                    constraints, stability, evidence, parent_id, computed_stability, labels
+             FROM intents
+             WHERE computed_stability >= ?1
+               AND EXISTS (SELECT 1 FROM json_each(labels) WHERE value = ?2)
+             ORDER BY timestamp ASC",
+        )?;
+
+        let intents = stmt
+            .query_map(params![min_stability, label], |row| self.row_to_intent(row))?
+            .collect::<SqlResult<_>>()?;
+
+        Ok(intents)
+    }
+
+    /// Query intents by the tags on their provided or required interfaces.
+    ///
+    /// Tags match exactly (case-sensitive, whole tag). With [`TagMatch::All`]
+    /// an intent must carry every tag across its interfaces; with
+    /// [`TagMatch::Any`] one is enough. An empty tag list matches nothing.
+    pub fn query_by_tags(
+        &self,
+        tags: &[&str],
+        mode: TagMatch,
+        min_stability: f64,
+    ) -> SqlResult<Vec<IntentNode>> {
+        if tags.is_empty() {
+            return Ok(Vec::new());
+        }
+        let tags_json = serde_json::to_string(tags)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let required: i64 = match mode {
+            TagMatch::All => tags.iter().collect::<std::collections::HashSet<_>>().len() as i64,
+            TagMatch::Any => 1,
+        };
+
+        let mut stmt = self.conn.prepare(
+            "SELECT id, agent_id, timestamp, intent, provides, requires,
+                    constraints, stability, evidence, parent_id, computed_stability, labels
+             FROM intents
+             WHERE computed_stability >= ?1
+               AND id IN (SELECT ii.intent_id
+                          FROM intent_interfaces ii
+                          JOIN json_each(?2) t
+                            ON instr(' ' || ii.tags || ' ', ' ' || t.value || ' ') > 0
+                          GROUP BY ii.intent_id
+                          HAVING COUNT(DISTINCT t.value) >= ?3)
+             ORDER BY timestamp ASC",
+        )?;
+
+        let intents = stmt
+            .query_map(params![min_stability, tags_json, required], |row| {
+                self.row_to_intent(row)
+            })?
+            .collect::<SqlResult<_>>()?;
 
         Ok(intents)
     }
 
+    /// Who introduced an interface and who touches it now. Specs match
+    /// `name` by normalized name through the interface index (`User` and
+    /// `UserModel` are the same interface). Only intents at or above
+    /// `min_stability` count.
+    pub fn interface_provenance(
+        &self,
+        name: &str,
+        min_stability: f64,
+    ) -> SqlResult<InterfaceProvenance> {
+        let normalized = crate::matching::normalize_name(name);
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT id, agent_id, timestamp, intent, provides, requires,
+                    constraints, stability, evidence, parent_id, computed_stability, labels
+             FROM intents
+             WHERE computed_stability >= ?1
+               AND id IN (SELECT intent_id FROM intent_interfaces
+                          WHERE normalized_name = ?2 AND role = ?3)
+             ORDER BY timestamp ASC",
+        )?;
+        let mut with_role = |role: &str| -> SqlResult<Vec<IntentNode>> {
+            let intents = stmt
+                .query_map(params![min_stability, normalized, role], |row| {
+                    self.row_to_intent(row)
+                })?
+                .collect::<SqlResult<_>>()?;
+            Ok(intents)
+        };
+
+        let providers = with_role("provides")?;
+        let requirers = with_role("requires")?;
+        Ok(InterfaceProvenance {
+            name: normalized.clone(),
+            earliest_provider: providers.first().cloned(),
+            providers,
+            requirers,
+        })
+    }
+
+    /// Every distinct interface tag with the number of interface index rows
+    /// carrying it, most used first (ties by tag). Near-duplicates such as
+    /// `auth` and `authentication` show up side by side here, though overlap
+    /// detection treats them as unrelated.
+    pub fn all_tags(&self) -> SqlResult<Vec<(String, usize)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT tags FROM intent_interfaces WHERE tags != ''")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        for tags in rows {
+            let tags = tags?;
+            let distinct: std::collections::HashSet<&str> = tags.split_whitespace().collect();
+            for tag in distinct {
+                *counts.entry(tag.to_string()).or_default() += 1;
+            }
+        }
+
+        let mut tags: Vec<(String, usize)> = counts.into_iter().collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(tags)
+    }
+
     /// Find all intents that provide or require interfaces overlapping with the given specs.
     /// This is the core query for the intent resolver.
     ///
@@ -211,67 +1481,49 @@ impl IntentGraph {
     /// candidate filter (indexed name/tag lookup), then validates candidates
     /// with full structural overlap checks. This avoids the O(n) JSON
     /// deserialization scan that the naive approach requires.
+    ///
+    /// Intents from `exclude_agent` are skipped; see
+    /// [`find_overlapping_with`](Self::find_overlapping_with) to include them.
     pub fn find_overlapping(
         &self,
         specs: &[InterfaceSpec],
         exclude_agent: &str,
         min_stability: f64,
     ) -> SqlResult<Vec<IntentNode>> {
+        self.find_overlapping_with(
+            specs,
+            exclude_agent,
+            min_stability,
+            OverlapOptions::default(),
+        )
+    }
+
+    /// Like [`find_overlapping`](Self::find_overlapping), tuned by `options`.
+    pub fn find_overlapping_with(
+        &self,
+        specs: &[InterfaceSpec],
+        exclude_agent: &str,
+        min_stability: f64,
+        options: OverlapOptions,
+    ) -> SqlResult<Vec<IntentNode>> {
+        let role_filter = options.role_filter;
         if specs.is_empty() {
             return Ok(Vec::new());
         }
 
         // Phase 1: Fast indexed candidate lookup via denormalized table.
-        // Find intent IDs that have matching normalized names or >=2 shared tags.
-        let mut candidate_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
-
-        for spec in specs {
-            let normalized = crate::matching::normalize_name(&spec.name);
-
-            // Name-based candidates: normalized name overlap
-            let mut name_stmt = self.conn.prepare(
-                "SELECT DISTINCT ii.intent_id
-                 FROM intent_interfaces ii
-                 JOIN intents i ON i.id = ii.intent_id
-                 WHERE ii.agent_id != ?1
-                   AND i.computed_stability >= ?2
-                   AND (ii.normalized_name = ?3
-                        OR ii.normalized_name LIKE ?4
-                        OR ?3 LIKE '%' || ii.normalized_name || '%')",
-            )?;
-
-            let pattern = format!("%{}%", normalized);
-            let rows = name_stmt.query_map(
-                params![exclude_agent, min_stability, normalized, pattern],
-                |row| row.get::<_, String>(0),
-            )?;
-            for id in rows.flatten() {
-                candidate_ids.insert(id);
+        let exclude_agent = self.agent_key(exclude_agent);
+        let excluded = (!options.include_self).then_some(exclude_agent.as_ref());
+        let candidate_ids = match role_filter {
+            None => self.overlap_candidates(specs, excluded, min_stability)?,
+            Some(role) => {
+                let [hits] = self.role_candidates([specs], excluded, min_stability)?;
+                hits.into_iter()
+                    .filter(|(_, r)| r == role.as_str())
+                    .map(|(id, _)| id)
+                    .collect()
             }
-
-            // Tag-based candidates: >=2 shared tags
-            if spec.tags.len() >= 2 {
-                for tag in &spec.tags {
-                    let mut tag_stmt = self.conn.prepare(
-                        "SELECT DISTINCT ii.intent_id
-                         FROM intent_interfaces ii
-                         JOIN intents i ON i.id = ii.intent_id
-                         WHERE ii.agent_id != ?1
-                           AND i.computed_stability >= ?2
-                           AND ii.tags LIKE ?3",
-                    )?;
-                    let tag_pattern = format!("%{}%", tag);
-                    let rows = tag_stmt
-                        .query_map(params![exclude_agent, min_stability, tag_pattern], |row| {
-                            row.get::<_, String>(0)
-                        })?;
-                    for id in rows.flatten() {
-                        candidate_ids.insert(id);
-                    }
-                }
-            }
-        }
-
+        };
         if candidate_ids.is_empty() {
             return Ok(Vec::new());
         }
@@ -282,16 +1534,20 @@ impl IntentGraph {
             .into_iter()
             .filter(|intent| candidate_ids.contains(&intent.id))
             .filter(|intent| {
-                let their_specs: Vec<&InterfaceSpec> = intent
-                    .provides
-                    .iter()
-                    .chain(intent.requires.iter())
-                    .collect();
+                let their_specs: Vec<&InterfaceSpec> = match role_filter {
+                    Some(Role::Provides) => intent.provides.iter().collect(),
+                    Some(Role::Requires) => intent.requires.iter().collect(),
+                    None => intent
+                        .provides
+                        .iter()
+                        .chain(intent.requires.iter())
+                        .collect(),
+                };
 
                 specs.iter().any(|my_spec| {
-                    their_specs
-                        .iter()
-                        .any(|their_spec| my_spec.structurally_overlaps(their_spec))
+                    their_specs.iter().any(|their_spec| {
+                        my_spec.structurally_overlaps_with(their_spec, self.name_match)
+                    })
                 })
             })
             .collect();
@@ -299,57 +1555,483 @@ impl IntentGraph {
         Ok(overlapping)
     }
 
-    /// Find constraints from other agents that apply to the given intent.
-    pub fn find_applicable_constraints(
+    /// Phase 1 of overlap detection: ids of intents whose indexed interfaces
+    /// share a normalized name or >=2 tags with any of `specs`. A superset of
+    /// the true overlaps; callers verify structurally.
+    fn overlap_candidates(
         &self,
-        intent: &IntentNode,
+        specs: &[InterfaceSpec],
+        exclude_agent: Option<&str>,
         min_stability: f64,
-    ) -> SqlResult<Vec<(Constraint, String, f64)>> {
-        // Returns (constraint, source_intent_id, source_stability)
-        let all = self.query_all(Some(min_stability))?;
-
-        let applicable: Vec<(Constraint, String, f64)> = all
-            .into_iter()
-            .filter(|other| other.agent_id != intent.agent_id)
-            .flat_map(|other| {
-                let id = other.id.clone();
-                let stability = self.scorer.compute(&other);
-                other
-                    .constraints
-                    .into_iter()
-                    .filter(|c| c.applies_to(intent))
-                    .map(move |c| (c, id.clone(), stability))
-            })
-            .collect();
-
-        Ok(applicable)
+    ) -> SqlResult<std::collections::HashSet<String>> {
+        let [hits] = self.role_candidates([specs], exclude_agent, min_stability)?;
+        Ok(hits.into_iter().map(|(id, _)| id).collect())
     }
 
-    /// Resolve an intent against the current graph state.
-    /// Returns adjustments the agent should make for compatibility.
-    pub fn resolve(&self, intent: &IntentNode, min_stability: f64) -> SqlResult<ResolutionResult> {
-        let mut adjustments = Vec::new();
-        let mut conflicts = Vec::new();
-        let mut adopted_constraints = Vec::new();
-
-        // 1. Find overlapping provisions — avoid duplication
-        let my_specs: Vec<InterfaceSpec> = intent
-            .provides
-            .iter()
-            .chain(intent.requires.iter())
-            .cloned()
-            .collect();
-
-        let overlapping = self.find_overlapping(&my_specs, &intent.agent_id, min_stability)?;
+    /// Like [`overlap_candidates`](Self::overlap_candidates), but keeps the
+    /// role (`"provides"` or `"requires"`) of each matching index row, so
+    /// callers can tell which of the other intent's lists to check.
+    ///
+    /// Returns one hit set per group of specs. Each distinct normalized name
+    /// and tag is only looked up once across all groups.
+    fn role_candidates<const N: usize>(
+        &self,
+        groups: [&[InterfaceSpec]; N],
+        exclude_agent: Option<&str>,
+        min_stability: f64,
+    ) -> SqlResult<[std::collections::HashSet<(String, String)>; N]> {
+        let mut name_hits: std::collections::HashMap<String, Vec<(String, String)>> =
+            std::collections::HashMap::new();
+        let mut tag_hits: std::collections::HashMap<&str, Vec<(String, String)>> =
+            std::collections::HashMap::new();
+        let mut results: [std::collections::HashSet<(String, String)>; N] =
+            std::array::from_fn(|_| std::collections::HashSet::new());
 
-        for other in &overlapping {
-            let other_stability = self.scorer.compute(other);
+        for (specs, candidates) in groups.iter().zip(results.iter_mut()) {
+            for spec in specs.iter() {
+                let normalized = crate::matching::normalize_name(&spec.name);
+
+                // Name-based candidates: normalized name overlap
+                if !name_hits.contains_key(&normalized) {
+                    let mut name_stmt = self.conn.prepare_cached(&format!(
+                        "SELECT DISTINCT ii.intent_id, ii.role
+                         FROM intent_interfaces ii
+                         JOIN intents i ON i.id = ii.intent_id
+                         WHERE (?1 IS NULL OR ii.agent_id != ?1)
+                           AND i.computed_stability >= ?2
+                           AND ({})",
+                        name_match_sql(self.name_match)
+                    ))?;
+
+                    let rows = name_stmt
+                        .query_map(params![exclude_agent, min_stability, normalized], |row| {
+                            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                        })?;
+                    name_hits.insert(normalized.clone(), rows.flatten().collect());
+                }
+                candidates.extend(name_hits[&normalized].iter().cloned());
+
+                // Tag-based candidates: >=2 shared tags, or one shared tag
+                // when the module path may confirm it
+                if spec.tags.len() >= 2 || (!spec.tags.is_empty() && !spec.module_path.is_empty()) {
+                    for tag in &spec.tags {
+                        if !tag_hits.contains_key(tag.as_str()) {
+                            let mut tag_stmt = self.conn.prepare_cached(
+                                "SELECT DISTINCT ii.intent_id, ii.role
+                                 FROM intent_interfaces ii
+                                 JOIN intents i ON i.id = ii.intent_id
+                                 WHERE (?1 IS NULL OR ii.agent_id != ?1)
+                                   AND i.computed_stability >= ?2
+                                   AND ii.tags LIKE ?3",
+                            )?;
+                            let tag_pattern = format!("%{}%", tag);
+                            let rows = tag_stmt.query_map(
+                                params![exclude_agent, min_stability, tag_pattern],
+                                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+                            )?;
+                            tag_hits.insert(tag.as_str(), rows.flatten().collect());
+                        }
+                        candidates.extend(tag_hits[tag.as_str()].iter().cloned());
+                    }
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Count intents that [`find_overlapping`](Self::find_overlapping) would
+    /// return, without materializing them. Only the `provides`/`requires`
+    /// columns of Phase-1 candidates are loaded for structural verification,
+    /// so this is the cheaper choice for "does anything overlap?" gating.
+    pub fn count_overlapping(
+        &self,
+        specs: &[InterfaceSpec],
+        exclude_agent: &str,
+        min_stability: f64,
+    ) -> SqlResult<usize> {
+        if specs.is_empty() {
+            return Ok(0);
+        }
+
+        let exclude_agent = self.agent_key(exclude_agent);
+        let candidate_ids = self.overlap_candidates(specs, Some(&exclude_agent), min_stability)?;
+        let mut stmt = self
+            .conn
+            .prepare("SELECT provides, requires FROM intents WHERE id = ?1")?;
+
+        let mut count = 0;
+        for id in &candidate_ids {
+            let (provides_json, requires_json): (String, String) =
+                stmt.query_row(params![id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            let provides: Vec<InterfaceSpec> =
+                serde_json::from_str(&provides_json).unwrap_or_default();
+            let requires: Vec<InterfaceSpec> =
+                serde_json::from_str(&requires_json).unwrap_or_default();
+
+            let overlaps = specs.iter().any(|my_spec| {
+                provides.iter().chain(requires.iter()).any(|their_spec| {
+                    my_spec.structurally_overlaps_with(their_spec, self.name_match)
+                })
+            });
+            if overlaps {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Like [`find_overlapping`](Self::find_overlapping), but also explains each
+    /// match: which of my specs matched which of theirs, by name or by tags, and
+    /// how strongly. Useful for debugging spurious matches.
+    pub fn find_overlapping_explained(
+        &self,
+        specs: &[InterfaceSpec],
+        exclude_agent: &str,
+        min_stability: f64,
+    ) -> SqlResult<Vec<(IntentNode, Vec<OverlapReason>)>> {
+        let overlapping = self.find_overlapping(specs, exclude_agent, min_stability)?;
+
+        Ok(overlapping
+            .into_iter()
+            .map(|intent| {
+                let reasons = specs
+                    .iter()
+                    .flat_map(|my_spec| {
+                        intent
+                            .provides
+                            .iter()
+                            .chain(intent.requires.iter())
+                            .filter_map(move |their_spec| {
+                                my_spec.explain_overlap_with(their_spec, self.name_match)
+                            })
+                    })
+                    .collect();
+                (intent, reasons)
+            })
+            .collect())
+    }
+
+    /// Stability of a decision over time, following its lineage.
+    ///
+    /// Walks the `parent_id` chain from the root down to `id`. Each intent
+    /// contributes a point when it was published, scored with the evidence it
+    /// carried at that time, and one point per later evidence timestamp until
+    /// the next refinement in the chain takes over. Points are ordered by
+    /// time. Fails with `QueryReturnedNoRows` if `id` is unknown.
+    pub fn stability_history(&self, id: &str) -> SqlResult<Vec<(DateTime<Utc>, f64)>> {
+        let mut chain = vec![self.get(id)?.ok_or(rusqlite::Error::QueryReturnedNoRows)?];
+        let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+        seen.insert(id.to_string());
+        while let Some(parent_id) = chain.last().and_then(|i| i.parent_id.clone()) {
+            // Stop at a malformed cycle rather than walking forever
+            if !seen.insert(parent_id.clone()) {
+                break;
+            }
+            match self.get(&parent_id)? {
+                Some(parent) => chain.push(parent),
+                None => break,
+            }
+        }
+        chain.reverse();
+
+        let mut points = Vec::new();
+        for (i, intent) in chain.iter().enumerate() {
+            let superseded_at = chain.get(i + 1).map(|next| next.timestamp);
+            let mut times: Vec<DateTime<Utc>> = intent
+                .evidence
+                .iter()
+                .map(|e| e.timestamp)
+                .filter(|t| *t > intent.timestamp && superseded_at.is_none_or(|s| *t < s))
+                .collect();
+            times.sort();
+            times.dedup();
+
+            for at in std::iter::once(intent.timestamp).chain(times) {
+                let mut snapshot = intent.clone();
+                snapshot.evidence.retain(|e| e.timestamp <= at);
+                points.push((at, self.scorer.compute_at(&snapshot, at)));
+            }
+        }
+        Ok(points)
+    }
+
+    /// Scan the `parent_id` links for cycles (`A` → `B` → `A`).
+    ///
+    /// Each intent has at most one parent, so lineage should form a forest;
+    /// a cycle means malformed data that would send any ancestor walk into an
+    /// infinite loop. Publishing requires the parent to exist, so cycles only
+    /// come from self-parented intents or rows written with foreign keys off.
+    ///
+    /// Returns each cycle once as a path of ids, starting from its smallest
+    /// id. An empty result means the lineage is a valid DAG.
+    pub fn validate_lineage(&self) -> SqlResult<Vec<Vec<String>>> {
+        let parents: std::collections::BTreeMap<String, Option<String>> = {
+            let mut stmt = self.conn.prepare("SELECT id, parent_id FROM intents")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<SqlResult<_>>()?
+        };
+
+        // 0 = unvisited, 1 = on the current walk, 2 = finished
+        let mut state: std::collections::HashMap<&str, u8> = std::collections::HashMap::new();
+        let mut cycles = Vec::new();
+        for start in parents.keys() {
+            let mut path: Vec<&str> = Vec::new();
+            let mut current = Some(start.as_str());
+            while let Some(id) = current {
+                match state.get(id).copied().unwrap_or(0) {
+                    2 => break,
+                    1 => {
+                        let pos = path.iter().position(|p| *p == id).unwrap_or(0);
+                        let mut cycle: Vec<String> =
+                            path[pos..].iter().map(|p| p.to_string()).collect();
+                        let min = (0..cycle.len()).min_by_key(|&i| &cycle[i]).unwrap_or(0);
+                        cycle.rotate_left(min);
+                        cycles.push(cycle);
+                        break;
+                    }
+                    _ => {
+                        state.insert(id, 1);
+                        path.push(id);
+                        current = parents.get(id).and_then(|p| p.as_deref());
+                    }
+                }
+            }
+            for id in path {
+                state.insert(id, 2);
+            }
+        }
+        Ok(cycles)
+    }
+
+    /// Stream every conflict in the graph to `writer` as newline-delimited JSON,
+    /// one [`ConflictReport`] per line, returning the number of lines written.
+    ///
+    /// Each intent at or above `min_stability` is resolved against the rest of
+    /// the graph in timestamp order and its conflicts are written as they are
+    /// found, so memory stays bounded by a single resolution rather than the
+    /// whole result set. Conflicts are reported from the resolving intent's
+    /// side, so a symmetric clash appears once per participant.
+    pub fn write_conflicts_ndjson<W: Write>(
+        &self,
+        min_stability: f64,
+        mut writer: W,
+    ) -> SqlResult<usize> {
+        let to_sql_err = |e: Box<dyn std::error::Error + Send + Sync>| {
+            rusqlite::Error::ToSqlConversionFailure(e)
+        };
+        let mut written = 0;
+        self.for_each_conflict(min_stability, |conflict| {
+            serde_json::to_writer(&mut writer, &conflict).map_err(|e| to_sql_err(Box::new(e)))?;
+            writer
+                .write_all(b"\n")
+                .map_err(|e| to_sql_err(Box::new(e)))?;
+            written += 1;
+            Ok(())
+        })?;
+        writer.flush().map_err(|e| to_sql_err(Box::new(e)))?;
+        Ok(written)
+    }
+
+    /// Count distinct pairs of intents in conflict, considering only intents
+    /// at or above `min_stability`. A pair clashing on several points counts once.
+    pub fn conflict_count(&self, min_stability: f64) -> SqlResult<usize> {
+        let mut pairs = std::collections::HashSet::new();
+        self.for_each_conflict(min_stability, |conflict| {
+            let mut pair = [conflict.my_intent_id, conflict.their_intent_id];
+            pair.sort();
+            pairs.insert(pair);
+            Ok(())
+        })?;
+        Ok(pairs.len())
+    }
+
+    /// Resolve each intent at or above `min_stability` against the rest of the
+    /// graph, in timestamp order, handing every conflict to `f` as it is found.
+    fn for_each_conflict(
+        &self,
+        min_stability: f64,
+        mut f: impl FnMut(ConflictReport) -> SqlResult<()>,
+    ) -> SqlResult<()> {
+        let ids: Vec<String> = {
+            let mut stmt = self.conn.prepare(
+                "SELECT id FROM intents WHERE computed_stability >= ?1 ORDER BY timestamp ASC",
+            )?;
+            let rows = stmt.query_map(params![min_stability], |row| row.get(0))?;
+            rows.collect::<SqlResult<_>>()?
+        };
+
+        for id in ids {
+            let Some(intent) = self.get(&id)? else {
+                continue;
+            };
+            for conflict in self.resolve(&intent, min_stability)?.conflicts {
+                f(conflict)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Find constraints from other agents that apply to the given intent.
+    ///
+    /// Each source's stability is the `computed_stability` stored when it was
+    /// published (or last rescored), not recomputed from its evidence, so it
+    /// always agrees with the `min_stability` filter. After
+    /// [`with_scorer`](Self::with_scorer), or as conflicts decay under a
+    /// conflict half-life, stored scores lag until
+    /// [`recompute_all_stability`](Self::recompute_all_stability).
+    pub fn find_applicable_constraints(
+        &self,
+        intent: &IntentNode,
+        min_stability: f64,
+    ) -> SqlResult<Vec<(Constraint, String, f64)>> {
+        // Returns (constraint, source_intent_id, source_stability)
+        let all = self.query_all(Some(min_stability))?;
+        Ok(self.applicable_constraints_in(intent, &all))
+    }
+
+    /// [`find_applicable_constraints`](Self::find_applicable_constraints)
+    /// over already-loaded intents.
+    fn applicable_constraints_in(
+        &self,
+        intent: &IntentNode,
+        all: &[IntentNode],
+    ) -> Vec<(Constraint, String, f64)> {
+        all.iter()
+            .filter(|other| !self.same_agent(&other.agent_id, &intent.agent_id))
+            .flat_map(|other| {
+                let stability = other
+                    .computed_stability
+                    .unwrap_or_else(|| self.stability_of(other));
+                other
+                    .constraints
+                    .iter()
+                    .filter(|c| {
+                        c.matches_interfaces(intent) && self.binds_agent(c, &intent.agent_id)
+                    })
+                    .map(move |c| (c.clone(), other.id.clone(), stability))
+            })
+            .collect()
+    }
+
+    /// Provision and requirement overlap checks shared by
+    /// [`resolve_with`](Self::resolve_with) and
+    /// [`publish_and_check`](Self::publish_and_check): duplicate provisions,
+    /// signature mismatches, and required→required disagreements.
+    fn check_overlaps(
+        &self,
+        intent: &IntentNode,
+        min_stability: f64,
+        adjustments: &mut Vec<Adjustment>,
+        conflicts: &mut Vec<ConflictReport>,
+        trace: Option<&mut Vec<TraceStep>>,
+    ) -> SqlResult<()> {
+        // Role-qualified candidates: each check below pairs one of our roles
+        // with one of theirs, so sort the index hits into exactly those pairs.
+        // An intent that only shares a name between our provides and their
+        // requires never gets loaded.
+        let agent_key = self.agent_key(&intent.agent_id);
+        let agent = Some(agent_key.as_ref());
+        let by_role = |hits: std::collections::HashSet<(String, String)>, role: &str| {
+            hits.iter()
+                .filter(|(_, r)| r == role)
+                .map(|(id, _)| id.clone())
+                .collect::<std::collections::HashSet<String>>()
+        };
+        let [provides_hits, requires_hits] =
+            self.role_candidates([&intent.provides, &intent.requires], agent, min_stability)?;
+        let candidates = OverlapCandidates {
+            duplicates: by_role(provides_hits, "provides"),
+            providers: by_role(requires_hits.clone(), "provides"),
+            requirers: by_role(requires_hits, "requires"),
+        };
+
+        let candidate_ids: Vec<&String> = candidates
+            .duplicates
+            .iter()
+            .chain(&candidates.providers)
+            .chain(&candidates.requirers)
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .collect();
+        if candidate_ids.is_empty() {
+            return Ok(());
+        }
+        let overlapping = self.load_intents(&candidate_ids)?;
+        let lineage = self.lineage_ids(intent)?;
+        self.check_overlaps_against(
+            intent,
+            overlapping.iter().filter(|o| !lineage.contains(&o.id)),
+            Some(&candidates),
+            adjustments,
+            conflicts,
+            trace,
+        );
+        Ok(())
+    }
+
+    /// The checks behind [`check_overlaps`](Self::check_overlaps), run against
+    /// already-loaded intents. `candidates` narrows each check to the intents
+    /// the index matched for it; `None` checks every intent in `others`, as
+    /// [`resolve_batch`](Self::resolve_batch) does with its snapshot.
+    /// Callers leave `intent`'s [`lineage_ids`](Self::lineage_ids) out of
+    /// `others`. Every spec pair compared is recorded in `trace` when one is
+    /// given.
+    fn check_overlaps_against<'o>(
+        &self,
+        intent: &IntentNode,
+        others: impl IntoIterator<Item = &'o IntentNode>,
+        candidates: Option<&OverlapCandidates>,
+        adjustments: &mut Vec<Adjustment>,
+        conflicts: &mut Vec<ConflictReport>,
+        mut trace: Option<&mut Vec<TraceStep>>,
+    ) {
+        let admits = |set: fn(&OverlapCandidates) -> &std::collections::HashSet<String>,
+                      id: &String| {
+            candidates.is_none_or(|c| set(c).contains(id))
+        };
+        let my_stability = self.stability_of(intent);
+
+        for other in others
+            .into_iter()
+            .filter(|o| !self.same_agent(&o.agent_id, &intent.agent_id))
+        {
+            let other_stability = self.stability_of(other);
 
             // Check for duplicate provisions
-            for my_provision in &intent.provides {
+            for my_provision in intent
+                .provides
+                .iter()
+                .filter(|_| admits(|c| &c.duplicates, &other.id))
+            {
                 for their_provision in &other.provides {
-                    if my_provision.structurally_overlaps(their_provision) {
-                        if other_stability > self.scorer.compute(intent) {
+                    let overlaps =
+                        my_provision.structurally_overlaps_with(their_provision, self.name_match);
+                    record(&mut trace, || {
+                        let outcome = if !overlaps || their_provision.deprecated {
+                            "no action"
+                        } else if other_stability > my_stability {
+                            "ConsumeInstead"
+                        } else {
+                            "conflict"
+                        };
+                        TraceStep {
+                            check: TraceCheck::DuplicateProvision,
+                            their_intent_id: other.id.clone(),
+                            mine: my_provision.name.clone(),
+                            theirs: their_provision.name.clone(),
+                            detail: format!(
+                                "structural overlap={}; deprecated={}; stability {:.2} vs {:.2}",
+                                overlaps, their_provision.deprecated, my_stability, other_stability
+                            ),
+                            outcome: outcome.to_string(),
+                        }
+                    });
+                    // A provision its owner is retiring neither competes with
+                    // ours nor should be consumed in its place; we may well be
+                    // its successor.
+                    if overlaps && !their_provision.deprecated {
+                        if other_stability > my_stability {
                             // They're more committed — consume theirs
                             adjustments.push(Adjustment {
                                 kind: AdjustmentKind::ConsumeInstead,
@@ -361,6 +2043,8 @@ impl IntentGraph {
                                     other_stability
                                 ),
                                 source_intent_id: other.id.clone(),
+                                signature_diff: None,
+                                adaptation: None,
                             });
                         } else {
                             // We're more committed or equal — report conflict
@@ -369,11 +2053,11 @@ impl IntentGraph {
                                 their_intent_id: other.id.clone(),
                                 description: format!(
                                     "Both provide '{}' — my stability {:.2} vs their {:.2}",
-                                    my_provision.name,
-                                    self.scorer.compute(intent),
-                                    other_stability,
+                                    my_provision.name, my_stability, other_stability,
                                 ),
+                                my_stability,
                                 their_stability: other_stability,
+                                winner: Winner::from_scores(my_stability, other_stability),
                                 resolution_suggestion:
                                     "Higher stability should provide; other should consume"
                                         .to_string(),
@@ -384,12 +2068,63 @@ impl IntentGraph {
             }
 
             // Check for interface signature mismatches in required→provided pairs
-            for my_requirement in &intent.requires {
+            for my_requirement in intent
+                .requires
+                .iter()
+                .filter(|_| admits(|c| &c.providers, &other.id))
+            {
                 for their_provision in &other.provides {
-                    if my_requirement.structurally_overlaps(their_provision)
-                        && !my_requirement.signature_compatible(their_provision)
-                        && other_stability > self.scorer.compute(intent)
+                    let overlaps =
+                        my_requirement.structurally_overlaps_with(their_provision, self.name_match);
+                    record(&mut trace, || {
+                        let compatible = my_requirement.is_satisfied_by(their_provision);
+                        let outcome = if !overlaps {
+                            "no action"
+                        } else if their_provision.deprecated {
+                            "MigrateOff"
+                        } else if !compatible && other_stability > my_stability {
+                            "AdaptSignature"
+                        } else {
+                            "no action"
+                        };
+                        TraceStep {
+                            check: TraceCheck::RequiredProvision,
+                            their_intent_id: other.id.clone(),
+                            mine: my_requirement.name.clone(),
+                            theirs: their_provision.name.clone(),
+                            detail: format!(
+                                "structural overlap={}; signature compatible={}; deprecated={}; \
+                                 stability {:.2} vs {:.2}",
+                                overlaps,
+                                compatible,
+                                their_provision.deprecated,
+                                my_stability,
+                                other_stability
+                            ),
+                            outcome: outcome.to_string(),
+                        }
+                    });
+                    if overlaps && their_provision.deprecated {
+                        adjustments.push(Adjustment {
+                            kind: AdjustmentKind::MigrateOff,
+                            description: format!(
+                                "'{}' from agent {} is deprecated; {}",
+                                their_provision.name,
+                                other.agent_id,
+                                their_provision.migration_hint()
+                            ),
+                            source_intent_id: other.id.clone(),
+                            signature_diff: None,
+                            adaptation: None,
+                        });
+                    } else if overlaps
+                        && !my_requirement.is_satisfied_by(their_provision)
+                        && other_stability > my_stability
                     {
+                        let diff = crate::matching::signature_diff(
+                            &my_requirement.signature,
+                            &their_provision.signature,
+                        );
                         adjustments.push(Adjustment {
                             kind: AdjustmentKind::AdaptSignature,
                             description: format!(
@@ -402,23 +2137,303 @@ impl IntentGraph {
                                 their_provision.signature,
                             ),
                             source_intent_id: other.id.clone(),
+                            adaptation: Some(SignatureAdaptation::new(
+                                my_requirement,
+                                their_provision,
+                                &diff,
+                            )),
+                            signature_diff: Some(diff),
+                        });
+                    }
+                }
+            }
+
+            // Check for required→required disagreement: both of us expect the same
+            // interface but with shapes no single provider signature reconciles.
+            for my_requirement in intent
+                .requires
+                .iter()
+                .filter(|_| admits(|c| &c.requirers, &other.id))
+            {
+                for their_requirement in &other.requires {
+                    let names_overlap =
+                        my_requirement.names_overlap_with(their_requirement, self.name_match);
+                    let disagree = names_overlap
+                        && !my_requirement.signature_compatible(their_requirement)
+                        && !their_requirement.signature_compatible(my_requirement);
+                    record(&mut trace, || TraceStep {
+                        check: TraceCheck::RequiredRequirement,
+                        their_intent_id: other.id.clone(),
+                        mine: my_requirement.name.clone(),
+                        theirs: their_requirement.name.clone(),
+                        detail: format!(
+                            "names overlap={}; signatures reconcilable={}",
+                            names_overlap,
+                            names_overlap && !disagree
+                        ),
+                        outcome: if disagree { "conflict" } else { "no action" }.to_string(),
+                    });
+                    if disagree {
+                        conflicts.push(ConflictReport {
+                            my_intent_id: intent.id.clone(),
+                            their_intent_id: other.id.clone(),
+                            description: format!(
+                                "Both require '{}' with incompatible signatures — \
+                                     I expect '{}', agent {} expects '{}'",
+                                my_requirement.name,
+                                my_requirement.signature,
+                                other.agent_id,
+                                their_requirement.signature,
+                            ),
+                            my_stability,
+                            their_stability: other_stability,
+                            winner: Winner::from_scores(my_stability, other_stability),
+                            resolution_suggestion:
+                                "Requirers should align on a shared contract before a provider lands"
+                                    .to_string(),
                         });
                     }
                 }
             }
         }
+    }
+
+    /// Resolve an intent against the current graph state.
+    /// Returns adjustments the agent should make for compatibility.
+    pub fn resolve(&self, intent: &IntentNode, min_stability: f64) -> SqlResult<ResolutionResult> {
+        self.resolve_with(intent, &ResolveOptions::new(min_stability))
+    }
+
+    /// Resolve an intent against the current graph state with explicit options.
+    /// See [`ResolveOptions`] for the available knobs.
+    pub fn resolve_with(
+        &self,
+        intent: &IntentNode,
+        options: &ResolveOptions,
+    ) -> SqlResult<ResolutionResult> {
+        self.resolve_inner(intent, options, None)
+    }
+
+    /// [`resolve`](Self::resolve), also returning a step-by-step record of
+    /// every comparison it made: each spec pair checked, each applicable
+    /// constraint, and each conflict turned into a yield, with what was
+    /// observed and what came of it. For tuning matching, not for
+    /// production paths; the result is the same as `resolve`'s.
+    pub fn resolve_traced(
+        &self,
+        intent: &IntentNode,
+        min_stability: f64,
+    ) -> SqlResult<(ResolutionResult, Vec<TraceStep>)> {
+        let mut trace = Vec::new();
+        let result = self.resolve_inner(
+            intent,
+            &ResolveOptions::new(min_stability),
+            Some(&mut trace),
+        )?;
+        Ok((result, trace))
+    }
+
+    fn resolve_inner(
+        &self,
+        intent: &IntentNode,
+        options: &ResolveOptions,
+        mut trace: Option<&mut Vec<TraceStep>>,
+    ) -> SqlResult<ResolutionResult> {
+        let min_stability = options.min_stability;
+        let mut adjustments = Vec::new();
+        let mut conflicts = Vec::new();
+
+        // 1. Find overlapping provisions — avoid duplication
+        self.check_overlaps(
+            intent,
+            min_stability,
+            &mut adjustments,
+            &mut conflicts,
+            trace.as_deref_mut(),
+        )?;
+
+        let active = self.query_all(Some(min_stability))?;
+        Ok(self.finish_resolution(intent, options, &active, adjustments, conflicts, trace))
+    }
+
+    /// Resolve several candidate intents against one snapshot of the graph.
+    ///
+    /// Equivalent to calling [`resolve`](Self::resolve) for each intent, but
+    /// the intents above `min_stability` are loaded once and shared by every
+    /// resolution instead of being queried again per intent. Intents are not
+    /// published, so candidates don't see each other.
+    pub fn resolve_batch(
+        &self,
+        intents: &[IntentNode],
+        min_stability: f64,
+    ) -> SqlResult<Vec<ResolutionResult>> {
+        let options = ResolveOptions::new(min_stability);
+        let active = self.query_all(Some(min_stability))?;
+        intents
+            .iter()
+            .map(|intent| {
+                let mut adjustments = Vec::new();
+                let mut conflicts = Vec::new();
+                let lineage = self.lineage_ids(intent)?;
+                self.check_overlaps_against(
+                    intent,
+                    active.iter().filter(|o| !lineage.contains(&o.id)),
+                    None,
+                    &mut adjustments,
+                    &mut conflicts,
+                    None,
+                );
+                Ok(self.finish_resolution(intent, &options, &active, adjustments, conflicts, None))
+            })
+            .collect()
+    }
+
+    /// Ids of `intent`'s ancestors (following `parent_id` up) and
+    /// descendants (intents whose chain leads back to it). A refinement
+    /// naturally repeats what it refines, so overlap checks skip these.
+    /// Cycles in malformed lineage end the walk instead of looping.
+    fn lineage_ids(&self, intent: &IntentNode) -> SqlResult<std::collections::HashSet<String>> {
+        let mut stmt = self.conn.prepare_cached(
+            "WITH RECURSIVE
+                 ancestors(id) AS (
+                     SELECT ?1 WHERE ?1 IS NOT NULL
+                     UNION
+                     SELECT i.parent_id FROM intents i JOIN ancestors a ON i.id = a.id
+                     WHERE i.parent_id IS NOT NULL
+                 ),
+                 descendants(id) AS (
+                     SELECT id FROM intents WHERE parent_id = ?2
+                     UNION
+                     SELECT i.id FROM intents i JOIN descendants d ON i.parent_id = d.id
+                 )
+             SELECT id FROM ancestors UNION SELECT id FROM descendants",
+        )?;
+        let mut lineage: std::collections::HashSet<String> = stmt
+            .query_map(params![intent.parent_id, intent.id], |row| row.get(0))?
+            .collect::<SqlResult<_>>()?;
+        lineage.remove(&intent.id);
+        Ok(lineage)
+    }
+
+    /// Suggest [`FollowRename`](AdjustmentKind::FollowRename) for each
+    /// requirement that no other agent's provision satisfies, when a more
+    /// stable intent provides a differently named interface of the same kind
+    /// whose non-empty signature matches the requirement's both ways. That is
+    /// what a provider renaming an interface in a refinement looks like to
+    /// its consumers.
+    fn check_renames(
+        &self,
+        intent: &IntentNode,
+        active: &[IntentNode],
+        adjustments: &mut Vec<Adjustment>,
+        trace: &mut Option<&mut Vec<TraceStep>>,
+    ) {
+        let my_stability = self.stability_of(intent);
+        let others: Vec<&IntentNode> = active
+            .iter()
+            .filter(|o| !self.same_agent(&o.agent_id, &intent.agent_id))
+            .collect();
+
+        for req in &intent.requires {
+            let satisfied = others.iter().flat_map(|o| &o.provides).any(|prov| {
+                req.structurally_overlaps_with(prov, self.name_match) && req.is_satisfied_by(prov)
+            });
+            if satisfied || crate::matching::parse_signature(&req.signature).is_empty() {
+                continue;
+            }
+            for other in &others {
+                let other_stability = self.stability_of(other);
+                if other_stability <= my_stability {
+                    continue;
+                }
+                let renamed = other.provides.iter().find(|prov| {
+                    !prov.deprecated
+                        && prov.kind == req.kind
+                        && !req.names_overlap_with(prov, self.name_match)
+                        && req.is_satisfied_by(prov)
+                        && prov.signature_compatible(req)
+                });
+                let Some(prov) = renamed else {
+                    continue;
+                };
+                record(trace, || TraceStep {
+                    check: TraceCheck::Rename,
+                    their_intent_id: other.id.clone(),
+                    mine: req.name.clone(),
+                    theirs: prov.name.clone(),
+                    detail: format!(
+                        "no provider of '{}'; same kind and signature; stability {:.2} vs {:.2}",
+                        req.name, my_stability, other_stability
+                    ),
+                    outcome: "FollowRename".to_string(),
+                });
+                adjustments.push(Adjustment {
+                    kind: AdjustmentKind::FollowRename,
+                    description: format!(
+                        "Nothing provides '{}'; '{}' from agent {} has the same signature \
+                         and is likely a rename — require '{}' instead",
+                        req.name, prov.name, other.agent_id, prov.name
+                    ),
+                    source_intent_id: other.id.clone(),
+                    signature_diff: None,
+                    adaptation: None,
+                });
+            }
+        }
+    }
+
+    /// Steps 2 and 3 of [`resolve_with`](Self::resolve_with), given the
+    /// overlap findings and the intents above the stability floor.
+    fn finish_resolution(
+        &self,
+        intent: &IntentNode,
+        options: &ResolveOptions,
+        active: &[IntentNode],
+        mut adjustments: Vec<Adjustment>,
+        mut conflicts: Vec<ConflictReport>,
+        mut trace: Option<&mut Vec<TraceStep>>,
+    ) -> ResolutionResult {
+        let min_stability = options.min_stability;
+        let mut adopted_constraints = Vec::new();
+        let mut rejected_constraints = Vec::new();
+        let mut notes = Vec::new();
+
+        // 1b. Requirements nobody provides may have been renamed by their provider
+        self.check_renames(intent, active, &mut adjustments, &mut trace);
 
         // 2. Find applicable constraints from other agents
-        let applicable = self.find_applicable_constraints(intent, min_stability)?;
+        let applicable = self.applicable_constraints_in(intent, active);
 
-        for (constraint, source_id, _source_stability) in applicable {
+        for (constraint, source_id, source_stability) in applicable {
             // Check if this constraint conflicts with our own constraints
             let has_conflict = intent
                 .constraints
                 .iter()
                 .any(|my_c| my_c.conflicts_with(&constraint));
+            let admitted = options.adopt_policy.admits(&constraint.severity);
+            record(&mut trace, || TraceStep {
+                check: TraceCheck::Constraint,
+                their_intent_id: source_id.clone(),
+                mine: String::new(),
+                theirs: constraint.target.clone(),
+                detail: format!(
+                    "conflicts with mine={}; {} severity admitted={}",
+                    has_conflict,
+                    constraint.severity.as_str(),
+                    admitted
+                ),
+                outcome: if has_conflict {
+                    "conflict"
+                } else if admitted {
+                    "AdoptConstraint"
+                } else {
+                    "not adopted"
+                }
+                .to_string(),
+            });
 
             if has_conflict {
+                let my_stability = self.stability_of(intent);
                 conflicts.push(ConflictReport {
                     my_intent_id: intent.id.clone(),
                     their_intent_id: source_id.clone(),
@@ -426,9 +2441,20 @@ impl IntentGraph {
                         "Constraint conflict on '{}': my requirement vs their requirement",
                         constraint.target
                     ),
-                    their_stability: _source_stability,
+                    my_stability,
+                    their_stability: source_stability,
+                    winner: Winner::from_scores(my_stability, source_stability),
                     resolution_suggestion: "Higher stability constraint should win".to_string(),
                 });
+                rejected_constraints.push((constraint, source_id));
+            } else if !admitted {
+                notes.push(format!(
+                    "Not adopting {} constraint from intent {}: {} — {}",
+                    constraint.severity.as_str(),
+                    source_id,
+                    constraint.target,
+                    constraint.requirement
+                ));
             } else {
                 adopted_constraints.push(constraint.clone());
                 adjustments.push(Adjustment {
@@ -438,70 +2464,969 @@ impl IntentGraph {
                         constraint.target, constraint.requirement
                     ),
                     source_intent_id: source_id,
+                    signature_diff: None,
+                    adaptation: None,
+                });
+            }
+        }
+
+        // 3. A conflict is only meaningful when both sides are credible. If we are
+        // below the floor ourselves, yield instead of reporting a conflict.
+        if options.both_above && self.stability_of(intent) < min_stability {
+            for conflict in conflicts.drain(..) {
+                record(&mut trace, || TraceStep {
+                    check: TraceCheck::Yield,
+                    their_intent_id: conflict.their_intent_id.clone(),
+                    mine: String::new(),
+                    theirs: String::new(),
+                    detail: format!(
+                        "my stability {:.2} is below the floor {:.2}",
+                        conflict.my_stability, min_stability
+                    ),
+                    outcome: "YieldTo".to_string(),
+                });
+                adjustments.push(Adjustment {
+                    kind: AdjustmentKind::YieldTo,
+                    description: format!(
+                        "Yield to intent {} (stability {:.2}): {}",
+                        conflict.their_intent_id, conflict.their_stability, conflict.description
+                    ),
+                    source_intent_id: conflict.their_intent_id,
+                    signature_diff: None,
+                    adaptation: None,
                 });
             }
         }
 
-        Ok(ResolutionResult {
+        ResolutionResult {
             original_intent: intent.id.clone(),
             adjustments,
             conflicts,
             adopted_constraints,
-        })
-    }
-
-    /// Get a count of all intents in the graph.
-    pub fn count(&self) -> SqlResult<usize> {
-        let count: i64 = self
-            .conn
-            .query_row("SELECT COUNT(*) FROM intents", [], |row| row.get(0))?;
-        Ok(count as usize)
+            rejected_constraints,
+            notes,
+        }
     }
 
-    /// Get a snapshot summary of the graph state.
-    pub fn summary(&self) -> SqlResult<GraphSummary> {
-        let total = self.count()?;
-        let all = self.query_all(None)?;
+    /// Resolve an intent, apply the `ConsumeInstead` adjustments it is
+    /// confident about, and publish it.
+    ///
+    /// A provision is consumed instead when the provider's stability and the
+    /// overlap strength (see [`OverlapReason`]) both reach
+    /// `auto_apply_threshold`: the provision is dropped from `provides`, the
+    /// provider's spec is added to `requires` (so it shows up in
+    /// [`dependency_edges`](Self::dependency_edges)), and the provider gains
+    /// `consumed_by` evidence from this intent's agent. Each applied swap is
+    /// listed in the result's notes; the adjustments themselves are returned
+    /// unchanged. Everything happens in one transaction.
+    pub fn resolve_and_apply(
+        &self,
+        intent: &IntentNode,
+        min_stability: f64,
+        auto_apply_threshold: f64,
+    ) -> SqlResult<ResolutionResult> {
+        let mut result = self.resolve(intent, min_stability)?;
 
-        let agents: Vec<String> = {
-            let mut ids: Vec<String> = all.iter().map(|i| i.agent_id.clone()).collect();
-            ids.sort();
-            ids.dedup();
-            ids
-        };
+        let mut providers: Vec<&String> = result
+            .adjustments
+            .iter()
+            .filter(|a| a.kind == AdjustmentKind::ConsumeInstead)
+            .map(|a| &a.source_intent_id)
+            .collect();
+        providers.sort();
+        providers.dedup();
 
-        let avg_stability = if all.is_empty() {
-            0.0
-        } else {
-            all.iter().map(|i| self.scorer.compute(i)).sum::<f64>() / all.len() as f64
-        };
+        let mut applied = intent.clone();
+        let mut consumed = Vec::new();
+        let mut notes = Vec::new();
+        for provider in self.load_intents(&providers)? {
+            if self.stability_of(&provider) < auto_apply_threshold {
+                continue;
+            }
+            let swaps = self.consume_from(&mut applied, &provider, auto_apply_threshold);
+            if !swaps.is_empty() {
+                consumed.push(provider.id.clone());
+            }
+            notes.extend(swaps);
+        }
 
-        let high_stability = all.iter().filter(|i| self.scorer.compute(i) >= 0.7).count();
+        let tx = self.conn.unchecked_transaction()?;
+        self.publish(&applied)?;
+        for provider_id in &consumed {
+            let consumer = self.agent_key(&intent.agent_id);
+            self.add_evidence(provider_id, &[Evidence::consumed_by(&consumer)])?;
+        }
+        tx.commit()?;
 
-        Ok(GraphSummary {
-            total_intents: total,
-            agent_count: agents.len(),
-            agents,
-            average_stability: avg_stability,
-            high_stability_count: high_stability,
-        })
+        result.notes.extend(notes);
+        Ok(result)
     }
 
-    fn row_to_intent(&self, row: &rusqlite::Row) -> IntentNode {
-        let provides_json: String = row.get(4).unwrap_or_default();
-        let requires_json: String = row.get(5).unwrap_or_default();
+    /// Swap each of `intent`'s provisions that overlaps one of `provider`'s
+    /// (non-deprecated) provisions with at least `min_strength` for a
+    /// requirement on the provider's spec. Returns a note per swap.
+    fn consume_from(
+        &self,
+        intent: &mut IntentNode,
+        provider: &IntentNode,
+        min_strength: f64,
+    ) -> Vec<String> {
+        let mut notes = Vec::new();
+        for theirs in provider.provides.iter().filter(|p| !p.deprecated) {
+            let confident = |mine: &InterfaceSpec| {
+                mine.explain_overlap_with(theirs, self.name_match)
+                    .is_some_and(|r| r.strength >= min_strength)
+            };
+            let Some(pos) = intent.provides.iter().position(confident) else {
+                continue;
+            };
+            let mine = intent.provides.remove(pos);
+            if !intent.requires.iter().any(|r| r == theirs) {
+                intent.requires.push(theirs.clone());
+            }
+            notes.push(format!(
+                "Auto-applied: dropped '{}', consuming '{}' from agent {}",
+                mine.name, theirs.name, provider.agent_id
+            ));
+        }
+        notes
+    }
+
+    /// Replay resolution in `order` on a fork of the graph and report whether
+    /// it reaches a conflict-free state.
+    ///
+    /// For each intent id in turn, the intent is resolved against the fork
+    /// and its mechanical adjustments are applied to it in place:
+    /// `ConsumeInstead` swaps a provision for a requirement (and credits the
+    /// provider with `consumed_by` evidence), `AdoptConstraint` adds the
+    /// constraint, and `AdaptSignature` takes the provider's signature. Other
+    /// adjustments need a human and are only counted. Conflicts are counted
+    /// as in [`conflict_count`](Self::conflict_count) after every step. This
+    /// graph is never modified. Fails with `QueryReturnedNoRows` for an id
+    /// not in the graph.
+    pub fn simulate_convergence(
+        &self,
+        order: &[String],
+        min_stability: f64,
+    ) -> SqlResult<ConvergenceReport> {
+        let sim = self.fork()?;
+        let initial_conflicts = sim.conflict_count(min_stability)?;
+
+        let mut steps = Vec::with_capacity(order.len());
+        for id in order {
+            let intent = sim.get(id)?.ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+            let result = sim.resolve(&intent, min_stability)?;
+
+            let mut adjusted = intent.clone();
+            let (mut applied, mut skipped) = (0, 0);
+            // A provider can be behind several adjustments of one kind; the
+            // first application covers them all.
+            let mut handled: Vec<(AdjustmentKind, String)> = Vec::new();
+            for adjustment in &result.adjustments {
+                let key = (adjustment.kind.clone(), adjustment.source_intent_id.clone());
+                let changed = match (&adjustment.kind, sim.get(&key.1)?) {
+                    _ if handled.contains(&key) => true,
+                    (AdjustmentKind::ConsumeInstead, Some(provider)) => {
+                        let swapped = !sim.consume_from(&mut adjusted, &provider, 0.0).is_empty();
+                        if swapped {
+                            let consumer = sim.agent_key(&intent.agent_id);
+                            sim.add_evidence(&provider.id, &[Evidence::consumed_by(&consumer)])?;
+                        }
+                        swapped
+                    }
+                    (AdjustmentKind::AdaptSignature, Some(provider)) => {
+                        let mut adapted = false;
+                        for req in &mut adjusted.requires {
+                            let target = provider.provides.iter().find(|prov| {
+                                req.structurally_overlaps_with(prov, sim.name_match)
+                                    && !req.is_satisfied_by(prov)
+                            });
+                            if let Some(prov) = target {
+                                req.signature = prov.signature.clone();
+                                adapted = true;
+                            }
+                        }
+                        adapted
+                    }
+                    (AdjustmentKind::AdoptConstraint, _) => true,
+                    _ => false,
+                };
+                if changed {
+                    applied += 1;
+                    handled.push(key);
+                } else {
+                    skipped += 1;
+                }
+            }
+            for constraint in &result.adopted_constraints {
+                if !adjusted.constraints.contains(constraint) {
+                    adjusted.constraints.push(constraint.clone());
+                }
+            }
+            if adjusted.provides != intent.provides
+                || adjusted.requires != intent.requires
+                || adjusted.constraints != intent.constraints
+            {
+                sim.rewrite_decision(&adjusted)?;
+            }
+
+            steps.push(ConvergenceStep {
+                intent_id: id.clone(),
+                applied,
+                skipped,
+                conflicts: sim.conflict_count(min_stability)?,
+            });
+        }
+
+        Ok(ConvergenceReport {
+            initial_conflicts,
+            final_conflicts: steps.last().map_or(initial_conflicts, |s| s.conflicts),
+            steps,
+        })
+    }
+
+    /// Check a proposed batch of intents without writing anything: publish it
+    /// into a [`fork`](Self::fork), then validate each intent and collect the
+    /// conflicts involving it.
+    ///
+    /// Validation runs after the whole batch is in, so a `parent_id` pointing
+    /// at an earlier batch intent is fine. Intents the fork refuses (publish
+    /// limits, duplicate ids) are reported in `rejected`; publish guards are
+    /// not carried into forks and do not run. Intents with an empty `id` are
+    /// assigned one from the graph's id generator first.
+    pub fn dry_run_batch(
+        &self,
+        intents: &[IntentNode],
+        min_stability: f64,
+    ) -> SqlResult<BatchReport> {
+        let sim = self.fork()?;
+        let mut report = BatchReport::default();
+        let mut batch = Vec::with_capacity(intents.len());
+        for intent in intents {
+            let mut intent = intent.clone();
+            if intent.id.is_empty() {
+                intent.id = self.ids.next_id();
+            }
+            if let Err(e) = sim.publish(&intent) {
+                let reason = match &e {
+                    rusqlite::Error::ToSqlConversionFailure(inner) => inner
+                        .downcast_ref::<PublishRejected>()
+                        .map_or_else(|| e.to_string(), |r| r.reason.clone()),
+                    _ => e.to_string(),
+                };
+                report.rejected.push(PublishRejected {
+                    intent_id: intent.id.clone(),
+                    reason,
+                });
+            }
+            batch.push(intent);
+        }
+
+        for intent in &batch {
+            for issue in sim.validate_intent(intent)? {
+                report.issues.push((intent.id.clone(), issue));
+            }
+        }
+        let ids: std::collections::HashSet<&str> = batch.iter().map(|i| i.id.as_str()).collect();
+        sim.for_each_conflict(min_stability, |conflict| {
+            if ids.contains(conflict.my_intent_id.as_str())
+                || ids.contains(conflict.their_intent_id.as_str())
+            {
+                report.new_conflicts.push(conflict);
+            }
+            Ok(())
+        })?;
+        Ok(report)
+    }
+
+    /// Overwrite an intent's provides, requires, and constraints in place and
+    /// reindex it. Only for scratch graphs like the fork in
+    /// [`simulate_convergence`](Self::simulate_convergence): published
+    /// decisions are otherwise immutable.
+    fn rewrite_decision(&self, intent: &IntentNode) -> SqlResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE intents SET provides = ?1, requires = ?2, constraints = ?3 WHERE id = ?4",
+            params![
+                serde_json::to_string(&intent.provides).unwrap_or_default(),
+                serde_json::to_string(&intent.requires).unwrap_or_default(),
+                serde_json::to_string(&intent.constraints).unwrap_or_default(),
+                intent.id,
+            ],
+        )?;
+        tx.execute(
+            "DELETE FROM intent_interfaces WHERE intent_id = ?1",
+            params![intent.id],
+        )?;
+        self.index_interfaces(&intent.id, &intent.agent_id, "provides", &intent.provides)?;
+        self.index_interfaces(&intent.id, &intent.agent_id, "requires", &intent.requires)?;
+        tx.commit()
+    }
+
+    /// Adjustments `agent_id` still owes across all of its published intents,
+    /// paired with the id of the intent that owes each one.
+    ///
+    /// Runs [`resolve`](Self::resolve) for every intent of the agent, oldest
+    /// first, so the result reflects the current graph rather than whatever
+    /// was true when each intent was published.
+    pub fn outstanding_adjustments(
+        &self,
+        agent_id: &str,
+        min_stability: f64,
+    ) -> SqlResult<Vec<(String, Adjustment)>> {
+        let mut outstanding = Vec::new();
+        for intent in self.query_by_agent(agent_id)? {
+            let result = self.resolve(&intent, min_stability)?;
+            outstanding.extend(
+                result
+                    .adjustments
+                    .into_iter()
+                    .map(|adj| (intent.id.clone(), adj)),
+            );
+        }
+        Ok(outstanding)
+    }
+
+    /// Get a count of all intents in the graph.
+    pub fn count(&self) -> SqlResult<usize> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM intents", [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    /// Copy the write-ahead log into the database file and truncate it, e.g.
+    /// after a bulk import, so a long-running WAL-mode graph's log stays
+    /// bounded. In-memory graphs have no log, and for a file not in WAL mode
+    /// SQLite does nothing; both return `Ok`. Fails with `SQLITE_BUSY` if
+    /// another connection's readers or writer kept the checkpoint from
+    /// finishing.
+    pub fn checkpoint(&self) -> SqlResult<()> {
+        if matches!(self.source, GraphSource::InMemory) {
+            return Ok(());
+        }
+        let busy: i64 = self
+            .conn
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| row.get(0))?;
+        if busy != 0 {
+            return Err(rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+                Some("WAL checkpoint could not complete".to_string()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Run an ad-hoc read-only query for what the API doesn't cover (custom
+    /// aggregates, joins across `intents` and `intent_interfaces`). Each row
+    /// comes back as a map from column name to value; blobs become arrays of
+    /// bytes.
+    ///
+    /// Only statements starting with `SELECT` or `PRAGMA` that SQLite also
+    /// reports as read-only are accepted; anything else fails with
+    /// `InvalidQuery` before running. That guards against accidents, not
+    /// against a hostile caller: the schema is internal and may change
+    /// between versions, queries can be arbitrarily slow, and a read-only
+    /// PRAGMA can still reveal more than the API does.
+    pub fn query_raw(
+        &self,
+        sql: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> SqlResult<Vec<std::collections::HashMap<String, serde_json::Value>>> {
+        let head = sql.trim_start().get(..6).unwrap_or("").to_ascii_uppercase();
+        if head != "SELECT" && head != "PRAGMA" {
+            return Err(rusqlite::Error::InvalidQuery);
+        }
+        let mut stmt = self.conn.prepare(sql)?;
+        if !stmt.readonly() {
+            return Err(rusqlite::Error::InvalidQuery);
+        }
+
+        let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let mut rows = stmt.query(params)?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next()? {
+            let mut map = std::collections::HashMap::with_capacity(columns.len());
+            for (i, column) in columns.iter().enumerate() {
+                use rusqlite::types::ValueRef;
+                let value = match row.get_ref(i)? {
+                    ValueRef::Null => serde_json::Value::Null,
+                    ValueRef::Integer(n) => n.into(),
+                    ValueRef::Real(x) => x.into(),
+                    ValueRef::Text(text) => String::from_utf8_lossy(text).into(),
+                    ValueRef::Blob(bytes) => bytes.to_vec().into(),
+                };
+                map.insert(column.clone(), value);
+            }
+            out.push(map);
+        }
+        Ok(out)
+    }
+
+    /// Delete every intent and its index entries in one transaction, keeping
+    /// the schema and this handle's configuration (scorer, clock, guards,
+    /// name matching). Refuses read-only graphs with `SQLITE_READONLY`
+    /// rather than attempting the delete. Publish sequence numbers (see
+    /// [`query_after_seq`](Self::query_after_seq)) keep counting up.
+    pub fn clear(&self) -> SqlResult<()> {
+        if self.conn.is_readonly(rusqlite::MAIN_DB)? {
+            return Err(rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_READONLY),
+                Some("cannot clear a read-only graph".to_string()),
+            ));
+        }
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute_batch(
+            "DELETE FROM intent_interfaces;
+             DELETE FROM intents;",
+        )?;
+        tx.commit()?;
+        if let Some(cache) = &self.stability_cache {
+            lock_cache(cache).clear();
+        }
+        Ok(())
+    }
+
+    /// Get a snapshot summary of the graph state.
+    ///
+    /// `conflict_count` resolves every intent, so it is only filled in for
+    /// graphs of at most [`SUMMARY_CONFLICT_LIMIT`] intents; use
+    /// [`summary_with_conflicts`](Self::summary_with_conflicts) to force it.
+    pub fn summary(&self) -> SqlResult<GraphSummary> {
+        let mut summary = self.summary_without_conflicts()?;
+        if summary.total_intents <= SUMMARY_CONFLICT_LIMIT {
+            summary.conflict_count = Some(self.conflict_count(0.0)?);
+        }
+        Ok(summary)
+    }
+
+    /// Like [`summary`](Self::summary), but always counts conflicts among
+    /// intents at or above `min_stability`, whatever the graph size.
+    pub fn summary_with_conflicts(&self, min_stability: f64) -> SqlResult<GraphSummary> {
+        let mut summary = self.summary_without_conflicts()?;
+        summary.conflict_count = Some(self.conflict_count(min_stability)?);
+        Ok(summary)
+    }
+
+    /// Counts and stability figures straight from SQL aggregates over the
+    /// stored `computed_stability`, without loading any intent.
+    fn summary_without_conflicts(&self) -> SqlResult<GraphSummary> {
+        let (total, avg_stability, high_stability): (i64, f64, i64) = self.conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(AVG(computed_stability), 0.0),
+                    COUNT(CASE WHEN computed_stability >= 0.7 THEN 1 END)
+             FROM intents",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        let agents: Vec<String> = {
+            let mut stmt = self
+                .conn
+                .prepare_cached("SELECT DISTINCT agent_id FROM intents ORDER BY agent_id")?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            rows.collect::<SqlResult<_>>()?
+        };
+
+        Ok(GraphSummary {
+            total_intents: total as usize,
+            agent_count: agents.len(),
+            agents,
+            average_stability: avg_stability,
+            high_stability_count: high_stability as usize,
+            conflict_count: None,
+        })
+    }
+
+    /// How well each agent's requirements line up with each other agent's
+    /// provisions, as `(consumer, provider, score)` sorted by consumer then
+    /// provider. The score is the fraction of the consumer's requirements
+    /// (across its intents) that some provision of the provider structurally
+    /// overlaps and [satisfies](InterfaceSpec::satisfies). Only intents at or
+    /// above `min_stability` count; agents with no requirements get no rows.
+    ///
+    /// Providers are narrowed per requirement through the interface index, so
+    /// only candidate intents are compared spec by spec.
+    pub fn compatibility_matrix(
+        &self,
+        min_stability: f64,
+    ) -> SqlResult<Vec<(String, String, f64)>> {
+        let all = self.query_all(Some(min_stability))?;
+        let by_id: std::collections::HashMap<&str, &IntentNode> =
+            all.iter().map(|i| (i.id.as_str(), i)).collect();
+        let agents: std::collections::BTreeSet<&str> =
+            all.iter().map(|i| i.agent_id.as_str()).collect();
+
+        let mut matrix = Vec::new();
+        for &consumer in &agents {
+            let requirements: Vec<&InterfaceSpec> = all
+                .iter()
+                .filter(|i| i.agent_id == consumer)
+                .flat_map(|i| &i.requires)
+                .collect();
+            if requirements.is_empty() {
+                continue;
+            }
+
+            let mut satisfied: std::collections::HashMap<&str, usize> =
+                std::collections::HashMap::new();
+            for req in &requirements {
+                let [hits] = self.role_candidates(
+                    [std::slice::from_ref(*req)],
+                    Some(consumer),
+                    min_stability,
+                )?;
+                let providers: std::collections::HashSet<&str> = hits
+                    .iter()
+                    .filter(|(_, role)| role == Role::Provides.as_str())
+                    .filter_map(|(id, _)| by_id.get(id.as_str()))
+                    .filter(|provider| {
+                        provider.provides.iter().any(|prov| {
+                            req.structurally_overlaps_with(prov, self.name_match)
+                                && prov.satisfies(req)
+                        })
+                    })
+                    .map(|provider| provider.agent_id.as_str())
+                    .collect();
+                for provider in providers {
+                    *satisfied.entry(provider).or_default() += 1;
+                }
+            }
+
+            for &provider in agents.iter().filter(|&&a| a != consumer) {
+                let count = satisfied.get(provider).copied().unwrap_or(0);
+                matrix.push((
+                    consumer.to_string(),
+                    provider.to_string(),
+                    count as f64 / requirements.len() as f64,
+                ));
+            }
+        }
+        Ok(matrix)
+    }
+
+    /// Groups of intents at or above `min_stability` that provide the same
+    /// interface compatibly: different agents' provisions structurally
+    /// overlap and, for every overlapping pair, one signature offers at least
+    /// the other's fields. A coordinator can keep one provider per group.
+    ///
+    /// Pairs with any incompatible overlapping provision are conflicts rather
+    /// than duplicates and don't link. Grouping is transitive, groups have at
+    /// least two intents, and both groups and their members are oldest first.
+    /// An intent never groups with its own ancestors or descendants via
+    /// `parent_id`.
+    pub fn consolidation_candidates(&self, min_stability: f64) -> SqlResult<Vec<Vec<IntentNode>>> {
+        let all = self.query_all(Some(min_stability))?;
+        let index: std::collections::HashMap<&str, usize> = all
+            .iter()
+            .enumerate()
+            .map(|(i, intent)| (intent.id.as_str(), i))
+            .collect();
+
+        // Union-find over positions in `all`; the root is the oldest member
+        let mut parent: Vec<usize> = (0..all.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for (i, intent) in all
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| !i.provides.is_empty())
+        {
+            let agent_key = self.agent_key(&intent.agent_id);
+            let [hits] =
+                self.role_candidates([&intent.provides], Some(agent_key.as_ref()), min_stability)?;
+            let lineage = self.lineage_ids(intent)?;
+            for j in hits
+                .iter()
+                .filter(|(_, role)| role == Role::Provides.as_str())
+                .filter(|(id, _)| !lineage.contains(id))
+                .filter_map(|(id, _)| index.get(id.as_str()).copied())
+            {
+                let other = &all[j];
+                if self.same_agent(&intent.agent_id, &other.agent_id) {
+                    continue;
+                }
+                let overlapping: Vec<(&InterfaceSpec, &InterfaceSpec)> = intent
+                    .provides
+                    .iter()
+                    .flat_map(|mine| other.provides.iter().map(move |theirs| (mine, theirs)))
+                    .filter(|(mine, theirs)| {
+                        mine.structurally_overlaps_with(theirs, self.name_match)
+                    })
+                    .collect();
+                let compatible = overlapping
+                    .iter()
+                    .all(|(mine, theirs)| mine.satisfies(theirs) || theirs.satisfies(mine));
+                if !overlapping.is_empty() && compatible {
+                    let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                    parent[ri.max(rj)] = ri.min(rj);
+                }
+            }
+        }
+
+        let mut groups: std::collections::BTreeMap<usize, Vec<IntentNode>> =
+            std::collections::BTreeMap::new();
+        for (i, intent) in all.iter().enumerate() {
+            let r = root(&mut parent, i);
+            groups.entry(r).or_default().push(intent.clone());
+        }
+        Ok(groups.into_values().filter(|g| g.len() >= 2).collect())
+    }
+
+    /// Count intents per stability bucket. `edges` are the inner bucket
+    /// boundaries, strictly increasing within [0.0, 1.0]: `[0.3, 0.7]` yields
+    /// three counts, for `< 0.3`, `0.3..0.7`, and `>= 0.7`. Each edge belongs
+    /// to the bucket above it. Counted in SQL over `computed_stability`.
+    ///
+    /// Invalid edges fail with [`rusqlite::Error::ToSqlConversionFailure`].
+    pub fn stability_histogram(&self, edges: &[f64]) -> SqlResult<Vec<usize>> {
+        let invalid = |msg: String| rusqlite::Error::ToSqlConversionFailure(msg.into());
+        if let Some(edge) = edges.iter().find(|e| !(0.0..=1.0).contains(*e)) {
+            return Err(invalid(format!(
+                "histogram edge {} is outside [0.0, 1.0]",
+                edge
+            )));
+        }
+        if let Some(pair) = edges.windows(2).find(|pair| pair[0] >= pair[1]) {
+            return Err(invalid(format!(
+                "histogram edges must be strictly increasing, got {} before {}",
+                pair[0], pair[1]
+            )));
+        }
+
+        // Each bucket is the first edge the score falls below
+        let bucket = if edges.is_empty() {
+            "0".to_string()
+        } else {
+            let cases: String = (0..edges.len())
+                .map(|i| format!("WHEN computed_stability < ?{} THEN {} ", i + 1, i))
+                .collect();
+            format!("CASE {}ELSE {} END", cases, edges.len())
+        };
+        let sql = format!(
+            "SELECT {} AS bucket, COUNT(*)
+             FROM intents
+             WHERE computed_stability IS NOT NULL
+             GROUP BY bucket",
+            bucket
+        );
+        let mut counts = vec![0; edges.len() + 1];
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(edges), |row| {
+            Ok((
+                row.get::<_, i64>(0)? as usize,
+                row.get::<_, i64>(1)? as usize,
+            ))
+        })?;
+        for row in rows {
+            let (bucket, count) = row?;
+            counts[bucket] = count;
+        }
+        Ok(counts)
+    }
+
+    /// Per-agent stability figures, highest average stability first (ties by
+    /// agent id). Counts and averages are SQL aggregates over the stored
+    /// `computed_stability`. Conflict involvement resolves every intent, so,
+    /// as in [`summary`](Self::summary), it is only filled in for graphs of at
+    /// most [`SUMMARY_CONFLICT_LIMIT`] intents; use
+    /// [`agent_stability_stats_with_conflicts`](Self::agent_stability_stats_with_conflicts)
+    /// to force it.
+    pub fn agent_stability_stats(&self) -> SqlResult<Vec<AgentStats>> {
+        let mut stats = self.agent_stability_stats_without_conflicts()?;
+        let total: usize = stats.iter().map(|s| s.intent_count).sum();
+        if total <= SUMMARY_CONFLICT_LIMIT {
+            self.fill_agent_conflicts(&mut stats, 0.0)?;
+        }
+        Ok(stats)
+    }
+
+    /// Like [`agent_stability_stats`](Self::agent_stability_stats), but
+    /// always counts each agent's conflicting pairs among intents at or above
+    /// `min_stability`, whatever the graph size.
+    pub fn agent_stability_stats_with_conflicts(
+        &self,
+        min_stability: f64,
+    ) -> SqlResult<Vec<AgentStats>> {
+        let mut stats = self.agent_stability_stats_without_conflicts()?;
+        self.fill_agent_conflicts(&mut stats, min_stability)?;
+        Ok(stats)
+    }
+
+    fn agent_stability_stats_without_conflicts(&self) -> SqlResult<Vec<AgentStats>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT agent_id, COUNT(*),
+                    AVG(COALESCE(computed_stability, 0.0)),
+                    MAX(COALESCE(computed_stability, 0.0))
+             FROM intents
+             GROUP BY agent_id
+             ORDER BY AVG(COALESCE(computed_stability, 0.0)) DESC, agent_id ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(AgentStats {
+                agent_id: row.get(0)?,
+                intent_count: row.get::<_, i64>(1)? as usize,
+                average_stability: row.get(2)?,
+                max_stability: row.get(3)?,
+                conflict_count: None,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Count the distinct conflicting pairs (as in
+    /// [`conflict_count`](Self::conflict_count)) that include one of each
+    /// agent's intents.
+    fn fill_agent_conflicts(&self, stats: &mut [AgentStats], min_stability: f64) -> SqlResult<()> {
+        let owners: std::collections::HashMap<String, String> = {
+            let mut stmt = self.conn.prepare("SELECT id, agent_id FROM intents")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<SqlResult<_>>()?
+        };
+        let mut pairs = std::collections::HashSet::new();
+        self.for_each_conflict(min_stability, |conflict| {
+            let mut pair = [conflict.my_intent_id, conflict.their_intent_id];
+            pair.sort();
+            pairs.insert(pair);
+            Ok(())
+        })?;
+        let mut involvement: std::collections::HashMap<&str, usize> =
+            std::collections::HashMap::new();
+        for pair in &pairs {
+            let mut agents: Vec<&str> = pair
+                .iter()
+                .filter_map(|id| owners.get(id).map(String::as_str))
+                .collect();
+            agents.dedup();
+            for agent in agents {
+                *involvement.entry(agent).or_default() += 1;
+            }
+        }
+        for entry in stats {
+            entry.conflict_count = Some(
+                involvement
+                    .get(entry.agent_id.as_str())
+                    .copied()
+                    .unwrap_or(0),
+            );
+        }
+        Ok(())
+    }
+
+    /// Check the denormalized `intent_interfaces` index against the intents table.
+    ///
+    /// Overlap queries only see what the index holds, so "find_overlapping
+    /// seems to miss things" usually means one of the counts here is nonzero.
+    pub fn index_health(&self) -> SqlResult<IndexHealth> {
+        let interface_rows: i64 =
+            self.conn
+                .query_row("SELECT COUNT(*) FROM intent_interfaces", [], |row| {
+                    row.get(0)
+                })?;
+        let orphaned_rows: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM intent_interfaces ii
+             WHERE NOT EXISTS (SELECT 1 FROM intents i WHERE i.id = ii.intent_id)",
+            [],
+            |row| row.get(0),
+        )?;
+        let intents_missing_entries: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM intents i
+             WHERE json_array_length(i.provides) + json_array_length(i.requires)
+                   > (SELECT COUNT(*) FROM intent_interfaces ii WHERE ii.intent_id = i.id)",
+            [],
+            |row| row.get(0),
+        )?;
+
+        Ok(IndexHealth {
+            interface_rows: interface_rows as usize,
+            orphaned_rows: orphaned_rows as usize,
+            intents_missing_entries: intents_missing_entries as usize,
+        })
+    }
+
+    /// Derive the consumes graph: `(consumer_intent_id, provider_intent_id)` pairs.
+    ///
+    /// An edge exists when one intent requires an interface that another
+    /// agent's intent provides in a structurally overlapping,
+    /// signature-compatible form: exactly the requirements
+    /// [`unsatisfied_requirements`](Self::unsatisfied_requirements) counts as
+    /// satisfied. Providers are found through the interface index rather than
+    /// by comparing every pair. Only intents at or above `min_stability`
+    /// participate. Edges are deduplicated and returned in a deterministic
+    /// order.
+    pub fn dependency_edges(&self, min_stability: f64) -> SqlResult<Vec<(String, String)>> {
+        let all = self.query_all(Some(min_stability))?;
+        let by_id: std::collections::HashMap<&str, &IntentNode> =
+            all.iter().map(|i| (i.id.as_str(), i)).collect();
+
+        let mut edges: Vec<(String, String)> = Vec::new();
+        for consumer in all.iter().filter(|i| !i.requires.is_empty()) {
+            let agent_key = self.agent_key(&consumer.agent_id);
+            let [hits] = self.role_candidates(
+                [&consumer.requires],
+                Some(agent_key.as_ref()),
+                min_stability,
+            )?;
+            for (id, _) in hits.iter().filter(|(_, role)| role == "provides") {
+                let Some(provider) = by_id.get(id.as_str()) else {
+                    continue;
+                };
+                if self.same_agent(&provider.agent_id, &consumer.agent_id) {
+                    continue;
+                }
+                let consumes = consumer.requires.iter().any(|req| {
+                    provider.provides.iter().any(|prov| {
+                        req.structurally_overlaps_with(prov, self.name_match)
+                            && req.is_satisfied_by(prov)
+                    })
+                });
+                if consumes {
+                    edges.push((consumer.id.clone(), provider.id.clone()));
+                }
+            }
+        }
+
+        edges.sort();
+        edges.dedup();
+        Ok(edges)
+    }
+
+    /// The `limit` most-consumed intents, ranked by how many distinct agents
+    /// consume them, descending. An agent counts once per intent whether it
+    /// recorded `consumed_by` evidence on it (see [`Evidence::consumer`]),
+    /// has an intent requiring it (per
+    /// [`dependency_edges`](Self::dependency_edges)), or both. Ties keep
+    /// timestamp order. Intents nobody consumes are left out. These are the
+    /// load-bearing decisions that merit extra review.
+    pub fn most_consumed(
+        &self,
+        limit: usize,
+        min_stability: f64,
+    ) -> SqlResult<Vec<(IntentNode, usize)>> {
+        let intents = self.query_all(Some(min_stability))?;
+        let agents: std::collections::HashMap<&str, &str> = intents
+            .iter()
+            .map(|i| (i.id.as_str(), i.agent_id.as_str()))
+            .collect();
+        let mut consumers: std::collections::HashMap<String, std::collections::HashSet<String>> =
+            std::collections::HashMap::new();
+        for intent in &intents {
+            for agent in intent.evidence.iter().filter_map(|e| e.consumer()) {
+                consumers
+                    .entry(intent.id.clone())
+                    .or_default()
+                    .insert(self.agent_key(agent).into_owned());
+            }
+        }
+        for (consumer, provider) in self.dependency_edges(min_stability)? {
+            if let Some(agent) = agents.get(consumer.as_str()) {
+                consumers
+                    .entry(provider)
+                    .or_default()
+                    .insert(self.agent_key(agent).into_owned());
+            }
+        }
+
+        let mut ranked: Vec<(IntentNode, usize)> = intents
+            .into_iter()
+            .filter_map(|intent| {
+                let count = consumers.get(&intent.id)?.len();
+                Some((intent, count))
+            })
+            .collect();
+        ranked.sort_by_key(|(_, consumers)| std::cmp::Reverse(*consumers));
+        ranked.truncate(limit);
+        Ok(ranked)
+    }
+
+    /// Required interfaces that nothing provides: each `(owning intent, requires
+    /// spec)` for which no intent from another agent provides a structurally
+    /// overlapping, signature-compatible interface. Only intents at or above
+    /// `min_stability` participate, on both sides. These are integration gaps
+    /// that `resolve` stays silent about.
+    pub fn unsatisfied_requirements(
+        &self,
+        min_stability: f64,
+    ) -> SqlResult<Vec<(IntentNode, InterfaceSpec)>> {
+        let all = self.query_all(Some(min_stability))?;
+
+        let mut gaps = Vec::new();
+        for consumer in &all {
+            for req in &consumer.requires {
+                let satisfied = all
+                    .iter()
+                    .filter(|provider| !self.same_agent(&provider.agent_id, &consumer.agent_id))
+                    .flat_map(|provider| &provider.provides)
+                    .any(|prov| {
+                        req.structurally_overlaps_with(prov, self.name_match)
+                            && req.is_satisfied_by(prov)
+                    });
+                if !satisfied {
+                    gaps.push((consumer.clone(), req.clone()));
+                }
+            }
+        }
+        Ok(gaps)
+    }
+
+    /// Render the consumes graph as Graphviz DOT.
+    ///
+    /// Nodes are labeled `agent_id` + `intent`; edges point from consumer to
+    /// provider (see [`dependency_edges`](Self::dependency_edges)).
+    pub fn to_dot(&self, min_stability: f64) -> SqlResult<String> {
+        let intents = self.query_all(Some(min_stability))?;
+        let edges = self.dependency_edges(min_stability)?;
+
+        let mut dot = String::from("digraph intents {\n");
+        for intent in &intents {
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\\n{}\"];\n",
+                dot_escape(&intent.id),
+                dot_escape(&intent.agent_id),
+                dot_escape(&intent.intent),
+            ));
+        }
+        for (consumer, provider) in &edges {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\";\n",
+                dot_escape(consumer),
+                dot_escape(provider),
+            ));
+        }
+        dot.push_str("}\n");
+        Ok(dot)
+    }
+
+    fn row_to_intent(&self, row: &rusqlite::Row) -> SqlResult<IntentNode> {
+        let provides_json: String = row.get(4).unwrap_or_default();
+        let requires_json: String = row.get(5).unwrap_or_default();
         let constraints_json: String = row.get(6).unwrap_or_default();
         let evidence_json: String = row.get(8).unwrap_or_default();
+        let labels_json: String = row.get(11).unwrap_or_default();
 
-        IntentNode {
+        // A bad timestamp is an error rather than "now": substituting the
+        // current time would silently reorder the graph.
+        let timestamp: String = row.get(2)?;
+        let timestamp = DateTime::parse_from_rfc3339(&timestamp)
+            .map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    2,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?
+            .with_timezone(&Utc);
+
+        Ok(IntentNode {
             id: row.get(0).unwrap_or_default(),
             agent_id: row.get(1).unwrap_or_default(),
-            timestamp: row
-                .get::<_, String>(2)
-                .ok()
-                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(Utc::now),
+            timestamp,
             intent: row.get(3).unwrap_or_default(),
             provides: serde_json::from_str(&provides_json).unwrap_or_default(),
             requires: serde_json::from_str(&requires_json).unwrap_or_default(),
@@ -509,192 +3434,4452 @@ impl IntentGraph {
             stability: row.get(7).unwrap_or(0.3),
             evidence: serde_json::from_str(&evidence_json).unwrap_or_default(),
             parent_id: row.get(9).ok(),
-        }
+            labels: serde_json::from_str(&labels_json).unwrap_or_default(),
+            computed_stability: row.get(10).unwrap_or(None),
+        })
+    }
+}
+
+/// Whether two resolutions of one intent reach the same outcome: the same
+/// adjustments against the same intents, conflict winners, and adopted and
+/// rejected constraints. Descriptions and stability figures are ignored.
+fn same_outcome(a: &ResolutionResult, b: &ResolutionResult) -> bool {
+    let adjustments = |r: &ResolutionResult| -> Vec<(AdjustmentKind, String)> {
+        r.adjustments
+            .iter()
+            .map(|adj| (adj.kind.clone(), adj.source_intent_id.clone()))
+            .collect()
+    };
+    let conflicts = |r: &ResolutionResult| -> Vec<(String, Winner)> {
+        r.conflicts
+            .iter()
+            .map(|c| (c.their_intent_id.clone(), c.winner))
+            .collect()
+    };
+    adjustments(a) == adjustments(b)
+        && conflicts(a) == conflicts(b)
+        && a.adopted_constraints == b.adopted_constraints
+        && a.rejected_constraints == b.rejected_constraints
+}
+
+/// Largest graph for which [`IntentGraph::summary`] and
+/// [`IntentGraph::agent_stability_stats`] count conflicts, since doing so
+/// resolves every intent against the graph.
+pub const SUMMARY_CONFLICT_LIMIT: usize = 500;
+
+/// Options controlling [`IntentGraph::find_overlapping_with`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OverlapOptions {
+    /// Also match intents from the excluded agent, which lets an agent
+    /// running several sub-scopes detect clashes between its own intents.
+    pub include_self: bool,
+    /// Only match the other intents' provisions or requirements ("is anyone
+    /// already providing this?"); `None` checks both.
+    pub role_filter: Option<Role>,
+}
+
+impl OverlapOptions {
+    pub fn with_include_self(mut self, include_self: bool) -> Self {
+        self.include_self = include_self;
+        self
+    }
+
+    pub fn with_role_filter(mut self, role_filter: Option<Role>) -> Self {
+        self.role_filter = role_filter;
+        self
     }
 }
 
+/// Options controlling [`IntentGraph::resolve_with`].
 #[derive(Debug, Clone)]
-pub struct GraphSummary {
-    pub total_intents: usize,
-    pub agent_count: usize,
-    pub agents: Vec<String>,
-    pub average_stability: f64,
-    pub high_stability_count: usize,
+pub struct ResolveOptions {
+    /// Only resolve against intents at or above this computed stability.
+    pub min_stability: f64,
+    /// Only report conflicts when both intents clear `min_stability`. When the
+    /// resolving intent is below the floor, each conflict becomes a `YieldTo`
+    /// adjustment instead — an exploring intent should simply give way.
+    pub both_above: bool,
+    /// Which constraint severities are adopted; the rest become notes.
+    pub adopt_policy: AdoptPolicy,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::{Evidence, InterfaceKind};
+impl ResolveOptions {
+    pub fn new(min_stability: f64) -> Self {
+        Self {
+            min_stability,
+            both_above: false,
+            adopt_policy: AdoptPolicy::default(),
+        }
+    }
+
+    pub fn with_both_above(mut self, both_above: bool) -> Self {
+        self.both_above = both_above;
+        self
+    }
+
+    pub fn with_adopt_policy(mut self, adopt_policy: AdoptPolicy) -> Self {
+        self.adopt_policy = adopt_policy;
+        self
+    }
+}
+
+/// Upper bounds on the size of a published intent, so a malformed agent
+/// can't bloat the database with a megabyte signature. Checked on every
+/// insert before the publish guards; an intent over any limit is refused
+/// with a [`PublishRejected`]. The defaults are far above anything normal
+/// use produces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishLimits {
+    /// Longest signature, in bytes
+    pub max_signature_len: usize,
+    /// Most tags on one interface spec
+    pub max_tags: usize,
+    /// Most entries in `provides`, and separately in `requires`
+    pub max_specs: usize,
+}
+
+impl Default for PublishLimits {
+    fn default() -> Self {
+        Self {
+            max_signature_len: 16 * 1024,
+            max_tags: 64,
+            max_specs: 256,
+        }
+    }
+}
+
+impl PublishLimits {
+    /// Why `intent` exceeds these limits, if it does.
+    pub fn check(&self, intent: &IntentNode) -> Result<(), String> {
+        for (section, specs) in [
+            ("provides", &intent.provides),
+            ("requires", &intent.requires),
+        ] {
+            if specs.len() > self.max_specs {
+                return Err(format!(
+                    "{} has {} entries; the limit is {}",
+                    section,
+                    specs.len(),
+                    self.max_specs
+                ));
+            }
+            for spec in specs.iter() {
+                if spec.signature.len() > self.max_signature_len {
+                    return Err(format!(
+                        "signature of '{}' is {} bytes; the limit is {}",
+                        spec.name,
+                        spec.signature.len(),
+                        self.max_signature_len
+                    ));
+                }
+                if spec.tags.len() > self.max_tags {
+                    return Err(format!(
+                        "'{}' has {} tags; the limit is {}",
+                        spec.name,
+                        spec.tags.len(),
+                        self.max_tags
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Which intents [`IntentGraph::export_json_filtered`] exports. Every
+/// restriction that is set must hold; the default exports everything.
+#[derive(Debug, Clone, Default)]
+pub struct ExportFilter {
+    /// Only intents from these agents.
+    pub agents: Option<Vec<String>>,
+    /// Only intents with a provided or required interface carrying at least
+    /// one of these tags (exact, case-sensitive).
+    pub tags: Option<Vec<String>>,
+    /// Only intents at or above this computed stability.
+    pub min_stability: f64,
+}
+
+impl ExportFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_agents(mut self, agents: Vec<&str>) -> Self {
+        self.agents = Some(agents.into_iter().map(String::from).collect());
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<&str>) -> Self {
+        self.tags = Some(tags.into_iter().map(String::from).collect());
+        self
+    }
+
+    pub fn with_min_stability(mut self, min_stability: f64) -> Self {
+        self.min_stability = min_stability;
+        self
+    }
+
+    /// Agent and tag restrictions; `min_stability` is applied by the query.
+    fn matches(&self, intent: &IntentNode) -> bool {
+        let agent_ok = self
+            .agents
+            .as_ref()
+            .is_none_or(|agents| agents.contains(&intent.agent_id));
+        let tags_ok = self.tags.as_ref().is_none_or(|tags| {
+            intent
+                .provides
+                .iter()
+                .chain(&intent.requires)
+                .any(|spec| spec.tags.iter().any(|t| tags.contains(t)))
+        });
+        agent_ok && tags_ok
+    }
+}
+
+/// Outcome of [`IntentGraph::publish_verbose`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishStats {
+    /// Id the intent was stored under, generated if it had none.
+    pub id: String,
+    /// Computed stability of the published intent.
+    pub stability: f64,
+    /// Non-fatal problems found in the intent. The intent is published regardless.
+    pub warnings: Vec<PublishWarning>,
+}
+
+/// Several specs in one section of an intent share a normalized name.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PublishWarning {
+    /// `"provides"` or `"requires"`.
+    pub section: String,
+    pub normalized_name: String,
+    /// The original spec names, in declaration order.
+    pub names: Vec<String>,
+}
+
+/// A problem found by [`IntentGraph::validate_intent`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationIssue {
+    pub severity: IssueSeverity,
+    pub kind: IssueKind,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn error(kind: IssueKind, message: String) -> Self {
+        Self {
+            severity: IssueSeverity::Error,
+            kind,
+            message,
+        }
+    }
+
+    fn warning(kind: IssueKind, message: String) -> Self {
+        Self {
+            severity: IssueSeverity::Warning,
+            kind,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum IssueSeverity {
+    /// Publish would fail, or the intent is meaningless as written
+    Error,
+    /// Publish succeeds, but matching against the intent is degraded
+    Warning,
+}
+
+impl IssueSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueSeverity::Error => "error",
+            IssueSeverity::Warning => "warning",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum IssueKind {
+    /// A required string field is blank
+    EmptyField,
+    /// `parent_id` names an intent that isn't in the graph
+    DanglingParent,
+    /// A signature has parts the matcher can't read
+    UnparseableSignature,
+    /// Specs in one section share a normalized name
+    DuplicateName,
+}
+
+impl IssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueKind::EmptyField => "empty_field",
+            IssueKind::DanglingParent => "dangling_parent",
+            IssueKind::UnparseableSignature => "unparseable_signature",
+            IssueKind::DuplicateName => "duplicate_name",
+        }
+    }
+}
+
+/// Group specs by normalized name and report every group with more than one member.
+fn name_collisions(section: &str, specs: &[InterfaceSpec]) -> Vec<PublishWarning> {
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for spec in specs {
+        let normalized = crate::matching::normalize_name(&spec.name);
+        match groups.iter_mut().find(|(n, _)| *n == normalized) {
+            Some((_, names)) => names.push(spec.name.clone()),
+            None => groups.push((normalized, vec![spec.name.clone()])),
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .map(|(normalized_name, names)| PublishWarning {
+            section: section.to_string(),
+            normalized_name,
+            names,
+        })
+        .collect()
+}
+
+/// Phase-1 filter on `ii.normalized_name` against the queried name `?3`.
+/// Each is a superset of what [`NameMatchMode`] accepts in Rust; fuzzy
+/// matching can't be expressed in SQL, so it loads every name.
+fn name_match_sql(mode: NameMatchMode) -> &'static str {
+    match mode {
+        NameMatchMode::Exact => "ii.normalized_name = ?3",
+        NameMatchMode::Prefix => {
+            "ii.normalized_name LIKE ?3 || '%' OR ?3 LIKE ii.normalized_name || '%'"
+        }
+        NameMatchMode::Substring => {
+            "ii.normalized_name LIKE '%' || ?3 || '%' OR ?3 LIKE '%' || ii.normalized_name || '%'"
+        }
+        NameMatchMode::Fuzzy => "?3 IS NOT NULL",
+    }
+}
+
+/// The one format timestamps are written to and compared in SQL: RFC 3339
+/// in UTC with fixed nanosecond precision, so that string order is time
+/// order and reads round-trip exactly.
+fn sql_timestamp(at: &DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(chrono::SecondsFormat::Nanos, false)
+}
+
+/// Lock the stability cache. A poisoned lock only means a panic mid-update of
+/// derived data, so recover the guard and keep going.
+fn lock_cache(cache: &Mutex<StabilityCache>) -> std::sync::MutexGuard<'_, StabilityCache> {
+    cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Escape a string for use inside a double-quoted DOT identifier or label.
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// How an [`IntentGraph`] was opened; see [`IntentGraph::source`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum GraphSource {
+    InMemory,
+    File {
+        /// Path as passed to [`IntentGraph::persistent`]
+        path: String,
+        read_only: bool,
+    },
+}
+
+/// One agent's row in [`IntentGraph::agent_stability_stats`].
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentStats {
+    pub agent_id: String,
+    pub intent_count: usize,
+    pub average_stability: f64,
+    pub max_stability: f64,
+    /// Distinct conflicting intent pairs the agent is part of, or `None`
+    /// when the graph was too large to count them as part of
+    /// [`IntentGraph::agent_stability_stats`].
+    pub conflict_count: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphSummary {
+    pub total_intents: usize,
+    pub agent_count: usize,
+    pub agents: Vec<String>,
+    pub average_stability: f64,
+    pub high_stability_count: usize,
+    /// Distinct conflicting intent pairs, or `None` when the graph was too
+    /// large to count them as part of [`IntentGraph::summary`].
+    pub conflict_count: Option<usize>,
+}
+
+/// Outcome of [`IntentGraph::simulate_convergence`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ConvergenceReport {
+    /// Conflicting pairs before the first step
+    pub initial_conflicts: usize,
+    /// Conflicting pairs after the last step
+    pub final_conflicts: usize,
+    /// One entry per id in the simulated order
+    pub steps: Vec<ConvergenceStep>,
+}
+
+impl ConvergenceReport {
+    /// Whether the order ends with no conflicts left.
+    pub fn converged(&self) -> bool {
+        self.final_conflicts == 0
+    }
+}
+
+/// Outcome of [`IntentGraph::dry_run_batch`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchReport {
+    /// Conflicts with a batch intent on at least one side
+    pub new_conflicts: Vec<ConflictReport>,
+    /// Validation issues, as (batch intent id, issue)
+    pub issues: Vec<(String, ValidationIssue)>,
+    /// Batch intents the fork refused to publish
+    pub rejected: Vec<PublishRejected>,
+}
+
+impl BatchReport {
+    /// Whether the batch would publish without conflicts or validation errors.
+    /// Warnings don't count.
+    pub fn is_clean(&self) -> bool {
+        self.new_conflicts.is_empty()
+            && self.rejected.is_empty()
+            && self
+                .issues
+                .iter()
+                .all(|(_, issue)| issue.severity != IssueSeverity::Error)
+    }
+}
+
+/// One intent's turn in a [`ConvergenceReport`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ConvergenceStep {
+    pub intent_id: String,
+    /// Adjustments applied to the intent
+    pub applied: usize,
+    /// Adjustments that can't be applied mechanically
+    pub skipped: usize,
+    /// Conflicting pairs after this step
+    pub conflicts: usize,
+}
+
+/// One decision recorded by [`IntentGraph::resolve_traced`].
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceStep {
+    pub check: TraceCheck,
+    /// The other intent involved
+    pub their_intent_id: String,
+    /// Name of the resolving intent's spec; empty for constraints and yields
+    pub mine: String,
+    /// Name of the other intent's spec, or the constraint's target
+    pub theirs: String,
+    /// What was observed, e.g. `"structural overlap=true; signature compatible=false"`
+    pub detail: String,
+    /// The adjustment kind produced, `"conflict"`, or `"no action"`
+    pub outcome: String,
+}
+
+impl std::fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.check.as_str())?;
+        match (self.mine.is_empty(), self.theirs.is_empty()) {
+            (false, _) => write!(
+                f,
+                "compared my '{}' to their '{}' (intent {})",
+                self.mine, self.theirs, self.their_intent_id
+            )?,
+            (true, false) => write!(f, "'{}' from intent {}", self.theirs, self.their_intent_id)?,
+            (true, true) => write!(f, "intent {}", self.their_intent_id)?,
+        }
+        write!(f, "; {} → {}", self.detail, self.outcome)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TraceCheck {
+    /// My provision against their provision
+    DuplicateProvision,
+    /// My requirement against their provision
+    RequiredProvision,
+    /// My requirement against their requirement
+    RequiredRequirement,
+    /// A constraint from another intent that applies to mine
+    Constraint,
+    /// A conflict turned into a yield because I am below the floor
+    Yield,
+    /// An unprovided requirement matched to a likely renamed provision
+    Rename,
+}
+
+impl TraceCheck {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TraceCheck::DuplicateProvision => "duplicate_provision",
+            TraceCheck::RequiredProvision => "required_provision",
+            TraceCheck::RequiredRequirement => "required_requirement",
+            TraceCheck::Constraint => "constraint",
+            TraceCheck::Yield => "yield",
+            TraceCheck::Rename => "rename",
+        }
+    }
+}
+
+/// Push a trace step when tracing; the step is only built if it's kept.
+fn record(trace: &mut Option<&mut Vec<TraceStep>>, step: impl FnOnce() -> TraceStep) {
+    if let Some(trace) = trace {
+        trace.push(step());
+    }
+}
+
+/// Ownership of one interface; see [`IntentGraph::interface_provenance`].
+#[derive(Debug, Clone, Serialize)]
+pub struct InterfaceProvenance {
+    /// The normalized interface name that was matched
+    pub name: String,
+    /// The first intent to provide it, by timestamp
+    pub earliest_provider: Option<IntentNode>,
+    /// Every intent providing it, oldest first
+    pub providers: Vec<IntentNode>,
+    /// Every intent requiring it, oldest first
+    pub requirers: Vec<IntentNode>,
+}
+
+/// Consistency report for the interface index; see [`IntentGraph::index_health`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexHealth {
+    /// Total rows in `intent_interfaces`
+    pub interface_rows: usize,
+    /// Index rows whose intent no longer exists
+    pub orphaned_rows: usize,
+    /// Intents with fewer index rows than declared provides + requires
+    pub intents_missing_entries: usize,
+}
+
+impl IndexHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.orphaned_rows == 0 && self.intents_missing_entries == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConstraintSeverity, EvidenceKind, Outcome, DEFAULT_STABILITY};
+
+    fn make_graph() -> IntentGraph {
+        IntentGraph::in_memory().unwrap()
+    }
+
+    #[test]
+    fn test_publish_and_query() {
+        let graph = make_graph();
+        let intent = IntentNode::new("agent-a", "Build AuthService");
+        graph.publish(&intent).unwrap();
+
+        let all = graph.query_all(None).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].agent_id, "agent-a");
+    }
+
+    #[test]
+    fn test_query_by_agent() {
+        let graph = make_graph();
+        graph.publish(&IntentNode::new("agent-a", "Auth")).unwrap();
+        graph
+            .publish(&IntentNode::new("agent-b", "Recipes"))
+            .unwrap();
+        graph
+            .publish(&IntentNode::new("agent-a", "Auth v2"))
+            .unwrap();
+
+        let a_intents = graph.query_by_agent("agent-a").unwrap();
+        assert_eq!(a_intents.len(), 2);
+    }
+
+    #[test]
+    fn test_stability_filter() {
+        let graph = make_graph();
+
+        // Low stability intent
+        let low = IntentNode::new("agent-a", "exploring");
+        graph.publish(&low).unwrap();
+
+        // High stability intent
+        let high = IntentNode::new("agent-b", "committed").with_evidence(vec![
+            Evidence::code_committed("initial"),
+            Evidence::test_pass("tests"),
+            Evidence::consumed_by("agent-c"),
+        ]);
+        graph.publish(&high).unwrap();
+
+        let high_only = graph.query_all(Some(0.6)).unwrap();
+        assert_eq!(high_only.len(), 1);
+        assert_eq!(high_only[0].agent_id, "agent-b");
+    }
+
+    #[test]
+    fn test_find_overlapping() {
+        let graph = make_graph();
+
+        // Agent A provides UserModel
+        let a = IntentNode::new("agent-a", "Auth module").with_provides(vec![InterfaceSpec::new(
+            "User",
+            InterfaceKind::Model,
+            "id: UUID, email: str",
+        )
+        .with_tags(vec!["user", "auth", "model"])]);
+        graph.publish(&a).unwrap();
+
+        // Agent B requires something user-related
+        let my_specs = vec![
+            InterfaceSpec::new("UserRef", InterfaceKind::Model, "user_id: UUID")
+                .with_tags(vec!["user", "recipe", "model"]),
+        ];
+
+        let overlapping = graph.find_overlapping(&my_specs, "agent-b", 0.0).unwrap();
+        assert_eq!(overlapping.len(), 1);
+        assert_eq!(overlapping[0].agent_id, "agent-a");
+    }
+
+    #[test]
+    fn test_find_overlapping_role_filter() {
+        let graph = make_graph();
+        let spec = || vec![InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID")];
+        let provider = IntentNode::new("agent-a", "Auth module").with_provides(spec());
+        let consumer = IntentNode::new("agent-b", "Profiles").with_requires(spec());
+        graph.publish(&provider).unwrap();
+        graph.publish(&consumer).unwrap();
+
+        let ids = |role| -> Vec<String> {
+            graph
+                .find_overlapping_with(
+                    &spec(),
+                    "agent-c",
+                    0.0,
+                    OverlapOptions::default().with_role_filter(role),
+                )
+                .unwrap()
+                .into_iter()
+                .map(|i| i.id)
+                .collect()
+        };
+        assert_eq!(ids(Some(Role::Provides)), vec![provider.id.clone()]);
+        assert_eq!(ids(Some(Role::Requires)), vec![consumer.id.clone()]);
+        assert_eq!(ids(None).len(), 2);
+    }
+
+    #[test]
+    fn test_resolve_consume_instead() {
+        let graph = make_graph();
+
+        // Agent A provides User model with high stability
+        let a = IntentNode::new("agent-a", "Auth module")
+            .with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, email: str",
+            )
+            .with_tags(vec!["user", "auth", "model"])])
+            .with_evidence(vec![
+                Evidence::code_committed("committed"),
+                Evidence::test_pass("passing"),
+            ]);
+        graph.publish(&a).unwrap();
+
+        // Agent C also wants to provide a User model but is less committed
+        let c =
+            IntentNode::new("agent-c", "Meal planning").with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, name: str",
+            )
+            .with_tags(vec!["user", "meal", "model"])]);
+
+        let result = graph.resolve(&c, 0.0).unwrap();
+        assert!(!result.adjustments.is_empty());
+        assert_eq!(result.adjustments[0].kind, AdjustmentKind::ConsumeInstead);
+    }
+
+    #[test]
+    fn test_declared_stability_does_not_win_resolution() {
+        let graph = make_graph();
+        let spec = || vec![InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID")];
+        let backed = IntentNode::new("agent-a", "Auth module")
+            .with_provides(spec())
+            .with_evidence(vec![Evidence::code_committed("landed")]);
+        let inflated = IntentNode::new("agent-b", "Accounts")
+            .with_provides(spec())
+            .with_stability(0.99);
+        graph.publish(&backed).unwrap();
+        graph.publish(&inflated).unwrap();
+
+        // Self-declared confidence doesn't count towards the stored score
+        let stored = graph.query_all(Some(0.5)).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].id, backed.id);
+        let overlapping = graph.find_overlapping(&spec(), "agent-c", 0.5).unwrap();
+        assert_eq!(overlapping.len(), 1);
+        assert_eq!(overlapping[0].id, backed.id);
+
+        // ...nor towards who yields
+        let result = graph.resolve(&inflated, 0.0).unwrap();
+        assert_eq!(result.adjustments.len(), 1);
+        assert_eq!(result.adjustments[0].kind, AdjustmentKind::ConsumeInstead);
+        assert_eq!(result.adjustments[0].source_intent_id, backed.id);
+        assert!(!graph.resolve(&backed, 0.0).unwrap().has_adjustments());
+    }
+
+    #[test]
+    fn test_resolution_outcome() {
+        let graph = make_graph();
+        let user = |agent: &str| {
+            IntentNode::new(agent, "Users").with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )])
+        };
+
+        assert_eq!(
+            graph.resolve(&user("agent-a"), 0.0).unwrap().outcome(),
+            Outcome::Clean
+        );
+
+        graph
+            .publish(&user("agent-a").with_evidence(vec![Evidence::code_committed("landed")]))
+            .unwrap();
+        let adjusted = graph.resolve(&user("agent-b"), 0.0).unwrap();
+        assert!(adjusted.is_clean());
+        assert_eq!(adjusted.outcome(), Outcome::AdjustmentsOnly);
+
+        let conflicted = graph
+            .resolve(
+                &user("agent-c").with_evidence(vec![Evidence::code_committed("landed")]),
+                0.0,
+            )
+            .unwrap();
+        assert_eq!(conflicted.outcome(), Outcome::Conflicted);
+
+        // Conflicts dominate adjustments
+        let mut both = conflicted.clone();
+        both.adjustments.extend(adjusted.adjustments.clone());
+        assert!(both.has_adjustments());
+        assert_eq!(both.outcome(), Outcome::Conflicted);
+        assert_eq!(Outcome::AdjustmentsOnly.as_str(), "adjustments_only");
+    }
+
+    #[test]
+    fn test_resolve_adopt_constraint() {
+        let graph = make_graph();
+
+        // Agent A publishes a constraint about User model
+        let a = IntentNode::new("agent-a", "Auth module")
+            .with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, email: str",
+            )
+            .with_tags(vec!["user", "auth"])])
+            .with_constraints(vec![Constraint::new(
+                "User model",
+                "must have email: str as unique field",
+            )
+            .with_affects(vec!["user", "account"])])
+            .with_evidence(vec![Evidence::code_committed("committed")]);
+        graph.publish(&a).unwrap();
+
+        // Agent B works on something user-related
+        let b =
+            IntentNode::new("agent-b", "Recipe module").with_provides(vec![InterfaceSpec::new(
+                "Recipe",
+                InterfaceKind::Model,
+                "id: UUID, author_id: UUID",
+            )
+            .with_tags(vec!["recipe", "user", "model"])]);
+
+        let result = graph.resolve(&b, 0.0).unwrap();
+        assert!(!result.adopted_constraints.is_empty());
+    }
+
+    #[test]
+    fn test_graph_summary() {
+        let graph = make_graph();
+        graph.publish(&IntentNode::new("agent-a", "Auth")).unwrap();
+        graph
+            .publish(&IntentNode::new("agent-b", "Recipes"))
+            .unwrap();
+        graph.publish(&IntentNode::new("agent-c", "Meals")).unwrap();
+
+        let summary = graph.summary().unwrap();
+        assert_eq!(summary.total_intents, 3);
+        assert_eq!(summary.agent_count, 3);
+    }
+
+    #[test]
+    fn test_summary_agents_sorted_and_distinct() {
+        let graph = make_graph();
+        for agent in ["agent-c", "agent-a", "agent-b", "agent-a", "Agent-Z"] {
+            graph.publish(&IntentNode::new(agent, "Work")).unwrap();
+        }
+
+        let summary = graph.summary().unwrap();
+        let distinct: Vec<String> = graph
+            .conn
+            .prepare("SELECT DISTINCT agent_id FROM intents")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<SqlResult<_>>()
+            .unwrap();
+        let mut expected = distinct.clone();
+        expected.sort();
+        assert_eq!(summary.agents, expected);
+        assert_eq!(
+            summary.agents,
+            vec!["Agent-Z", "agent-a", "agent-b", "agent-c"]
+        );
+        assert_eq!(summary.agent_count, 4);
+    }
+
+    #[test]
+    fn test_summary_aggregates_stored_stability() {
+        let graph = make_graph();
+        assert_eq!(graph.summary().unwrap().average_stability, 0.0);
+        for (agent, stability) in [("agent-a", 0.9), ("agent-b", 0.5), ("agent-c", 0.7)] {
+            graph
+                .publish_with_stability(&IntentNode::new(agent, "Work"), stability)
+                .unwrap();
+        }
+
+        // Stored scores are used as-is, not rescored from (absent) evidence
+        let summary = graph.summary().unwrap();
+        assert_eq!(summary.total_intents, 3);
+        assert!((summary.average_stability - 0.7).abs() < 1e-9);
+        assert_eq!(summary.high_stability_count, 2);
+    }
+
+    #[test]
+    fn test_summary_conflict_count_tracks_conflicts() {
+        let graph = make_graph();
+        assert_eq!(graph.summary().unwrap().conflict_count, Some(0));
+
+        graph
+            .publish(&constrained_intent("agent-a", "ids must be UUID"))
+            .unwrap();
+        graph
+            .publish(&constrained_intent("agent-b", "ids must be UUID"))
+            .unwrap();
+        assert_eq!(graph.summary().unwrap().conflict_count, Some(0));
+
+        graph
+            .publish(&constrained_intent("agent-c", "ids must be integers"))
+            .unwrap();
+        // agent-c clashes with both a and b
+        assert_eq!(graph.summary().unwrap().conflict_count, Some(2));
+
+        // Only the committed intent clears a 0.5 floor, so nothing conflicts there
+        let committed = constrained_intent("agent-a", "ids must be UUID")
+            .with_evidence(vec![Evidence::code_committed("committed")]);
+        graph.publish(&committed).unwrap();
+        assert_eq!(
+            graph.summary_with_conflicts(0.5).unwrap().conflict_count,
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_agent_stability_stats_ranks_by_average() {
+        let graph = make_graph();
+        assert!(graph.agent_stability_stats().unwrap().is_empty());
+
+        let committed = |agent: &str, name: &str| {
+            IntentNode::new(agent, name).with_evidence(vec![
+                Evidence::code_committed("landed"),
+                Evidence::test_pass("passing"),
+            ])
+        };
+        graph
+            .publish(
+                &committed("agent-a", "Auth").with_constraints(
+                    constrained_intent("agent-a", "ids must be UUID").constraints,
+                ),
+            )
+            .unwrap();
+        graph.publish(&committed("agent-a", "Sessions")).unwrap();
+        graph.publish(&committed("agent-b", "Billing")).unwrap();
+        graph
+            .publish(&IntentNode::new("agent-b", "Invoices sketch"))
+            .unwrap();
+        graph
+            .publish(&constrained_intent("agent-c", "ids must be integers"))
+            .unwrap();
+
+        let high = graph.scorer().compute(&committed("x", "x"));
+        let stats = graph.agent_stability_stats().unwrap();
+        let order: Vec<&str> = stats.iter().map(|s| s.agent_id.as_str()).collect();
+        assert_eq!(order, vec!["agent-a", "agent-b", "agent-c"]);
+
+        assert_eq!(stats[0].intent_count, 2);
+        assert!((stats[0].average_stability - high).abs() < 1e-9);
+        assert!((stats[1].average_stability - (high + 0.3) / 2.0).abs() < 1e-9);
+        assert!((stats[1].max_stability - high).abs() < 1e-9);
+        assert!((stats[2].average_stability - 0.3).abs() < 1e-9);
+
+        // agent-c's integer ids clash with agent-a's UUID constraint
+        let conflicts: Vec<Option<usize>> = stats.iter().map(|s| s.conflict_count).collect();
+        assert_eq!(conflicts, vec![Some(1), Some(0), Some(1)]);
+
+        let forced = graph.agent_stability_stats_with_conflicts(0.5).unwrap();
+        let conflicts: Vec<Option<usize>> = forced.iter().map(|s| s.conflict_count).collect();
+        assert_eq!(conflicts, vec![Some(0), Some(0), Some(0)]);
+    }
+
+    #[test]
+    fn test_publish_and_check_reports_duplicate_provision() {
+        let graph = make_graph();
+        let spec = || InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID");
+        let a = IntentNode::new("agent-a", "Users").with_provides(vec![spec()]);
+        let (_, conflicts) = graph.publish_and_check(&a, 0.0).unwrap();
+        assert!(conflicts.is_empty());
+
+        let b = IntentNode::new("agent-b", "Users again").with_provides(vec![spec()]);
+        let (stability, conflicts) = graph.publish_and_check(&b, 0.0).unwrap();
+        assert!((stability - 0.3).abs() < f64::EPSILON);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].their_intent_id, a.id);
+        assert_eq!(graph.count().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_new_with_default_stability() {
+        let graph = make_graph();
+        let baseline = IntentNode::new("agent-a", "Auth");
+        assert!((baseline.stability - DEFAULT_STABILITY).abs() < f64::EPSILON);
+
+        let exploratory = IntentNode::new_with_default_stability("agent-b", "Billing", 0.1);
+        assert!((exploratory.stability - 0.1).abs() < f64::EPSILON);
+        graph.publish(&exploratory).unwrap();
+        let stored = graph.get(&exploratory.id).unwrap().unwrap();
+        assert!((stored.stability - 0.1).abs() < f64::EPSILON);
+
+        // Clamped like with_stability
+        let clamped = IntentNode::new_with_default_stability("agent-c", "Search", 1.5);
+        assert!((clamped.stability - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_adapt_signature_carries_field_diff() {
+        let graph = make_graph();
+        let provider = IntentNode::new("agent-a", "Users")
+            .with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, email: str",
+            )])
+            .with_evidence(vec![
+                Evidence::code_committed("committed"),
+                Evidence::test_pass("passing"),
+            ]);
+        graph.publish(&provider).unwrap();
+
+        let consumer =
+            IntentNode::new("agent-b", "Profiles").with_requires(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: int, name: str",
+            )]);
+        let result = graph.resolve(&consumer, 0.0).unwrap();
+        let adapt = result
+            .adjustments
+            .iter()
+            .find(|a| a.kind == AdjustmentKind::AdaptSignature)
+            .unwrap();
+        let diff = adapt.signature_diff.as_ref().unwrap();
+        assert_eq!(diff.missing, vec![("name".to_string(), "str".to_string())]);
+        assert_eq!(diff.mismatched.len(), 1);
+        assert_eq!(diff.mismatched[0].field, "id");
+        assert_eq!(diff.mismatched[0].expected, "int");
+        assert_eq!(diff.mismatched[0].found, "uuid");
+        assert_eq!(diff.extra, vec![("email".to_string(), "str".to_string())]);
+
+        let adaptation = adapt.adaptation.as_ref().unwrap();
+        assert_eq!(adaptation.interface_name, "User");
+        assert_eq!(adaptation.current_signature, "id: int, name: str");
+        assert_eq!(adaptation.target_signature, provider.provides[0].signature);
+        assert_eq!(adaptation.missing_fields, diff.missing);
+        assert_eq!(adaptation.type_mismatches, diff.mismatched);
+
+        // Other adjustment kinds carry no diff
+        assert!(result
+            .adjustments
+            .iter()
+            .filter(|a| a.kind != AdjustmentKind::AdaptSignature)
+            .all(|a| a.signature_diff.is_none() && a.adaptation.is_none()));
+    }
+
+    #[test]
+    fn test_resolve_suggests_following_a_rename() {
+        let graph = make_graph();
+        let model = |name: &str, signature: &str| {
+            vec![InterfaceSpec::new(name, InterfaceKind::Model, signature)]
+        };
+        let renamed = IntentNode::new("agent-a", "Customers")
+            .with_provides(model("Customer", "id: UUID, email: str"))
+            .with_evidence(vec![Evidence::code_committed("landed")]);
+        graph.publish(&renamed).unwrap();
+        // Less stable than the consumer, so it's no authority on names
+        graph
+            .publish(
+                &IntentNode::new("agent-c", "Clients")
+                    .with_provides(model("Client", "id: UUID, email: str")),
+            )
+            .unwrap();
+
+        let consumer = IntentNode::new("agent-b", "Billing")
+            .with_requires(model("Account", "id: UUID, email: str"));
+        let result = graph.resolve(&consumer, 0.0).unwrap();
+        assert_eq!(result.adjustments.len(), 1);
+        let rename = &result.adjustments[0];
+        assert_eq!(rename.kind, AdjustmentKind::FollowRename);
+        assert_eq!(rename.source_intent_id, renamed.id);
+        assert!(rename.description.contains("'Account'"));
+        assert!(rename.description.contains("'Customer'"));
+
+        let (_, trace) = graph.resolve_traced(&consumer, 0.0).unwrap();
+        assert!(trace
+            .iter()
+            .any(|step| step.check == TraceCheck::Rename && step.theirs == "Customer"));
+
+        // A different signature is a different interface, not a rename
+        let other = IntentNode::new("agent-b", "Billing")
+            .with_requires(model("Account", "id: UUID, balance: float"));
+        assert!(!graph.resolve(&other, 0.0).unwrap().has_adjustments());
+
+        // Once something provides the name, there's nothing to follow
+        graph
+            .publish(
+                &IntentNode::new("agent-d", "Accounts")
+                    .with_provides(model("Account", "id: UUID, email: str")),
+            )
+            .unwrap();
+        assert!(!graph.resolve(&consumer, 0.0).unwrap().has_adjustments());
+    }
+
+    #[test]
+    fn test_resolve_traced_names_the_spec_pair() {
+        let graph = make_graph();
+        let provider = IntentNode::new("agent-a", "Users")
+            .with_provides(vec![
+                InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID, email: str"),
+                InterfaceSpec::new("Invoice", InterfaceKind::Model, "total: float"),
+            ])
+            .with_evidence(vec![Evidence::code_committed("committed")]);
+        graph.publish(&provider).unwrap();
+
+        let consumer =
+            IntentNode::new("agent-b", "Profiles").with_requires(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: int, name: str",
+            )]);
+        let (result, trace) = graph.resolve_traced(&consumer, 0.0).unwrap();
+        assert_eq!(
+            result.adjustments.len(),
+            graph.resolve(&consumer, 0.0).unwrap().adjustments.len()
+        );
+
+        let adapt = trace
+            .iter()
+            .find(|step| step.outcome == "AdaptSignature")
+            .unwrap();
+        assert_eq!(adapt.check, TraceCheck::RequiredProvision);
+        assert_eq!(
+            (adapt.mine.as_str(), adapt.theirs.as_str()),
+            ("User", "User")
+        );
+        assert_eq!(adapt.their_intent_id, provider.id);
+        let line = adapt.to_string();
+        assert!(line.contains("compared my 'User' to their 'User'"));
+        assert!(line.contains("signature compatible=false"));
+        assert!(line.ends_with("→ AdaptSignature"));
+
+        // Pairs that came to nothing are recorded too
+        assert!(trace
+            .iter()
+            .any(|step| step.theirs == "Invoice" && step.outcome == "no action"));
+    }
+
+    #[test]
+    fn test_conflict_reports_structured_winner() {
+        let graph = make_graph();
+        let provider = |agent: &str| {
+            IntentNode::new(agent, "Users").with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )])
+        };
+        let committed = || {
+            vec![
+                Evidence::code_committed("committed"),
+                Evidence::test_pass("passing"),
+            ]
+        };
+        graph.publish(&provider("agent-a")).unwrap();
+
+        // Exact tie: both exploring at the base score
+        let tie = graph.resolve(&provider("agent-b"), 0.0).unwrap();
+        assert_eq!(tie.conflicts.len(), 1);
+        assert_eq!(tie.conflicts[0].winner, Winner::Tie);
+        assert_eq!(
+            tie.conflicts[0].my_stability,
+            tie.conflicts[0].their_stability
+        );
+
+        // More committed provider wins
+        let me = graph
+            .resolve(&provider("agent-b").with_evidence(committed()), 0.0)
+            .unwrap();
+        assert_eq!(me.conflicts[0].winner, Winner::Me);
+        assert!(me.conflicts[0].my_stability > me.conflicts[0].their_stability);
+
+        // Requirers disagreeing with a more committed requirer lose
+        let requirer = |agent: &str, sig: &str| {
+            IntentNode::new(agent, "Orders").with_requires(vec![InterfaceSpec::new(
+                "Order",
+                InterfaceKind::Model,
+                sig,
+            )])
+        };
+        graph
+            .publish(&requirer("agent-c", "total: int").with_evidence(committed()))
+            .unwrap();
+        let them = graph
+            .resolve(&requirer("agent-d", "id: UUID"), 0.0)
+            .unwrap();
+        assert_eq!(them.conflicts.len(), 1);
+        assert_eq!(them.conflicts[0].winner, Winner::Them);
+        assert!(them.conflicts[0].my_stability < them.conflicts[0].their_stability);
+    }
+
+    /// A mixed graph exercising every overlap check in `resolve`, with fixed
+    /// ids and timestamps so resolution output is reproducible.
+    fn resolve_fixture() -> (IntentGraph, Vec<IntentNode>) {
+        use crate::clock::FixedClock;
+
+        let clock = FixedClock::new(
+            DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+        let graph = make_graph();
+        let spec = |name: &str, sig: &str, tags: Vec<&str>| {
+            InterfaceSpec::new(name, InterfaceKind::Model, sig).with_tags(tags)
+        };
+        let node = |id: &str, agent: &str| {
+            clock.advance(chrono::Duration::seconds(1));
+            let mut n = IntentNode::new_with_clock(agent, id, &clock);
+            n.id = id.to_string();
+            n
+        };
+
+        let committed = vec![Evidence::code_committed("c"), Evidence::test_pass("t")];
+        let published = vec![
+            node("users", "agent-a")
+                .with_provides(vec![spec(
+                    "User",
+                    "id: UUID, email: str",
+                    vec!["user", "auth"],
+                )])
+                .with_evidence(committed.clone()),
+            node("profiles", "agent-b")
+                .with_provides(vec![spec("Profile", "id: UUID", vec!["user", "auth"])])
+                .with_requires(vec![spec("UserModel", "id: int", vec![])]),
+            node("orders", "agent-c")
+                .with_provides(vec![spec("Order", "id: UUID", vec!["billing"])])
+                .with_requires(vec![spec("User", "id: UUID", vec![])])
+                .with_evidence(committed.clone()),
+            node("invoices", "agent-d").with_requires(vec![spec("Order", "total: int", vec![])]),
+            node("carts", "agent-e")
+                .with_provides(vec![spec("Cart", "id: UUID", vec!["billing", "checkout"])])
+                .with_requires(vec![spec("UserService", "email: str", vec![])]),
+        ];
+        for intent in &published {
+            graph.publish(intent).unwrap();
+        }
+
+        let probes = vec![
+            node("probe-dup", "agent-x").with_provides(vec![spec("UserModel", "id: UUID", vec![])]),
+            node("probe-req", "agent-y").with_requires(vec![
+                spec("User", "id: int", vec![]),
+                spec("Order", "total: str", vec![]),
+            ]),
+            node("probe-tags", "agent-z").with_provides(vec![spec(
+                "Checkout",
+                "id: UUID",
+                vec!["billing", "checkout", "user", "auth"],
+            )]),
+        ];
+        let mut all = published;
+        all.extend(probes);
+        (graph, all)
+    }
+
+    fn render_resolution(result: &ResolutionResult) -> Vec<String> {
+        let mut lines: Vec<String> = result
+            .adjustments
+            .iter()
+            .map(|a| format!("{:?} {} {}", a.kind, a.source_intent_id, a.description))
+            .collect();
+        lines.extend(result.conflicts.iter().map(|c| {
+            format!(
+                "Conflict {} {} {}",
+                c.their_intent_id, c.description, c.resolution_suggestion
+            )
+        }));
+        lines
+    }
+
+    #[test]
+    fn test_resolve_fixture_output() {
+        let expected = [
+            "== users",
+            "Conflict profiles Both provide 'User' — my stability 0.55 vs their 0.30 Higher stability should provide; other should consume",
+            "== profiles",
+            "ConsumeInstead users Drop 'Profile', consume 'User' from agent agent-a (stability 0.55)",
+            "AdaptSignature users Adapt 'UserModel' signature to match 'User' from agent agent-a — expected 'id: int', they provide 'id: UUID, email: str'",
+            "Conflict orders Both require 'UserModel' with incompatible signatures — I expect 'id: int', agent agent-c expects 'id: UUID' Requirers should align on a shared contract before a provider lands",
+            "Conflict carts Both require 'UserModel' with incompatible signatures — I expect 'id: int', agent agent-e expects 'email: str' Requirers should align on a shared contract before a provider lands",
+            "== orders",
+            "Conflict profiles Both require 'User' with incompatible signatures — I expect 'id: UUID', agent agent-b expects 'id: int' Requirers should align on a shared contract before a provider lands",
+            "Conflict carts Both require 'User' with incompatible signatures — I expect 'id: UUID', agent agent-e expects 'email: str' Requirers should align on a shared contract before a provider lands",
+            "== invoices",
+            "AdaptSignature orders Adapt 'Order' signature to match 'Order' from agent agent-c — expected 'total: int', they provide 'id: UUID'",
+            "== carts",
+            "Conflict profiles Both require 'UserService' with incompatible signatures — I expect 'email: str', agent agent-b expects 'id: int' Requirers should align on a shared contract before a provider lands",
+            "Conflict orders Both require 'UserService' with incompatible signatures — I expect 'email: str', agent agent-c expects 'id: UUID' Requirers should align on a shared contract before a provider lands",
+            "== probe-dup",
+            "ConsumeInstead users Drop 'UserModel', consume 'User' from agent agent-a (stability 0.55)",
+            "== probe-req",
+            "AdaptSignature users Adapt 'User' signature to match 'User' from agent agent-a — expected 'id: int', they provide 'id: UUID, email: str'",
+            "AdaptSignature orders Adapt 'Order' signature to match 'Order' from agent agent-c — expected 'total: str', they provide 'id: UUID'",
+            "Conflict orders Both require 'User' with incompatible signatures — I expect 'id: int', agent agent-c expects 'id: UUID' Requirers should align on a shared contract before a provider lands",
+            "Conflict invoices Both require 'Order' with incompatible signatures — I expect 'total: str', agent agent-d expects 'total: int' Requirers should align on a shared contract before a provider lands",
+            "Conflict carts Both require 'User' with incompatible signatures — I expect 'id: int', agent agent-e expects 'email: str' Requirers should align on a shared contract before a provider lands",
+            "== probe-tags",
+            "ConsumeInstead users Drop 'Checkout', consume 'User' from agent agent-a (stability 0.55)",
+            "Conflict profiles Both provide 'Checkout' — my stability 0.30 vs their 0.30 Higher stability should provide; other should consume",
+            "Conflict carts Both provide 'Checkout' — my stability 0.30 vs their 0.30 Higher stability should provide; other should consume",
+        ];
+        let (graph, intents) = resolve_fixture();
+        let mut actual = Vec::new();
+        for intent in &intents {
+            let result = graph.resolve(intent, 0.0).unwrap();
+            actual.push(format!("== {}", intent.id));
+            actual.extend(render_resolution(&result));
+        }
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_resolve_batch_matches_individual_resolves() {
+        let (graph, mut intents) = resolve_fixture();
+        // A constraint-bearing candidate exercises the constraint step too
+        graph
+            .publish(
+                &IntentNode::new("agent-z", "Auth policy")
+                    .with_constraints(vec![
+                        Constraint::new("User", "must have email").with_affects(vec!["user"])
+                    ])
+                    .with_evidence(vec![Evidence::code_committed("landed")]),
+            )
+            .unwrap();
+        intents.push(
+            IntentNode::new("agent-y", "Unpublished profile").with_provides(vec![
+                InterfaceSpec::new("User", InterfaceKind::Model, "id: int")
+                    .with_tags(vec!["user", "model"]),
+            ]),
+        );
+
+        let as_json = |results: &[ResolutionResult]| serde_json::to_value(results).unwrap();
+        for min_stability in [0.0, 0.5] {
+            let individual: Vec<ResolutionResult> = intents
+                .iter()
+                .map(|i| graph.resolve(i, min_stability).unwrap())
+                .collect();
+            let batched = graph.resolve_batch(&intents, min_stability).unwrap();
+            assert_eq!(as_json(&batched), as_json(&individual));
+        }
+        let batched = graph.resolve_batch(&intents, 0.0).unwrap();
+        assert!(batched.iter().any(|r| !r.adopted_constraints.is_empty()));
+        assert!(graph.resolve_batch(&[], 0.0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_overlapping_explained() {
+        let graph = make_graph();
+
+        let a = IntentNode::new("agent-a", "Auth module").with_provides(vec![
+            InterfaceSpec::new("UserModel", InterfaceKind::Model, "id: UUID")
+                .with_tags(vec!["user", "auth"]),
+            InterfaceSpec::new("Session", InterfaceKind::Model, "token: str")
+                .with_tags(vec!["auth", "login", "token"]),
+        ]);
+        graph.publish(&a).unwrap();
+
+        let my_specs = vec![
+            InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID"),
+            InterfaceSpec::new("LoginToken", InterfaceKind::Model, "token: str")
+                .with_tags(vec!["login", "token"]),
+        ];
+
+        let explained = graph
+            .find_overlapping_explained(&my_specs, "agent-b", 0.0)
+            .unwrap();
+        assert_eq!(explained.len(), 1);
+        let (intent, reasons) = &explained[0];
+        assert_eq!(intent.id, a.id);
+        assert_eq!(
+            reasons,
+            &vec![
+                OverlapReason {
+                    my_spec: "User".to_string(),
+                    their_spec: "UserModel".to_string(),
+                    kind: crate::models::OverlapKind::Name,
+                    strength: 1.0,
+                },
+                OverlapReason {
+                    my_spec: "LoginToken".to_string(),
+                    their_spec: "Session".to_string(),
+                    kind: crate::models::OverlapKind::Tags,
+                    strength: 2.0 / 3.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_self_overlap() {
+        let graph = make_graph();
+
+        let a = IntentNode::new("agent-a", "Auth module").with_provides(vec![InterfaceSpec::new(
+            "User",
+            InterfaceKind::Model,
+            "id: UUID",
+        )
+        .with_tags(vec!["user", "model"])]);
+        graph.publish(&a).unwrap();
+
+        // Querying overlap for agent-a's own specs should exclude itself
+        let overlapping = graph.find_overlapping(&a.provides, "agent-a", 0.0).unwrap();
+        assert!(overlapping.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_required_required_conflict() {
+        let graph = make_graph();
+
+        let a =
+            IntentNode::new("agent-a", "Recipe module").with_requires(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, email: str",
+            )]);
+        graph.publish(&a).unwrap();
+
+        let b =
+            IntentNode::new("agent-b", "Meal planning").with_requires(vec![InterfaceSpec::new(
+                "UserModel",
+                InterfaceKind::Model,
+                "id: int, name: str",
+            )]);
+
+        let result = graph.resolve(&b, 0.0).unwrap();
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].their_intent_id, a.id);
+        assert!(result.conflicts[0].description.contains("Both require"));
+    }
+
+    #[test]
+    fn test_resolve_required_required_compatible() {
+        let graph = make_graph();
+
+        let a =
+            IntentNode::new("agent-a", "Recipe module").with_requires(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+        graph.publish(&a).unwrap();
+
+        // A superset expectation is satisfiable by the same provider
+        let b =
+            IntentNode::new("agent-b", "Meal planning").with_requires(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, email: str",
+            )]);
+
+        let result = graph.resolve(&b, 0.0).unwrap();
+        assert!(result.is_clean());
+    }
+
+    #[test]
+    fn test_source_reports_how_graph_was_opened() {
+        let graph = make_graph();
+        assert_eq!(graph.source(), &GraphSource::InMemory);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.db");
+        let path = path.to_str().unwrap();
+        let persistent = IntentGraph::persistent(path).unwrap();
+        assert_eq!(
+            persistent.source(),
+            &GraphSource::File {
+                path: path.to_string(),
+                read_only: false,
+            }
+        );
+        assert_eq!(persistent.fork().unwrap().source(), &GraphSource::InMemory);
+    }
+
+    #[test]
+    fn test_validate_intent_clean() {
+        let graph = make_graph();
+        let parent = IntentNode::new("agent-a", "Auth draft");
+        graph.publish(&parent).unwrap();
+        let intent = IntentNode::new("agent-a", "Auth module")
+            .with_parent(&parent.id)
+            .with_provides(vec![
+                InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID, email: str"),
+                InterfaceSpec::new("Login", InterfaceKind::Endpoint, "POST /login -> 200 Token"),
+            ])
+            .with_constraints(vec![Constraint::new("User", "must have email")]);
+        assert_eq!(graph.validate_intent(&intent).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_validate_intent_reports_each_issue() {
+        let graph = make_graph();
+        let kinds = |intent: &IntentNode| -> Vec<(IssueSeverity, IssueKind)> {
+            graph
+                .validate_intent(intent)
+                .unwrap()
+                .into_iter()
+                .map(|i| (i.severity, i.kind))
+                .collect()
+        };
+
+        let blank = IntentNode::new(" ", "").with_requires(vec![InterfaceSpec::new(
+            "",
+            InterfaceKind::Model,
+            "id: UUID",
+        )]);
+        let issues = graph.validate_intent(&blank).unwrap();
+        assert_eq!(issues.len(), 3);
+        assert!(issues
+            .iter()
+            .all(|i| i.severity == IssueSeverity::Error && i.kind == IssueKind::EmptyField));
+        assert_eq!(issues[2].message, "requires[0].name is empty");
+
+        let orphan = IntentNode::new("agent-a", "Auth").with_parent("missing");
+        assert_eq!(
+            kinds(&orphan),
+            vec![(IssueSeverity::Error, IssueKind::DanglingParent)]
+        );
+        assert!(graph.publish(&orphan).is_err());
+
+        let garbled = IntentNode::new("agent-a", "Auth").with_provides(vec![
+            InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id UUID, scores: Dict[str, int]",
+            ),
+            InterfaceSpec::new("Login", InterfaceKind::Endpoint, "login please"),
+        ]);
+        let issues = graph.validate_intent(&garbled).unwrap();
+        assert_eq!(
+            issues
+                .iter()
+                .map(|i| (i.severity, i.kind))
+                .collect::<Vec<_>>(),
+            vec![(IssueSeverity::Warning, IssueKind::UnparseableSignature); 2]
+        );
+        assert!(issues[0].message.contains("'id UUID', 'int]'"));
+
+        let duplicated = IntentNode::new("agent-a", "Auth").with_provides(vec![
+            InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID"),
+            InterfaceSpec::new("UserModel", InterfaceKind::Model, "id: UUID"),
+        ]);
+        let issues = graph.validate_intent(&duplicated).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, IssueKind::DuplicateName);
+        assert_eq!(
+            issues[0].message,
+            "provides User, UserModel all normalize to 'user'"
+        );
+    }
+
+    #[test]
+    fn test_reopen_rebuilds_index_from_older_normalization() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.db");
+        let path = path.to_str().unwrap();
+        let categories =
+            IntentNode::new("agent-a", "Catalog").with_provides(vec![InterfaceSpec::new(
+                "Categories",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+        {
+            let graph = IntentGraph::persistent(path).unwrap();
+            graph.publish(&categories).unwrap();
+            // Simulate an index written before names were singularized
+            graph
+                .conn
+                .execute_batch(
+                    "UPDATE intent_interfaces SET normalized_name = 'categories';
+                     PRAGMA user_version = 0;",
+                )
+                .unwrap();
+        }
+
+        let graph = IntentGraph::persistent(path).unwrap();
+        let specs = [InterfaceSpec::new(
+            "Category",
+            InterfaceKind::Model,
+            "id: UUID",
+        )];
+        let found = graph.find_overlapping(&specs, "agent-b", 0.0).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, categories.id);
+        let version: i64 = graph
+            .conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, INDEX_VERSION);
+    }
+
+    #[test]
+    fn test_read_only_open_of_older_schema_asks_for_migration() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.db");
+        let path = path.to_str().unwrap();
+        let read_only = format!("file:{}?mode=ro", path);
+        {
+            let graph = IntentGraph::persistent(path).unwrap();
+            graph
+                .publish(&IntentNode::new("agent-a", "Auth module"))
+                .unwrap();
+        }
+
+        // A current file opens read-only and serves reads
+        let graph = IntentGraph::persistent(&read_only).unwrap();
+        assert_eq!(graph.count().unwrap(), 1);
+        drop(graph);
+
+        // Simulate a file written before intents carried labels
+        Connection::open(path)
+            .unwrap()
+            .execute_batch("ALTER TABLE intents DROP COLUMN labels;")
+            .unwrap();
+
+        let err = IntentGraph::persistent(&read_only).err().unwrap();
+        assert_eq!(err.sqlite_error_code(), Some(rusqlite::ErrorCode::ReadOnly));
+        assert!(err.to_string().contains("needs migration"));
+
+        let graph = IntentGraph::persistent(path).unwrap();
+        assert_eq!(
+            graph.query_all(None).unwrap()[0].labels,
+            Vec::<String>::new()
+        );
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_bincode_round_trip_matches_json_round_trip() {
+        let (graph, _) = resolve_fixture();
+        let mut original = graph.query_all(None).unwrap();
+        original[1] = original[1]
+            .clone()
+            .with_evidence(vec![Evidence::test_pass("ok")]);
+        original[2] = original[2].clone().with_constraints(vec![Constraint::new(
+            "User model",
+            "must have id: UUID",
+        )
+        .with_severity(ConstraintSeverity::Critical)]);
+        let source = make_graph();
+        for intent in &original {
+            source.publish(intent).unwrap();
+        }
+
+        let via_json = make_graph();
+        let json = serde_json::to_string(&source.query_all(None).unwrap()).unwrap();
+        for intent in crate::api::intents_from_json(&json).unwrap() {
+            via_json.publish(&intent).unwrap();
+        }
+
+        let via_bincode = make_graph();
+        let bytes = source.export_bincode().unwrap();
+        assert_eq!(
+            via_bincode.import_bincode(&bytes, false).unwrap(),
+            original.len()
+        );
+
+        let dump = |g: &IntentGraph| serde_json::to_value(g.query_all(None).unwrap()).unwrap();
+        assert_eq!(dump(&via_bincode), dump(&via_json));
+        assert_eq!(dump(&via_bincode), dump(&source));
+        assert!(bytes.len() < json.len());
+
+        // Garbage and trailing bytes are rejected without importing anything
+        assert!(via_bincode.import_bincode(b"not bincode", false).is_err());
+        let mut padded = bytes.clone();
+        padded.push(0);
+        assert!(via_bincode.import_bincode(&padded, false).is_err());
+        assert_eq!(via_bincode.count().unwrap(), original.len());
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_import_bincode_trusts_exported_scores_on_request() {
+        let source = make_graph();
+        let intent = IntentNode::new("agent-a", "Auth module")
+            .with_evidence(vec![Evidence::code_committed("landed")]);
+        source.publish_with_stability(&intent, 0.95).unwrap();
+        let bytes = source.export_bincode().unwrap();
+        let score = |g: &IntentGraph| g.get(&intent.id).unwrap().unwrap().computed_stability;
+
+        let trusted = make_graph();
+        trusted.import_bincode(&bytes, true).unwrap();
+        assert_eq!(score(&trusted), Some(0.95));
+
+        let rescored = make_graph();
+        rescored.import_bincode(&bytes, false).unwrap();
+        assert_eq!(score(&rescored), Some(0.5));
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_import_bincode_rolls_back_lineage_cycles() {
+        // Foreign keys rule out longer cycles; a self-parent slips past them
+        let root = IntentNode::new("agent-a", "Root");
+        let mut looped = IntentNode::new("agent-b", "Looped");
+        looped.parent_id = Some(looped.id.clone());
+        let payload: Vec<(&IntentNode, Option<f64>)> = vec![(&root, None), (&looped, None)];
+        let bytes = bincode::serde::encode_to_vec(&payload, bincode::config::standard()).unwrap();
+
+        let graph = make_graph();
+        let err = graph.import_bincode(&bytes, false).unwrap_err();
+        assert!(err.to_string().contains("lineage cycles"));
+        assert_eq!(graph.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fork_is_independent() {
+        let graph = make_graph();
+        let a = IntentNode::new("agent-a", "Auth").with_provides(vec![InterfaceSpec::new(
+            "User",
+            InterfaceKind::Model,
+            "id: UUID",
+        )]);
+        graph.publish(&a).unwrap();
+
+        let fork = graph.fork().unwrap();
+        assert_eq!(fork.count().unwrap(), 1);
+
+        // Interface index rows are copied too, so overlap queries work on the fork
+        let overlapping = fork.find_overlapping(&a.provides, "agent-b", 0.0).unwrap();
+        assert_eq!(overlapping.len(), 1);
+        assert_eq!(overlapping[0].id, a.id);
+
+        fork.publish(&IntentNode::new("agent-b", "Speculative"))
+            .unwrap();
+        assert_eq!(fork.count().unwrap(), 2);
+        assert_eq!(graph.count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_query_by_kind() {
+        let graph = make_graph();
+
+        let migration = IntentNode::new("agent-a", "Add recipes table").with_provides(vec![
+            InterfaceSpec::new("CreateRecipes", InterfaceKind::Migration, ""),
+        ]);
+        graph.publish(&migration).unwrap();
+
+        let needs_migration =
+            IntentNode::new("agent-b", "Recipe model").with_requires(vec![InterfaceSpec::new(
+                "CreateRecipes",
+                InterfaceKind::Migration,
+                "",
+            )]);
+        graph.publish(&needs_migration).unwrap();
+
+        let model = IntentNode::new("agent-c", "User").with_provides(vec![InterfaceSpec::new(
+            "User",
+            InterfaceKind::Model,
+            "id: UUID",
+        )]);
+        graph.publish(&model).unwrap();
+
+        let any_role = graph
+            .query_by_kind(InterfaceKind::Migration, None, 0.0)
+            .unwrap();
+        assert_eq!(any_role.len(), 2);
+
+        let providers = graph
+            .query_by_kind(InterfaceKind::Migration, Some("provides"), 0.0)
+            .unwrap();
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].id, migration.id);
+
+        assert!(graph
+            .query_by_kind(InterfaceKind::Migration, None, 0.5)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_migrations_since() {
+        let graph = make_graph();
+        let t0 = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let at = |mut intent: IntentNode, mins: i64| {
+            intent.timestamp = t0 + chrono::Duration::minutes(mins);
+            intent
+        };
+        let migration = |name: &str| vec![InterfaceSpec::new(name, InterfaceKind::Migration, "")];
+
+        let old = at(
+            IntentNode::new("agent-a", "Add users table").with_provides(migration("CreateUsers")),
+            0,
+        );
+        let later = at(
+            IntentNode::new("agent-b", "Add orders table").with_provides(migration("CreateOrders")),
+            30,
+        );
+        let first = at(
+            IntentNode::new("agent-a", "Add recipes table")
+                .with_provides(migration("CreateRecipes")),
+            20,
+        );
+        let consumer = at(
+            IntentNode::new("agent-c", "Recipe model").with_requires(migration("CreateRecipes")),
+            25,
+        );
+        let model = at(
+            IntentNode::new("agent-c", "User").with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]),
+            40,
+        );
+        for intent in [&old, &later, &first, &consumer, &model] {
+            graph.publish(intent).unwrap();
+        }
+
+        let ids: Vec<String> = graph
+            .migrations_since(t0 + chrono::Duration::minutes(10))
+            .unwrap()
+            .into_iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(ids, vec![first.id.clone(), later.id.clone()]);
+        assert!(graph
+            .migrations_since(t0 + chrono::Duration::minutes(30))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_kind_column_backfilled_for_old_databases() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("old.db");
+        let path = path.to_str().unwrap();
+
+        // Simulate a database created before intent_interfaces stored `kind`
+        {
+            let conn = Connection::open(path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE intents (
+                    id TEXT PRIMARY KEY, agent_id TEXT NOT NULL, timestamp TEXT NOT NULL,
+                    intent TEXT NOT NULL, provides TEXT NOT NULL, requires TEXT NOT NULL,
+                    constraints TEXT NOT NULL, stability REAL NOT NULL, evidence TEXT NOT NULL,
+                    parent_id TEXT, computed_stability REAL
+                 );
+                 CREATE TABLE intent_interfaces (
+                    intent_id TEXT NOT NULL, agent_id TEXT NOT NULL,
+                    normalized_name TEXT NOT NULL, role TEXT NOT NULL, tags TEXT NOT NULL
+                 );",
+            )
+            .unwrap();
+            let provides = vec![InterfaceSpec::new(
+                "CreateUsers",
+                InterfaceKind::Migration,
+                "",
+            )];
+            conn.execute(
+                "INSERT INTO intents VALUES ('m1', 'agent-a', ?1, 'users table', ?2,
+                 '[]', '[]', 0.3, '[]', NULL, 0.3)",
+                params![
+                    Utc::now().to_rfc3339(),
+                    serde_json::to_string(&provides).unwrap()
+                ],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO intent_interfaces VALUES ('m1', 'agent-a', 'create users',
+                 'provides', '')",
+                [],
+            )
+            .unwrap();
+        }
+
+        let graph = IntentGraph::persistent(path).unwrap();
+        let found = graph
+            .query_by_kind(InterfaceKind::Migration, Some("provides"), 0.0)
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "m1");
+
+        // The rebuild must not duplicate index rows
+        let rows: i64 = graph
+            .conn
+            .query_row("SELECT COUNT(*) FROM intent_interfaces", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(rows, 1);
+    }
+
+    #[test]
+    fn test_query_by_label() {
+        let graph = make_graph();
+        let review = IntentNode::new("agent-a", "Auth module")
+            .with_labels(vec!["sprint-12", "needs-review"]);
+        let sprint = IntentNode::new("agent-b", "Billing").with_labels(vec!["sprint-12"]);
+        let plain = IntentNode::new("agent-c", "Search");
+        for intent in [&review, &sprint, &plain] {
+            graph.publish(intent).unwrap();
+        }
+
+        let ids = |label: &str| -> Vec<String> {
+            graph
+                .query_by_label(label, 0.0)
+                .unwrap()
+                .into_iter()
+                .map(|i| i.id)
+                .collect()
+        };
+        assert_eq!(ids("sprint-12"), vec![review.id.clone(), sprint.id.clone()]);
+        assert_eq!(ids("needs-review"), vec![review.id.clone()]);
+        assert!(ids("Sprint-12").is_empty());
+        assert!(graph.query_by_label("sprint-12", 0.5).unwrap().is_empty());
+
+        assert_eq!(
+            graph.get(&review.id).unwrap().unwrap().labels,
+            vec!["sprint-12", "needs-review"]
+        );
+        assert!(graph.get(&plain.id).unwrap().unwrap().labels.is_empty());
+    }
+
+    #[test]
+    fn test_labels_column_added_to_old_databases() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("old.db");
+        let path = path.to_str().unwrap();
+        {
+            let graph = IntentGraph::persistent(path).unwrap();
+            graph
+                .publish(&IntentNode::new("agent-a", "Auth module"))
+                .unwrap();
+            graph
+                .conn
+                .execute_batch("ALTER TABLE intents DROP COLUMN labels;")
+                .unwrap();
+        }
+
+        let graph = IntentGraph::persistent(path).unwrap();
+        let all = graph.query_all(None).unwrap();
+        assert_eq!(all.len(), 1);
+        assert!(all[0].labels.is_empty());
+        let labelled = IntentNode::new("agent-b", "Billing").with_labels(vec!["sprint-12"]);
+        graph.publish(&labelled).unwrap();
+        assert_eq!(graph.query_by_label("sprint-12", 0.0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_seq_cursor_reads_same_timestamp_intents_once() {
+        let graph = make_graph();
+        let at = Utc::now();
+        let publish = |n: usize| -> Vec<String> {
+            (0..n)
+                .map(|i| {
+                    let intent = IntentNode::new(&format!("agent-{i}"), "Burst").with_timestamp(at);
+                    graph.publish(&intent).unwrap();
+                    intent.id
+                })
+                .collect()
+        };
+
+        let first = publish(3);
+        let (read, cursor) = graph.query_after_seq(0, None).unwrap();
+        assert_eq!(read.into_iter().map(|i| i.id).collect::<Vec<_>>(), first);
+        assert_eq!(cursor, 3);
+
+        let second = publish(4);
+        let (read, cursor) = graph.query_after_seq(cursor, None).unwrap();
+        assert_eq!(read.into_iter().map(|i| i.id).collect::<Vec<_>>(), second);
+        assert_eq!(cursor, 7);
+        let (read, cursor) = graph.query_after_seq(cursor, None).unwrap();
+        assert!(read.is_empty());
+        assert_eq!(cursor, 7);
+        // A timestamp cursor would miss every intent after the first read
+        assert!(graph.query_since(at, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_seq_cursor_survives_clear_and_fork() {
+        let graph = make_graph();
+        for agent in ["agent-a", "agent-b", "agent-c"] {
+            graph.publish(&IntentNode::new(agent, "Before")).unwrap();
+        }
+        let (_, cursor) = graph.query_after_seq(0, None).unwrap();
+        assert_eq!(cursor, 3);
+
+        graph.clear().unwrap();
+        let after = IntentNode::new("agent-d", "After");
+        graph.publish(&after).unwrap();
+        let (read, cursor) = graph.query_after_seq(cursor, None).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].id, after.id);
+        assert_eq!(cursor, 4);
+
+        let fork = graph.fork().unwrap();
+        graph.clear().unwrap();
+        fork.clear().unwrap();
+        fork.publish(&IntentNode::new("agent-e", "Forked")).unwrap();
+        assert_eq!(fork.query_after_seq(cursor, None).unwrap().1, 5);
+    }
+
+    #[test]
+    fn test_seq_column_added_to_old_databases() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("old.db");
+        let path = path.to_str().unwrap();
+        {
+            let graph = IntentGraph::persistent(path).unwrap();
+            for agent in ["agent-a", "agent-b"] {
+                graph.publish(&IntentNode::new(agent, "Work")).unwrap();
+            }
+            graph
+                .conn
+                .execute_batch(
+                    "DROP INDEX idx_intents_seq;
+                     ALTER TABLE intents DROP COLUMN seq;",
+                )
+                .unwrap();
+        }
+
+        let graph = IntentGraph::persistent(path).unwrap();
+        graph.publish(&IntentNode::new("agent-c", "Work")).unwrap();
+        let (read, cursor) = graph.query_after_seq(0, None).unwrap();
+        let agents: Vec<&str> = read.iter().map(|i| i.agent_id.as_str()).collect();
+        assert_eq!(agents, vec!["agent-a", "agent-b", "agent-c"]);
+        assert_eq!(cursor, 3);
+    }
+
+    fn committed_user_provider() -> IntentNode {
+        IntentNode::new("agent-a", "Auth module")
+            .with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, email: str",
+            )])
+            .with_evidence(vec![
+                Evidence::code_committed("committed"),
+                Evidence::test_pass("passing"),
+            ])
+    }
+
+    #[test]
+    fn test_resolve_both_above_strong_vs_strong_conflicts() {
+        let graph = make_graph();
+        graph.publish(&committed_user_provider()).unwrap();
+
+        // Equally committed duplicate provider: a genuine two-sided conflict
+        let b = IntentNode::new("agent-b", "Accounts")
+            .with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, name: str",
+            )])
+            .with_evidence(vec![
+                Evidence::code_committed("committed"),
+                Evidence::test_pass("passing"),
+            ]);
+
+        let options = ResolveOptions::new(0.5).with_both_above(true);
+        let result = graph.resolve_with(&b, &options).unwrap();
+        assert_eq!(result.conflicts.len(), 1);
+        assert!(result
+            .adjustments
+            .iter()
+            .all(|a| a.kind != AdjustmentKind::YieldTo));
+    }
+
+    #[test]
+    fn test_resolve_both_above_weak_side_yields() {
+        let graph = make_graph();
+        let a = committed_user_provider();
+        graph.publish(&a).unwrap();
+
+        // Exploring requirer with an incompatible expectation of a committed requirer
+        let a_req = IntentNode::new("agent-a", "Profile page")
+            .with_requires(vec![InterfaceSpec::new(
+                "Account",
+                InterfaceKind::Model,
+                "id: UUID",
+            )])
+            .with_evidence(vec![
+                Evidence::code_committed("committed"),
+                Evidence::test_pass("passing"),
+            ]);
+        graph.publish(&a_req).unwrap();
+
+        let weak = IntentNode::new("agent-b", "Exploring").with_requires(vec![InterfaceSpec::new(
+            "Account",
+            InterfaceKind::Model,
+            "id: int",
+        )]);
+
+        let default = graph.resolve(&weak, 0.5).unwrap();
+        assert_eq!(default.conflicts.len(), 1);
+
+        let options = ResolveOptions::new(0.5).with_both_above(true);
+        let result = graph.resolve_with(&weak, &options).unwrap();
+        assert!(result.conflicts.is_empty());
+        let yields: Vec<_> = result
+            .adjustments
+            .iter()
+            .filter(|a| a.kind == AdjustmentKind::YieldTo)
+            .collect();
+        assert_eq!(yields.len(), 1);
+        assert_eq!(yields[0].source_intent_id, a_req.id);
+    }
+
+    #[test]
+    fn test_recompute_all_stability() {
+        use crate::stability::StabilityWeights;
+
+        let graph = make_graph();
+        graph.publish(&IntentNode::new("agent-a", "Auth")).unwrap();
+        graph
+            .publish(
+                &IntentNode::new("agent-b", "Recipes")
+                    .with_evidence(vec![Evidence::code_committed("committed")]),
+            )
+            .unwrap();
+        assert_eq!(graph.query_all(Some(0.5)).unwrap().len(), 1);
+
+        let graph = graph.with_scorer(StabilityScorer::with_weights(StabilityWeights {
+            base: 0.6,
+            ..StabilityWeights::default()
+        }));
+        // Stored values are stale until recomputed
+        assert_eq!(graph.query_all(Some(0.5)).unwrap().len(), 1);
+
+        assert_eq!(graph.recompute_all_stability().unwrap(), 2);
+        assert_eq!(graph.query_all(Some(0.5)).unwrap().len(), 2);
+
+        let stored: f64 = graph
+            .conn
+            .query_row(
+                "SELECT computed_stability FROM intents WHERE agent_id = 'agent-b'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert!((stored - 0.8).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_graphs_share_one_scorer() {
+        use crate::stability::StabilityWeights;
+
+        let policy = StabilityScorer::with_weights(StabilityWeights {
+            code_committed: 0.4,
+            ..StabilityWeights::default()
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| dir.path().join(name).to_str().unwrap().to_string();
+        let billing =
+            IntentGraph::persistent_with_scorer(&path("billing.db"), policy.clone()).unwrap();
+        let search = IntentGraph::persistent_with_scorer(&path("search.db"), policy).unwrap();
+
+        let intent = IntentNode::new("agent-a", "Auth")
+            .with_evidence(vec![Evidence::code_committed("landed")]);
+        let a = billing.publish(&intent).unwrap();
+        let b = search.publish(&intent).unwrap();
+        assert!((a - 0.7).abs() < f64::EPSILON);
+        assert!((a - b).abs() < f64::EPSILON);
+        assert_eq!(search.scorer().weights(), billing.scorer().weights());
+
+        // Forks keep the policy too
+        let forked = billing.fork().unwrap();
+        let mut refined = intent.clone();
+        refined.id = "refined".to_string();
+        assert!((forked.publish(&refined).unwrap() - 0.7).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_recompute_stability_changed_reports_only_changes() {
+        use crate::stability::StabilityWeights;
+
+        let graph = make_graph();
+        let plain = IntentNode::new("agent-a", "Auth");
+        let committed = IntentNode::new("agent-b", "Recipes")
+            .with_evidence(vec![Evidence::code_committed("committed")]);
+        graph.publish(&plain).unwrap();
+        graph.publish(&committed).unwrap();
+
+        // Nothing changes under the same weights
+        assert!(graph.recompute_stability_changed().unwrap().is_empty());
+
+        // Only the committed intent is affected by the code_committed weight
+        let graph = graph.with_scorer(StabilityScorer::with_weights(StabilityWeights {
+            code_committed: 0.4,
+            ..StabilityWeights::default()
+        }));
+        assert_eq!(
+            graph.recompute_stability_changed().unwrap(),
+            vec![committed.id.clone()]
+        );
+        assert!(graph.recompute_stability_changed().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_evidence_raises_stability() {
+        let graph = make_graph();
+        let intent = IntentNode::new("agent-a", "Auth");
+        let initial = graph.publish(&intent).unwrap();
+        assert!((initial - 0.3).abs() < f64::EPSILON);
+
+        let updated = graph
+            .add_evidence(&intent.id, &[Evidence::code_committed("landed")])
+            .unwrap();
+        assert!((updated - 0.5).abs() < f64::EPSILON);
+
+        let stored = graph.get(&intent.id).unwrap().unwrap();
+        assert_eq!(stored.evidence.len(), 1);
+        assert_eq!(graph.query_all(Some(0.5)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_evidence_source_agent_round_trips() {
+        let graph = make_graph();
+        let intent = IntentNode::new("agent-a", "Auth");
+        graph.publish(&intent).unwrap();
+        graph
+            .add_evidence(
+                &intent.id,
+                &[Evidence::consumed_by("agent-b"), Evidence::test_pass("ok")],
+            )
+            .unwrap();
+
+        let stored = graph.get(&intent.id).unwrap().unwrap();
+        assert_eq!(stored.evidence[0].source_agent.as_deref(), Some("agent-b"));
+        assert_eq!(stored.evidence[0].description, "Consumed by agent agent-b");
+        assert_eq!(stored.evidence[1].source_agent, None);
+
+        // Evidence serialized before the field existed still loads
+        let legacy: Evidence = serde_json::from_str(
+            r#"{"kind":"ConsumedByOther","description":"Consumed by agent agent-c"}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.source_agent, None);
+        assert_eq!(legacy.consumer(), Some("agent-c"));
+    }
+
+    #[test]
+    fn test_add_evidence_unknown_id() {
+        let graph = make_graph();
+        let err = graph
+            .add_evidence("missing", &[Evidence::test_pass("t")])
+            .unwrap_err();
+        assert!(matches!(err, rusqlite::Error::QueryReturnedNoRows));
+    }
+
+    fn constrained_intent(agent_id: &str, requirement: &str) -> IntentNode {
+        IntentNode::new(agent_id, "User work")
+            .with_provides(vec![InterfaceSpec::new(
+                &format!("{}Thing", agent_id.replace('-', "")),
+                InterfaceKind::Model,
+                "",
+            )
+            .with_tags(vec!["user"])])
+            .with_constraints(vec![
+                Constraint::new("User model", requirement).with_affects(vec!["user"])
+            ])
+    }
+
+    #[test]
+    fn test_resolve_equivalent_constraints_do_not_conflict() {
+        let graph = make_graph();
+        graph
+            .publish(&constrained_intent("agent-a", "must have email"))
+            .unwrap();
+
+        let b = constrained_intent("agent-b", "Must have email: str.");
+        let result = graph.resolve(&b, 0.0).unwrap();
+        assert!(result.is_clean());
+        assert_eq!(result.adopted_constraints.len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_opposed_constraints_conflict() {
+        let graph = make_graph();
+        graph
+            .publish(&constrained_intent("agent-a", "ids must be UUID"))
+            .unwrap();
+
+        let b = constrained_intent("agent-b", "ids must be integers");
+        let result = graph.resolve(&b, 0.0).unwrap();
+        assert_eq!(result.conflicts.len(), 1);
+        assert!(result.adopted_constraints.is_empty());
+    }
+
+    #[test]
+    fn test_applicable_constraints_use_stored_stability() {
+        let graph = make_graph();
+        let source = constrained_intent("agent-a", "ids must be UUID")
+            .with_evidence(vec![Evidence::code_committed("landed")]);
+        let published = graph.publish(&source).unwrap();
+        let stored = || -> f64 {
+            graph
+                .conn
+                .query_row(
+                    "SELECT computed_stability FROM intents WHERE id = ?1",
+                    params![source.id],
+                    |row| row.get(0),
+                )
+                .unwrap()
+        };
+
+        let consumer = constrained_intent("agent-b", "ids must be UUID");
+        let found = graph.find_applicable_constraints(&consumer, 0.0).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1, source.id);
+        assert_eq!(found[0].2, stored());
+        assert_eq!(found[0].2, published);
+
+        // The stored score is read back, not recomputed from evidence
+        graph
+            .conn
+            .execute(
+                "UPDATE intents SET computed_stability = 0.9 WHERE id = ?1",
+                params![source.id],
+            )
+            .unwrap();
+        let found = graph.find_applicable_constraints(&consumer, 0.0).unwrap();
+        assert_eq!(found[0].2, 0.9);
+        assert_eq!(found[0].2, stored());
+    }
+
+    #[test]
+    fn test_applicable_constraints_wait_for_recompute_under_half_life() {
+        use crate::clock::FixedClock;
+
+        let t0 = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let clock = Arc::new(FixedClock::new(t0));
+        let graph = make_graph()
+            .with_clock(clock.clone())
+            .with_stability_cache(16)
+            .with_scorer(StabilityScorer::new().with_conflict_half_life(chrono::Duration::days(1)));
+        let mut conflict = Evidence::conflict("clashed");
+        conflict.timestamp = t0;
+        let source =
+            constrained_intent("agent-a", "ids must be UUID").with_evidence(vec![conflict]);
+        assert!((graph.publish(&source).unwrap() - 0.15).abs() < 1e-9);
+
+        clock.advance(chrono::Duration::days(30));
+        let consumer = constrained_intent("agent-b", "ids must be UUID");
+        let found = graph.find_applicable_constraints(&consumer, 0.0).unwrap();
+        // The reported score and the filter both read the stored 0.15
+        assert!((found[0].2 - 0.15).abs() < 1e-9);
+        assert!(graph
+            .find_applicable_constraints(&consumer, 0.2)
+            .unwrap()
+            .is_empty());
+        graph.recompute_all_stability().unwrap();
+        let found = graph.find_applicable_constraints(&consumer, 0.2).unwrap();
+        assert!((found[0].2 - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_resolve_lists_rejected_constraints() {
+        let graph = make_graph();
+        let uuid_ids = constrained_intent("agent-a", "ids must be UUID");
+        let mut email = constrained_intent("agent-c", "must have email");
+        email.constraints[0].target = "User table".to_string();
+        graph.publish(&uuid_ids).unwrap();
+        graph.publish(&email).unwrap();
+
+        let b = constrained_intent("agent-b", "ids must be integers");
+        let result = graph.resolve(&b, 0.0).unwrap();
+        assert_eq!(result.rejected_constraints.len(), 1);
+        let (rejected, source) = &result.rejected_constraints[0];
+        assert_eq!(rejected.requirement, "ids must be UUID");
+        assert_eq!(source, &uuid_ids.id);
+
+        let adopted: Vec<&str> = result
+            .adopted_constraints
+            .iter()
+            .map(|c| c.requirement.as_str())
+            .collect();
+        assert_eq!(adopted, vec!["must have email"]);
+    }
+
+    #[test]
+    fn test_write_conflicts_ndjson_round_trip() {
+        let graph = make_graph();
+        graph
+            .publish(&constrained_intent("agent-a", "ids must be UUID"))
+            .unwrap();
+        graph
+            .publish(&constrained_intent("agent-b", "ids must be integers"))
+            .unwrap();
+        graph
+            .publish(&IntentNode::new("agent-c", "Unrelated"))
+            .unwrap();
+
+        let mut buf = Vec::new();
+        let written = graph.write_conflicts_ndjson(0.0, &mut buf).unwrap();
+        assert_eq!(written, 2);
+
+        let text = String::from_utf8(buf).unwrap();
+        let reports: Vec<ConflictReport> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(reports.len(), written);
+        assert_ne!(reports[0].my_intent_id, reports[1].my_intent_id);
+        assert_eq!(reports[0].my_intent_id, reports[1].their_intent_id);
+    }
+
+    #[test]
+    fn test_stability_history_over_supersession_chain() {
+        let t0 = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let at = |mins: i64| t0 + chrono::Duration::minutes(mins);
+        let evidence = |kind: EvidenceKind, mins: i64| Evidence {
+            kind,
+            description: "evidence".to_string(),
+            timestamp: at(mins),
+            source_agent: None,
+        };
+        let node = |id: &str, mins: i64| {
+            let mut intent = IntentNode::new("agent-a", "Auth module");
+            intent.id = id.to_string();
+            intent.timestamp = at(mins);
+            intent
+        };
+
+        let graph = make_graph();
+        // Exploring draft; a test passes later, then it is refined
+        let draft = node("draft", 0).with_evidence(vec![evidence(EvidenceKind::TestPass, 5)]);
+        // Refinement carries the committed code from the start, gains approval
+        let refined = node("refined", 10).with_parent("draft").with_evidence(vec![
+            evidence(EvidenceKind::CodeCommitted, 10),
+            evidence(EvidenceKind::ManualApproval, 20),
+        ]);
+        graph.publish(&draft).unwrap();
+        graph.publish(&refined).unwrap();
+
+        let history = graph.stability_history("refined").unwrap();
+        let times: Vec<DateTime<Utc>> = history.iter().map(|(t, _)| *t).collect();
+        assert_eq!(times, vec![at(0), at(5), at(10), at(20)]);
+        assert!(history.windows(2).all(|w| w[1].1 > w[0].1));
+        assert!((history[0].1 - 0.3).abs() < f64::EPSILON);
+
+        // Final point matches the stored score of the latest refinement
+        let stored: f64 = graph
+            .conn
+            .query_row(
+                "SELECT computed_stability FROM intents WHERE id = 'refined'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!((history[3].1 - stored).abs() < f64::EPSILON);
+
+        // The root's own history stops at its own evidence
+        assert_eq!(graph.stability_history("draft").unwrap().len(), 2);
+        assert!(matches!(
+            graph.stability_history("missing"),
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        ));
+    }
+
+    #[test]
+    fn test_validate_lineage_detects_cycle() {
+        let graph = make_graph();
+        // Publish checks that a parent exists, so a multi-intent cycle needs
+        // data written with foreign keys off, as a raw import might.
+        graph
+            .conn
+            .execute_batch("PRAGMA foreign_keys = OFF")
+            .unwrap();
+        let mut a = IntentNode::new("agent-a", "A");
+        let b = IntentNode::new("agent-a", "B").with_parent(&a.id);
+        a.parent_id = Some(b.id.clone());
+        graph.publish(&a).unwrap();
+        graph.publish(&b).unwrap();
+        // A child hanging off the cycle is not itself part of it
+        graph
+            .publish(&IntentNode::new("agent-a", "C").with_parent(&b.id))
+            .unwrap();
+
+        let cycles = graph.validate_lineage().unwrap();
+        let mut expected = vec![a.id.clone(), b.id.clone()];
+        expected.sort();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0][0], expected[0]);
+        let mut found = cycles[0].clone();
+        found.sort();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_validate_lineage_detects_self_parent() {
+        let graph = make_graph();
+        let mut a = IntentNode::new("agent-a", "A");
+        a.parent_id = Some(a.id.clone());
+        graph.publish(&a).unwrap();
+
+        assert_eq!(graph.validate_lineage().unwrap(), vec![vec![a.id.clone()]]);
+    }
+
+    #[test]
+    fn test_validate_lineage_valid_dag_is_empty() {
+        let graph = make_graph();
+        let root = IntentNode::new("agent-a", "Root");
+        let child = IntentNode::new("agent-a", "Child").with_parent(&root.id);
+        let sibling = IntentNode::new("agent-b", "Sibling").with_parent(&root.id);
+        let grandchild = IntentNode::new("agent-b", "Grandchild").with_parent(&child.id);
+        for intent in [&root, &child, &sibling, &grandchild] {
+            graph.publish(intent).unwrap();
+        }
+        assert!(graph.validate_lineage().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_index_health_flags_corruption() {
+        let graph = make_graph();
+        let a = IntentNode::new("agent-a", "Users")
+            .with_provides(vec![InterfaceSpec::new("User", InterfaceKind::Model, "")])
+            .with_requires(vec![InterfaceSpec::new("Db", InterfaceKind::Config, "")]);
+        let b = IntentNode::new("agent-b", "Orders").with_provides(vec![InterfaceSpec::new(
+            "Order",
+            InterfaceKind::Model,
+            "",
+        )]);
+        graph.publish(&a).unwrap();
+        graph.publish(&b).unwrap();
+
+        let health = graph.index_health().unwrap();
+        assert_eq!(health.interface_rows, 3);
+        assert!(health.is_healthy());
+
+        // Drop one of a's index rows, and leave b's rows behind its intent
+        graph
+            .conn
+            .execute_batch(&format!(
+                "PRAGMA foreign_keys = OFF;
+                 DELETE FROM intent_interfaces WHERE intent_id = '{}' AND role = 'requires';
+                 DELETE FROM intents WHERE id = '{}';",
+                a.id, b.id
+            ))
+            .unwrap();
+
+        let health = graph.index_health().unwrap();
+        assert_eq!(
+            health,
+            IndexHealth {
+                interface_rows: 2,
+                orphaned_rows: 1,
+                intents_missing_entries: 1,
+            }
+        );
+        assert!(!health.is_healthy());
+    }
+
+    #[test]
+    fn test_publish_with_stability_stores_supplied_value() {
+        let graph = make_graph();
+        let intent = IntentNode::new("agent-a", "Imported")
+            .with_evidence(vec![Evidence::code_committed("committed")]);
+        graph.publish_with_stability(&intent, 0.9).unwrap();
+
+        assert_eq!(graph.query_all(Some(0.9)).unwrap().len(), 1);
+        let stored: f64 = graph
+            .conn
+            .query_row(
+                "SELECT computed_stability FROM intents WHERE id = ?1",
+                params![intent.id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!((stored - 0.9).abs() < f64::EPSILON);
+
+        let clamped = IntentNode::new("agent-a", "Out of range");
+        graph.publish_with_stability(&clamped, 1.7).unwrap();
+        assert_eq!(graph.query_all(Some(1.0)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_publish_with_stability_rejects_non_finite() {
+        let graph = make_graph();
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let intent = IntentNode::new("agent-a", "Bad score");
+            let err = graph.publish_with_stability(&intent, value).unwrap_err();
+            assert!(matches!(err, rusqlite::Error::ToSqlConversionFailure(_)));
+        }
+        assert_eq!(graph.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_old_conflicts_heal_under_graph_clock() {
+        use crate::clock::FixedClock;
+
+        let t0 = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let clock = Arc::new(FixedClock::new(t0));
+        let graph = make_graph()
+            .with_clock(clock.clone())
+            .with_scorer(StabilityScorer::new().with_conflict_half_life(chrono::Duration::days(1)));
+        let conflicted = |agent: &str, at: DateTime<Utc>| {
+            let mut conflict = Evidence::conflict("clashed");
+            conflict.timestamp = at;
+            IntentNode::new(agent, "Work").with_evidence(vec![conflict])
+        };
+
+        let old = conflicted("agent-a", t0);
+        clock.advance(chrono::Duration::days(30));
+        let recent = conflicted("agent-b", clock.now());
+        let old_score = graph.publish(&old).unwrap();
+        let recent_score = graph.publish(&recent).unwrap();
+        assert!(old_score > 0.29);
+        assert!((recent_score - 0.15).abs() < 1e-9);
+
+        clock.advance(chrono::Duration::days(30));
+        graph.recompute_all_stability().unwrap();
+        assert_eq!(graph.query_all(Some(0.29)).unwrap().len(), 2);
+
+        // Without a half-life the same conflict docks the score for good
+        let plain = make_graph().with_clock(clock.clone());
+        assert!((plain.publish(&old).unwrap() - 0.15).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_query_since_with_fixed_clock() {
+        use crate::clock::FixedClock;
+
+        let t0 = DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let clock = Arc::new(FixedClock::new(t0));
+        let graph = make_graph().with_clock(clock.clone());
+        assert_eq!(graph.now(), t0);
+
+        let first = IntentNode::new_with_clock("agent-a", "First", graph.clock());
+        graph.publish(&first).unwrap();
+        clock.advance(chrono::Duration::seconds(1));
+        let second = IntentNode::new_with_clock("agent-a", "Second", graph.clock());
+        graph.publish(&second).unwrap();
+
+        assert_eq!(first.timestamp, t0);
+        assert_eq!(second.timestamp, t0 + chrono::Duration::seconds(1));
+
+        // query_since is strictly-after: the boundary intent is excluded
+        let since_t0 = graph.query_since(t0, None).unwrap();
+        assert_eq!(since_t0.len(), 1);
+        assert_eq!(since_t0[0].id, second.id);
+
+        let before = graph
+            .query_since(t0 - chrono::Duration::milliseconds(1), None)
+            .unwrap();
+        assert_eq!(before.len(), 2);
+
+        assert!(graph.query_since(graph.now(), None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_kind_targeted_constraint_binds_without_tags() {
+        let graph = make_graph();
+        let policy = IntentNode::new("agent-a", "Schema policy")
+            .with_constraints(vec![Constraint::new("migrations", "must be reversible")
+                .with_kind(InterfaceKind::Migration)]);
+        graph.publish(&policy).unwrap();
+
+        let migration = IntentNode::new("agent-b", "Add recipes table").with_provides(vec![
+            InterfaceSpec::new("CreateRecipes", InterfaceKind::Migration, ""),
+        ]);
+        let result = graph.resolve(&migration, 0.0).unwrap();
+        assert_eq!(result.adopted_constraints.len(), 1);
+        assert_eq!(
+            result.adopted_constraints[0].requirement,
+            "must be reversible"
+        );
+
+        let model =
+            IntentNode::new("agent-c", "Recipe model").with_provides(vec![InterfaceSpec::new(
+                "Recipe",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+        assert!(graph
+            .resolve(&model, 0.0)
+            .unwrap()
+            .adopted_constraints
+            .is_empty());
+
+        // The kind survives the JSON round trip through storage
+        let stored = graph.get(&policy.id).unwrap().unwrap();
+        assert_eq!(
+            stored.constraints[0].applies_to_kind,
+            Some(InterfaceKind::Migration)
+        );
+    }
+
+    #[test]
+    fn test_agent_scoped_constraint_binds_only_listed_agents() {
+        let graph = make_graph().with_normalize_agent_ids(true);
+        let policy =
+            IntentNode::new("agent-a", "Billing policy").with_constraints(vec![Constraint::new(
+                "money",
+                "amounts in integer cents",
+            )
+            .with_affects(vec!["billing"])
+            .with_agents(vec!["billing-bot"])]);
+        graph.publish(&policy).unwrap();
+
+        let consumer = |agent: &str| {
+            IntentNode::new(agent, "Invoices").with_provides(vec![InterfaceSpec::new(
+                "Invoice",
+                InterfaceKind::Model,
+                "id: UUID",
+            )
+            .with_tags(vec!["billing"])])
+        };
+        let adopted = |agent: &str| {
+            graph
+                .resolve(&consumer(agent), 0.0)
+                .unwrap()
+                .adopted_constraints
+                .len()
+        };
+        assert_eq!(adopted("billing-bot"), 1);
+        assert_eq!(adopted("Billing-Bot"), 1);
+        assert_eq!(adopted("search-bot"), 0);
+
+        let scoped = &policy.constraints[0];
+        assert!(scoped.applies_to(&consumer("billing-bot")));
+        assert!(!scoped.applies_to(&consumer("search-bot")));
+        assert!(scoped
+            .clone()
+            .with_agents(vec![])
+            .applies_to(&consumer("search-bot")));
+
+        let stored = graph.get(&policy.id).unwrap().unwrap();
+        assert_eq!(stored.constraints[0].applies_to_agents, vec!["billing-bot"]);
+
+        // A list spelled differently from the consumer still binds it once
+        // both sides are normalized, the same rule binds_agent applies
+        let loose = scoped.clone().with_agents(vec![" Billing-Bot"]);
+        assert!(graph.binds_agent(&loose, "billing-BOT"));
+        assert!(!graph.binds_agent(&loose, "search-bot"));
+        assert!(!make_graph().binds_agent(&loose, "billing-bot"));
+    }
+
+    #[test]
+    fn test_publish_verbose_warns_on_name_collision() {
+        let graph = make_graph();
+        let intent = IntentNode::new("agent-a", "Models").with_provides(vec![
+            InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID"),
+            InterfaceSpec::new("UserModel", InterfaceKind::Model, "id: UUID"),
+            InterfaceSpec::new("Order", InterfaceKind::Model, "id: UUID"),
+        ]);
+
+        let stats = graph.publish_verbose(&intent).unwrap();
+        assert_eq!(graph.count().unwrap(), 1);
+        assert_eq!(
+            stats.warnings,
+            vec![PublishWarning {
+                section: "provides".to_string(),
+                normalized_name: "user".to_string(),
+                names: vec!["User".to_string(), "UserModel".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_publish_verbose_clean_intent_has_no_warnings() {
+        let graph = make_graph();
+        let intent = IntentNode::new("agent-a", "Models")
+            .with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )])
+            .with_requires(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+
+        let stats = graph.publish_verbose(&intent).unwrap();
+        assert!(stats.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_count_overlapping_matches_find_overlapping() {
+        let graph = make_graph();
+        graph
+            .publish(
+                &IntentNode::new("agent-a", "Users").with_provides(vec![InterfaceSpec::new(
+                    "User",
+                    InterfaceKind::Model,
+                    "id: UUID",
+                )]),
+            )
+            .unwrap();
+        graph
+            .publish(&IntentNode::new("agent-b", "Accounts").with_requires(vec![
+                InterfaceSpec::new("UserModel", InterfaceKind::Model, "id: UUID"),
+            ]))
+            .unwrap();
+        graph
+            .publish(
+                &IntentNode::new("agent-c", "Orders").with_provides(vec![InterfaceSpec::new(
+                    "Order",
+                    InterfaceKind::Model,
+                    "id: UUID",
+                )]),
+            )
+            .unwrap();
+
+        let specs = vec![InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID")];
+        for exclude in ["agent-x", "agent-a"] {
+            let expected = graph.find_overlapping(&specs, exclude, 0.0).unwrap().len();
+            assert_eq!(
+                graph.count_overlapping(&specs, exclude, 0.0).unwrap(),
+                expected
+            );
+        }
+        assert_eq!(graph.count_overlapping(&specs, "agent-x", 0.0).unwrap(), 2);
+        assert_eq!(graph.count_overlapping(&specs, "agent-x", 0.9).unwrap(), 0);
+        assert_eq!(graph.count_overlapping(&[], "agent-x", 0.0).unwrap(), 0);
+    }
+
+    fn severity_constraint_provider(severity: ConstraintSeverity) -> IntentNode {
+        IntentNode::new("agent-a", "Auth module")
+            .with_constraints(vec![Constraint::new("User model", "must have email")
+                .with_affects(vec!["user"])
+                .with_severity(severity)])
+            .with_evidence(vec![Evidence::code_committed("committed")])
+    }
+
+    fn user_tagged_intent() -> IntentNode {
+        IntentNode::new("agent-b", "Profiles").with_provides(vec![InterfaceSpec::new(
+            "Profile",
+            InterfaceKind::Model,
+            "id: UUID",
+        )
+        .with_tags(vec!["user"])])
+    }
+
+    #[test]
+    fn test_adopt_policy_critical_only_drops_preferred() {
+        let graph = make_graph();
+        graph
+            .publish(&severity_constraint_provider(ConstraintSeverity::Preferred))
+            .unwrap();
+
+        let all = graph.resolve(&user_tagged_intent(), 0.0).unwrap();
+        assert_eq!(all.adopted_constraints.len(), 1);
+        assert!(all.notes.is_empty());
+
+        let options = ResolveOptions::new(0.0).with_adopt_policy(AdoptPolicy::CriticalOnly);
+        let critical = graph.resolve_with(&user_tagged_intent(), &options).unwrap();
+        assert!(critical.adopted_constraints.is_empty());
+        assert!(!critical.has_adjustments());
+        assert_eq!(critical.notes.len(), 1);
+        assert!(critical.notes[0].contains("preferred"));
+    }
+
+    #[test]
+    fn test_adopt_policy_required_and_above() {
+        let graph = make_graph();
+        graph
+            .publish(&severity_constraint_provider(ConstraintSeverity::Preferred))
+            .unwrap();
+        graph
+            .publish(&severity_constraint_provider(ConstraintSeverity::Critical))
+            .unwrap();
+
+        let options = ResolveOptions::new(0.0).with_adopt_policy(AdoptPolicy::RequiredAndAbove);
+        let result = graph.resolve_with(&user_tagged_intent(), &options).unwrap();
+        assert_eq!(result.adopted_constraints.len(), 1);
+        assert_eq!(
+            result.adopted_constraints[0].severity,
+            ConstraintSeverity::Critical
+        );
+        assert_eq!(result.notes.len(), 1);
+    }
+
+    fn deprecated_user_provider() -> IntentNode {
+        IntentNode::new("agent-a", "Legacy users")
+            .with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )
+            .with_deprecated(Some("Account"))])
+            .with_evidence(vec![
+                Evidence::code_committed("committed"),
+                Evidence::test_pass("unit"),
+            ])
+    }
+
+    #[test]
+    fn test_resolve_requiring_deprecated_interface_suggests_migration() {
+        let graph = make_graph();
+        graph.publish(&deprecated_user_provider()).unwrap();
+
+        let b = IntentNode::new("agent-b", "Profiles").with_requires(vec![InterfaceSpec::new(
+            "User",
+            InterfaceKind::Model,
+            "id: UUID",
+        )]);
+        let result = graph.resolve(&b, 0.0).unwrap();
+        assert_eq!(result.adjustments.len(), 1);
+        assert_eq!(result.adjustments[0].kind, AdjustmentKind::MigrateOff);
+        assert!(result.adjustments[0].description.contains("'Account'"));
+    }
+
+    #[test]
+    fn test_resolve_duplicating_deprecated_interface_is_skipped() {
+        let graph = make_graph();
+        graph.publish(&deprecated_user_provider()).unwrap();
+
+        // Providing the same name or the named successor: no consume, no
+        // migration advice and no conflict with the retiring provision
+        for name in ["User", "Account"] {
+            let b = IntentNode::new("agent-b", "Users").with_provides(vec![InterfaceSpec::new(
+                name,
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+            let result = graph.resolve(&b, 0.0).unwrap();
+            assert!(result.adjustments.is_empty());
+            assert!(result.conflicts.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_stability_cache_matches_uncached_and_invalidates() {
+        let graph = make_graph().with_stability_cache(16);
+        graph.publish(&committed_user_provider()).unwrap();
+        let weak = IntentNode::new("agent-c", "Weak").with_provides(vec![InterfaceSpec::new(
+            "Order",
+            InterfaceKind::Model,
+            "id: UUID",
+        )]);
+        graph.publish(&weak).unwrap();
+
+        let uncached = make_graph();
+        uncached.publish(&committed_user_provider()).unwrap();
+        uncached.publish(&weak).unwrap();
+
+        for _ in 0..2 {
+            let cached = graph.summary().unwrap();
+            let plain = uncached.summary().unwrap();
+            assert!((cached.average_stability - plain.average_stability).abs() < f64::EPSILON);
+        }
+
+        // New evidence must not be masked by a cached score
+        graph
+            .add_evidence(&weak.id, &[Evidence::manual_approval()])
+            .unwrap();
+        let after = graph.summary().unwrap();
+        assert!(after.average_stability > uncached.summary().unwrap().average_stability);
+    }
+
+    #[test]
+    fn test_find_overlapping_include_self() {
+        let graph = make_graph();
+        let left =
+            IntentNode::new("agent-a", "Left scope").with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+        let right =
+            IntentNode::new("agent-a", "Right scope").with_provides(vec![InterfaceSpec::new(
+                "UserModel",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+        graph.publish(&left).unwrap();
+        graph.publish(&right).unwrap();
+
+        assert!(graph
+            .find_overlapping(&left.provides, "agent-a", 0.0)
+            .unwrap()
+            .is_empty());
+
+        let with_self = graph
+            .find_overlapping_with(
+                &left.provides,
+                "agent-a",
+                0.0,
+                OverlapOptions::default().with_include_self(true),
+            )
+            .unwrap();
+        assert!(with_self.iter().any(|i| i.id == right.id));
+    }
+
+    #[test]
+    fn test_outstanding_adjustments_reports_duplicate_provision() {
+        let graph = make_graph();
+        let user = |agent: &str, name: &str| {
+            IntentNode::new(agent, name).with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )])
+        };
+
+        let committed = user("agent-a", "Auth module").with_evidence(vec![
+            Evidence::code_committed("committed"),
+            Evidence::test_pass("passing"),
+        ]);
+        graph.publish(&committed).unwrap();
+        let duplicate = user("agent-b", "Profiles");
+        graph.publish(&duplicate).unwrap();
+        graph
+            .publish(&IntentNode::new("agent-b", "Unrelated"))
+            .unwrap();
+
+        let owed = graph.outstanding_adjustments("agent-b", 0.0).unwrap();
+        assert_eq!(owed.len(), 1);
+        assert_eq!(owed[0].0, duplicate.id);
+        assert_eq!(owed[0].1.kind, AdjustmentKind::ConsumeInstead);
+        assert_eq!(owed[0].1.source_intent_id, committed.id);
+
+        // The more committed side owes nothing
+        assert!(graph
+            .outstanding_adjustments("agent-a", 0.0)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_clear_keeps_schema_and_configuration() {
+        let graph = make_graph()
+            .with_stability_cache(8)
+            .with_name_match(NameMatchMode::Exact);
+        let (_, intents) = resolve_fixture();
+        for intent in &intents {
+            graph.publish(intent).unwrap();
+        }
+        let refined = IntentNode::new("agent-a", "Users v2").with_parent(&intents[0].id);
+        graph.publish(&refined).unwrap();
+
+        graph.clear().unwrap();
+        assert_eq!(graph.count().unwrap(), 0);
+        assert_eq!(graph.index_health().unwrap().interface_rows, 0);
+        assert_eq!(graph.name_match(), NameMatchMode::Exact);
+
+        // Same ids publish again cleanly
+        graph.publish(&intents[0]).unwrap();
+        assert_eq!(graph.count().unwrap(), 1);
+        assert_eq!(graph.index_health().unwrap().interface_rows, 1);
+    }
+
+    #[test]
+    fn test_clear_refuses_read_only_graph() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.db");
+        let mut graph = IntentGraph::persistent(path.to_str().unwrap()).unwrap();
+        graph
+            .publish(&IntentNode::new("agent-a", "Auth module"))
+            .unwrap();
+        graph.conn =
+            Connection::open_with_flags(&path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY).unwrap();
+
+        let err = graph.clear().unwrap_err();
+        assert_eq!(err.sqlite_error_code(), Some(rusqlite::ErrorCode::ReadOnly));
+        assert_eq!(graph.count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_id_generator_assigns_missing_ids() {
+        let graph = make_graph()
+            .with_id_generator(Arc::new(crate::ids::MonotonicGenerator::new("intent-")));
+        let mut anonymous = IntentNode::new("agent-a", "Auth");
+        anonymous.id = String::new();
+        graph.publish(&anonymous).unwrap();
+        graph.publish(&anonymous).unwrap();
+        let explicit = IntentNode::new("agent-a", "Billing");
+        graph.publish(&explicit).unwrap();
+
+        let ids: Vec<String> = graph
+            .query_by_agent("agent-a")
+            .unwrap()
+            .into_iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "intent-000000000001".to_string(),
+                "intent-000000000002".to_string(),
+                explicit.id.clone(),
+            ]
+        );
+        assert_eq!(
+            graph.fork().unwrap().id_generator().next_id(),
+            "intent-000000000003"
+        );
+    }
+
+    #[test]
+    fn test_publish_reports_assigned_id() {
+        let graph = make_graph()
+            .with_id_generator(Arc::new(crate::ids::MonotonicGenerator::new("intent-")));
+        let json = r#"{"agent_id": "agent-a", "intent": "Auth"}"#;
+        let anonymous = crate::api::intent_from_json(json).unwrap();
+        assert!(anonymous.id.is_empty());
+
+        let stats = graph.publish_verbose(&anonymous).unwrap();
+        assert_eq!(stats.id, "intent-000000000001");
+        graph
+            .add_evidence(&stats.id, &[Evidence::test_pass("unit")])
+            .unwrap();
+        let id = graph.publish_with_stability(&anonymous, 0.5).unwrap();
+        assert_eq!(id, "intent-000000000002");
+        assert!(graph.get(&id).unwrap().is_some());
+
+        let named = IntentNode::new("agent-a", "Billing");
+        assert_eq!(graph.publish_verbose(&named).unwrap().id, named.id);
+    }
+
+    #[test]
+    fn test_normalize_agent_ids_collapses_variants() {
+        let publish_variants = |graph: &IntentGraph| {
+            for agent in ["Agent-A", "agent-a ", "agent-a"] {
+                graph.publish(&IntentNode::new(agent, "Auth")).unwrap();
+            }
+        };
+
+        let raw = make_graph();
+        publish_variants(&raw);
+        assert_eq!(raw.summary().unwrap().agent_count, 3);
+
+        let graph = make_graph().with_normalize_agent_ids(true);
+        publish_variants(&graph);
+        let summary = graph.summary().unwrap();
+        assert_eq!(summary.agent_count, 1);
+        assert_eq!(summary.agents, vec!["agent-a"]);
+        assert_eq!(graph.query_by_agent(" AGENT-A").unwrap().len(), 3);
+
+        // Overlap checks treat the variants as the same agent too
+        graph
+            .publish(
+                &IntentNode::new("agent-a", "Users").with_provides(vec![InterfaceSpec::new(
+                    "User",
+                    InterfaceKind::Model,
+                    "id: UUID",
+                )]),
+            )
+            .unwrap();
+        let specs = [InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID")];
+        assert!(graph
+            .find_overlapping(&specs, "Agent-A", 0.0)
+            .unwrap()
+            .is_empty());
+        let mine = IntentNode::new("AGENT-A ", "More users").with_provides(specs.to_vec());
+        assert!(graph.resolve(&mine, 0.0).unwrap().is_clean());
+    }
+
+    #[test]
+    fn test_name_match_mode_controls_overlap_precision() {
+        let build =
+            |mode: NameMatchMode| {
+                let graph = make_graph().with_name_match(mode);
+                for (agent, name) in [("agent-a", "PowerUser"), ("agent-b", "UserModel")] {
+                    graph
+                        .publish(&IntentNode::new(agent, name).with_provides(vec![
+                            InterfaceSpec::new(name, InterfaceKind::Model, "id: UUID"),
+                        ]))
+                        .unwrap();
+                }
+                graph
+            };
+        let specs = [InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID")];
+        let names = |graph: &IntentGraph| -> Vec<String> {
+            graph
+                .find_overlapping(&specs, "agent-new", 0.0)
+                .unwrap()
+                .into_iter()
+                .map(|i| i.intent)
+                .collect()
+        };
+
+        // Substring (the default) keeps today's behavior: `user` ⊂ `power user`
+        let substring = build(NameMatchMode::default());
+        assert_eq!(substring.name_match(), NameMatchMode::Substring);
+        assert_eq!(names(&substring), vec!["PowerUser", "UserModel"]);
+        assert_eq!(
+            substring
+                .count_overlapping(&specs, "agent-new", 0.0)
+                .unwrap(),
+            2
+        );
+
+        // Exact drops the substring false positive, in resolve as well
+        let exact = build(NameMatchMode::Exact);
+        assert_eq!(names(&exact), vec!["UserModel"]);
+        assert_eq!(
+            exact.count_overlapping(&specs, "agent-new", 0.0).unwrap(),
+            1
+        );
+        let probe = IntentNode::new("agent-new", "Users").with_provides(specs.to_vec());
+        let conflicts = exact.resolve(&probe, 0.0).unwrap().conflicts;
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(exact.fork().unwrap().name_match(), NameMatchMode::Exact);
+
+        // Fuzzy reaches past substrings to near-misses the index can't see
+        let fuzzy = build(NameMatchMode::Fuzzy);
+        let typo = [InterfaceSpec::new(
+            "UsrModel",
+            InterfaceKind::Model,
+            "id: UUID",
+        )];
+        assert!(substring
+            .find_overlapping(&typo, "agent-new", 0.0)
+            .unwrap()
+            .is_empty());
+        let found = fuzzy.find_overlapping(&typo, "agent-new", 0.0).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].intent, "UserModel");
+    }
+
+    #[test]
+    fn test_publish_guard_blocks_noncompliant_intents() {
+        let mut graph = make_graph();
+        graph.register_publish_guard(Box::new(|intent| {
+            if intent.stability < 0.2 {
+                Err(format!("stability {:.2} is below 0.2", intent.stability))
+            } else {
+                Ok(())
+            }
+        }));
+
+        let low = IntentNode::new("agent-a", "Sketch").with_stability(0.1);
+        let err = graph.publish(&low).unwrap_err();
+        let rejected = match &err {
+            rusqlite::Error::ToSqlConversionFailure(e) => e.downcast_ref::<PublishRejected>(),
+            _ => None,
+        }
+        .unwrap();
+        assert_eq!(rejected.intent_id, low.id);
+        assert_eq!(rejected.reason, "stability 0.10 is below 0.2");
+        assert!(graph.get(&low.id).unwrap().is_none());
+        assert!(graph.publish_with_stability(&low, 0.9).is_err());
+
+        let compliant = IntentNode::new("agent-a", "Auth module");
+        graph.publish(&compliant).unwrap();
+        assert!(graph.get(&compliant.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_publish_limits_reject_oversized_intents() {
+        let graph = make_graph().with_publish_limits(PublishLimits {
+            max_signature_len: 32,
+            max_tags: 2,
+            max_specs: 2,
+        });
+        let reason = |intent: &IntentNode| match graph.publish(intent).unwrap_err() {
+            rusqlite::Error::ToSqlConversionFailure(e) => {
+                e.downcast_ref::<PublishRejected>().unwrap().reason.clone()
+            }
+            other => panic!("unexpected error: {other}"),
+        };
+        let spec =
+            |name: &str, signature: &str| InterfaceSpec::new(name, InterfaceKind::Model, signature);
+
+        let long = IntentNode::new("agent-a", "Blob")
+            .with_provides(vec![spec("Blob", &"x: int, ".repeat(10))]);
+        assert_eq!(
+            reason(&long),
+            "signature of 'Blob' is 80 bytes; the limit is 32"
+        );
+        assert!(graph.get(&long.id).unwrap().is_none());
+
+        let tagged = IntentNode::new("agent-a", "Tagged")
+            .with_requires(vec![spec("User", "id: UUID").with_tags(vec!["a", "b", "c"])]);
+        assert_eq!(reason(&tagged), "'User' has 3 tags; the limit is 2");
+
+        let wide = IntentNode::new("agent-a", "Wide").with_provides(vec![
+            spec("A", "id: UUID"),
+            spec("B", "id: UUID"),
+            spec("C", "id: UUID"),
+        ]);
+        assert_eq!(reason(&wide), "provides has 3 entries; the limit is 2");
+
+        let normal = IntentNode::new("agent-a", "Users")
+            .with_provides(vec![
+                spec("User", "id: UUID").with_tags(vec!["user", "auth"])
+            ]);
+        graph.publish(&normal).unwrap();
+
+        // Forks keep the limits; the defaults admit ordinary intents
+        assert_eq!(graph.fork().unwrap().publish_limits().max_tags, 2);
+        make_graph().publish(&long).unwrap();
+    }
+
+    #[test]
+    fn test_publish_guards_run_in_order() {
+        let mut graph = make_graph();
+        graph.register_publish_guard(Box::new(|intent| {
+            let migrates = intent
+                .provides
+                .iter()
+                .any(|s| s.kind == InterfaceKind::Migration);
+            let approved = intent
+                .evidence
+                .iter()
+                .any(|e| e.kind == EvidenceKind::ManualApproval);
+            if migrates && !approved {
+                Err("migrations require manual_approval evidence".to_string())
+            } else {
+                Ok(())
+            }
+        }));
+        graph.register_publish_guard(Box::new(|_| Err("frozen".to_string())));
+
+        let migration =
+            IntentNode::new("agent-a", "Add recipes").with_provides(vec![InterfaceSpec::new(
+                "recipes_table",
+                InterfaceKind::Migration,
+                "",
+            )]);
+        let err = graph.publish(&migration).unwrap_err().to_string();
+        assert!(err.contains("manual_approval"), "{}", err);
+        let approved = migration.with_evidence(vec![Evidence::manual_approval()]);
+        let err = graph.publish(&approved).unwrap_err().to_string();
+        assert!(err.contains("frozen"), "{}", err);
+        assert_eq!(graph.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_shared_module_confirms_single_tag_overlap() {
+        let graph = make_graph();
+        let spec = |name: &str, module: &str| {
+            InterfaceSpec::new(name, InterfaceKind::Function, "(x: str) -> str")
+                .with_module(module)
+                .with_tags(vec!["checkout"])
+        };
+        let existing = IntentNode::new("agent-a", "Cart totals")
+            .with_provides(vec![spec("compute_total", "shop/checkout/pricing")]);
+        graph.publish(&existing).unwrap();
+
+        // One shared tag alone is not an overlap...
+        let elsewhere =
+            IntentNode::new("agent-b", "Receipt").with_provides(vec![spec("apply_discount", "")]);
+        assert!(graph.resolve(&elsewhere, 0.0).unwrap().is_clean());
+
+        // ...but it is inside the same module
+        let same_module = IntentNode::new("agent-b", "Discounts")
+            .with_provides(vec![spec("apply_discount", "shop/checkout")]);
+        let result = graph.resolve(&same_module, 0.0).unwrap();
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].their_intent_id, existing.id);
+    }
+
+    #[test]
+    fn test_distant_modules_suppress_weak_name_overlap() {
+        let graph = make_graph();
+        let existing = IntentNode::new("agent-a", "Users").with_provides(vec![InterfaceSpec::new(
+            "User",
+            InterfaceKind::Model,
+            "id: UUID",
+        )
+        .with_module("auth/models")]);
+        graph.publish(&existing).unwrap();
+
+        let profile = |module: &str| {
+            IntentNode::new("agent-b", "Profiles").with_provides(vec![InterfaceSpec::new(
+                "UserProfile",
+                InterfaceKind::Model,
+                "id: UUID",
+            )
+            .with_module(module)])
+        };
+        // Prefix match within the same tree still overlaps
+        assert_eq!(
+            graph
+                .resolve(&profile("auth/profiles"), 0.0)
+                .unwrap()
+                .conflicts
+                .len(),
+            1
+        );
+        assert_eq!(graph.resolve(&profile(""), 0.0).unwrap().conflicts.len(), 1);
+        // A different tree suppresses the weak match
+        assert!(graph
+            .resolve(&profile("billing/accounts"), 0.0)
+            .unwrap()
+            .is_clean());
+
+        // Equal names overlap however far apart their modules are
+        let user = IntentNode::new("agent-b", "Users too").with_provides(vec![InterfaceSpec::new(
+            "User",
+            InterfaceKind::Model,
+            "id: UUID",
+        )
+        .with_module("billing/accounts")]);
+        assert_eq!(graph.resolve(&user, 0.0).unwrap().conflicts.len(), 1);
+    }
+
+    #[test]
+    fn test_all_tags_counts_interface_rows() {
+        let graph = make_graph();
+        assert!(graph.all_tags().unwrap().is_empty());
+
+        let spec = |name: &str, tags: Vec<&str>| {
+            InterfaceSpec::new(name, InterfaceKind::Model, "id: UUID").with_tags(tags)
+        };
+        graph
+            .publish(
+                &IntentNode::new("agent-a", "Auth")
+                    .with_provides(vec![
+                        spec("User", vec!["auth", "user"]),
+                        spec("Session", vec!["auth"]),
+                    ])
+                    .with_requires(vec![spec("Token", vec!["authentication", "auth"])]),
+            )
+            .unwrap();
+        graph
+            .publish(&IntentNode::new("agent-b", "Profiles").with_provides(vec![
+                spec("Profile", vec!["user", "user"]),
+                spec("Avatar", vec![]),
+            ]))
+            .unwrap();
+
+        assert_eq!(
+            graph.all_tags().unwrap(),
+            vec![
+                ("auth".to_string(), 3),
+                ("user".to_string(), 2),
+                ("authentication".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_query_by_tags_all_and_any() {
+        let graph = make_graph();
+        let tagged = |name: &str, tags: Vec<&str>| {
+            IntentNode::new("agent-a", name).with_provides(vec![InterfaceSpec::new(
+                name,
+                InterfaceKind::Model,
+                "id: UUID",
+            )
+            .with_tags(tags)])
+        };
+        let auth_model = tagged("User", vec!["auth", "model"]);
+        let auth_only = tagged("Session", vec!["auth"]);
+        let billing = tagged("Invoice", vec!["billing", "model"]);
+        // Substring of "auth" must not count as the tag itself
+        let author = tagged("Author", vec!["author"]);
+        for intent in [&auth_model, &auth_only, &billing, &author] {
+            graph.publish(intent).unwrap();
+        }
+        // Tags spread across provides and requires still count for `All`
+        let split = IntentNode::new("agent-b", "Split")
+            .with_provides(vec![InterfaceSpec::new(
+                "Token",
+                InterfaceKind::Model,
+                "id: UUID",
+            )
+            .with_tags(vec!["auth"])])
+            .with_requires(vec![InterfaceSpec::new(
+                "Account",
+                InterfaceKind::Model,
+                "id: UUID",
+            )
+            .with_tags(vec!["model"])]);
+        graph.publish(&split).unwrap();
+
+        let ids = |tags: &[&str], mode: TagMatch| -> Vec<String> {
+            graph
+                .query_by_tags(tags, mode, 0.0)
+                .unwrap()
+                .into_iter()
+                .map(|i| i.id)
+                .collect()
+        };
+
+        assert_eq!(
+            ids(&["auth", "model"], TagMatch::All),
+            vec![auth_model.id.clone(), split.id.clone()]
+        );
+        assert_eq!(
+            ids(&["auth", "billing"], TagMatch::Any),
+            vec![
+                auth_model.id.clone(),
+                auth_only.id.clone(),
+                billing.id.clone(),
+                split.id.clone()
+            ]
+        );
+        assert_eq!(
+            ids(&["auth", "auth"], TagMatch::All),
+            vec![
+                auth_model.id.clone(),
+                auth_only.id.clone(),
+                split.id.clone()
+            ]
+        );
+        assert!(ids(&["auth", "billing"], TagMatch::All).is_empty());
+        assert!(ids(&[], TagMatch::Any).is_empty());
+    }
+
+    #[test]
+    fn test_diff_intents_categorizes_changes() {
+        use crate::models::diff_intents;
+
+        let parent = IntentNode::new("agent-a", "Auth module")
+            .with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, email: str",
+            )])
+            .with_constraints(vec![
+                Constraint::new("User model", "must have email: str"),
+                Constraint::new("Sessions", "expire after 24h"),
+            ]);
+        let refined = IntentNode::new("agent-a", "Auth module, refined")
+            .with_provides(vec![
+                // Signature changed incompatibly: email dropped
+                InterfaceSpec::new("user", InterfaceKind::Model, "id: UUID"),
+                InterfaceSpec::new("Token", InterfaceKind::Model, "value: str"),
+            ])
+            .with_constraints(vec![Constraint::new("User model", "must have email: str")])
+            .with_parent(&parent.id);
+
+        let diff = diff_intents(&parent, &refined);
+
+        let added: Vec<&str> = diff
+            .provides
+            .added
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(added, vec!["Token"]);
+        assert!(diff.provides.removed.is_empty());
+        assert_eq!(diff.provides.changed.len(), 1);
+        let change = &diff.provides.changed[0];
+        assert_eq!(change.before.name, "User");
+        assert_eq!(change.after.name, "user");
+        assert!(!change.signature_compatible);
+
+        assert!(diff.requires.is_empty());
+        assert!(diff.constraints.added.is_empty());
+        assert!(diff.constraints.changed.is_empty());
+        let removed: Vec<&str> = diff
+            .constraints
+            .removed
+            .iter()
+            .map(|c| c.target.as_str())
+            .collect();
+        assert_eq!(removed, vec!["Sessions"]);
+
+        // A widened signature still satisfies the old one
+        let widened =
+            IntentNode::new("agent-a", "Auth module").with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, email: str, name: str",
+            )]);
+        let diff = diff_intents(&parent, &widened);
+        assert!(diff.provides.changed[0].signature_compatible);
+
+        assert!(diff_intents(&parent, &parent).is_empty());
+    }
+
+    #[test]
+    fn test_diff_intents_matches_constraint_targets_like_resolution() {
+        use crate::models::diff_intents;
+
+        let before = IntentNode::new("agent-a", "Auth")
+            .with_constraints(vec![Constraint::new("user_model", "must have email")]);
+        let after = IntentNode::new("agent-a", "Auth")
+            .with_constraints(vec![Constraint::new("User model", "must have email")]);
+
+        let diff = diff_intents(&before, &after);
+        assert!(diff.constraints.added.is_empty());
+        assert!(diff.constraints.removed.is_empty());
+        // Same requirement, only the spelling of the target moved
+        assert_eq!(diff.constraints.changed.len(), 1);
+    }
+
+    #[test]
+    fn test_most_consumed_ranks_by_consumer_count() {
+        let graph = make_graph();
+        let provider = |agent: &str, name: &str| {
+            IntentNode::new(agent, name).with_provides(vec![InterfaceSpec::new(
+                name,
+                InterfaceKind::Model,
+                "id: UUID",
+            )])
+        };
+        let consumer = |agent: &str, names: &[&str]| {
+            IntentNode::new(agent, "Consumer").with_requires(
+                names
+                    .iter()
+                    .map(|n| InterfaceSpec::new(n, InterfaceKind::Model, "id: UUID"))
+                    .collect(),
+            )
+        };
+
+        let user = provider("agent-a", "User");
+        let order = provider("agent-b", "Order");
+        let invoice = provider("agent-c", "Invoice");
+        for intent in [&invoice, &order, &user] {
+            graph.publish(intent).unwrap();
+        }
+        graph
+            .publish(&consumer("agent-d", &["User", "Order"]))
+            .unwrap();
+        graph.publish(&consumer("agent-e", &["User"])).unwrap();
+        graph
+            .publish(&consumer("agent-f", &["User", "Order"]))
+            .unwrap();
+        graph.publish(&consumer("agent-g", &["Invoice"])).unwrap();
+        // Recorded consumption counts too, once per agent
+        graph
+            .add_evidence(&invoice.id, &[Evidence::consumed_by("agent-h")])
+            .unwrap();
+        graph
+            .add_evidence(&order.id, &[Evidence::consumed_by("agent-d")])
+            .unwrap();
+
+        let ranked: Vec<(String, usize)> = graph
+            .most_consumed(10, 0.0)
+            .unwrap()
+            .into_iter()
+            .map(|(intent, n)| (intent.id, n))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                (user.id.clone(), 3),
+                (order.id.clone(), 2),
+                (invoice.id.clone(), 2)
+            ]
+        );
+
+        let top = graph.most_consumed(1, 0.0).unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].0.id, user.id);
+    }
+
+    #[test]
+    fn test_resolve_and_apply_only_confident_consumes() {
+        let graph = make_graph();
+        let provider = |agent: &str, name: &str| {
+            IntentNode::new(agent, name)
+                .with_provides(vec![InterfaceSpec::new(
+                    name,
+                    InterfaceKind::Model,
+                    "id: UUID",
+                )])
+                .with_evidence(vec![Evidence::code_committed("landed")])
+        };
+        let user = provider("agent-a", "User");
+        let profile = provider("agent-c", "Profile");
+        graph.publish(&user).unwrap();
+        graph.publish(&profile).unwrap();
+
+        let mine = IntentNode::new("agent-b", "Accounts").with_provides(vec![
+            InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID"),
+            InterfaceSpec::new("ProfileSettings", InterfaceKind::Model, "id: UUID"),
+        ]);
+        let result = graph.resolve_and_apply(&mine, 0.0, 0.5).unwrap();
+        let consumes = result
+            .adjustments
+            .iter()
+            .filter(|a| a.kind == AdjustmentKind::ConsumeInstead)
+            .count();
+        assert_eq!(consumes, 2);
+        // "Profile" within "ProfileSettings" is too weak a match to apply
+        assert_eq!(result.notes.len(), 1);
+        assert!(result.notes[0].contains("'User'"));
+
+        let stored = graph.get(&mine.id).unwrap().unwrap();
+        let names = |specs: &[InterfaceSpec]| -> Vec<String> {
+            specs.iter().map(|s| s.name.clone()).collect()
+        };
+        assert_eq!(names(&stored.provides), vec!["ProfileSettings"]);
+        assert_eq!(names(&stored.requires), vec!["User"]);
+        assert!(graph
+            .dependency_edges(0.0)
+            .unwrap()
+            .contains(&(mine.id.clone(), user.id.clone())));
+
+        let user_after = graph.get(&user.id).unwrap().unwrap();
+        assert_eq!(user_after.evidence[1].consumer(), Some("agent-b"));
+        let stable: Vec<String> = graph
+            .query_all(Some(0.59))
+            .unwrap()
+            .into_iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(stable, vec![user.id.clone()]);
+        assert_eq!(graph.get(&profile.id).unwrap().unwrap().evidence.len(), 1);
+
+        // Providers below the threshold are left alone
+        let other = IntentNode::new("agent-d", "Users").with_provides(vec![InterfaceSpec::new(
+            "User",
+            InterfaceKind::Model,
+            "id: UUID",
+        )]);
+        let result = graph.resolve_and_apply(&other, 0.0, 0.9).unwrap();
+        assert!(result.has_adjustments());
+        assert!(result.notes.is_empty());
+        assert_eq!(graph.get(&other.id).unwrap().unwrap().provides.len(), 1);
+    }
+
+    #[test]
+    fn test_simulate_convergence_depends_on_order() {
+        let graph = make_graph();
+        let spec = || vec![InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID")];
+        let a = IntentNode::new("agent-a", "User model")
+            .with_provides(spec())
+            .with_evidence(vec![Evidence::code_committed("landed")]);
+        let b = IntentNode::new("agent-b", "Accounts").with_provides(spec());
+        graph.publish(&a).unwrap();
+        graph.publish(&b).unwrap();
+
+        let report = graph
+            .simulate_convergence(&[a.id.clone(), b.id.clone()], 0.0)
+            .unwrap();
+        assert_eq!(report.initial_conflicts, 1);
+        assert!(report.converged());
+        assert_eq!(report.steps.len(), 2);
+        assert_eq!(report.steps[0].conflicts, 1);
+        assert_eq!(report.steps[1].applied, 1);
+        assert_eq!(report.steps[1].conflicts, 0);
+
+        // The more stable side has nothing to do on its own
+        let report = graph
+            .simulate_convergence(std::slice::from_ref(&a.id), 0.0)
+            .unwrap();
+        assert_eq!(report.final_conflicts, 1);
+        assert!(!report.converged());
 
-    fn make_graph() -> IntentGraph {
-        IntentGraph::in_memory().unwrap()
+        // The simulation never touches the real graph
+        assert_eq!(graph.conflict_count(0.0).unwrap(), 1);
+        assert_eq!(graph.get(&b.id).unwrap().unwrap().provides.len(), 1);
+        assert_eq!(graph.get(&a.id).unwrap().unwrap().evidence.len(), 1);
+
+        let missing = graph.simulate_convergence(&["nope".to_string()], 0.0);
+        assert!(matches!(missing, Err(rusqlite::Error::QueryReturnedNoRows)));
     }
 
     #[test]
-    fn test_publish_and_query() {
+    fn test_refinements_do_not_conflict_with_their_lineage() {
         let graph = make_graph();
-        let intent = IntentNode::new("agent-a", "Build AuthService");
-        graph.publish(&intent).unwrap();
+        let spec = || vec![InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID")];
+        let parent = IntentNode::new("agent-a", "User model").with_provides(spec());
+        graph.publish(&parent).unwrap();
+        let child = IntentNode::new("agent-b", "User model, refined")
+            .with_provides(spec())
+            .with_parent(&parent.id);
+        graph.publish(&child).unwrap();
+        let grandchild = IntentNode::new("agent-c", "User model, refined again")
+            .with_provides(spec())
+            .with_parent(&child.id);
 
-        let all = graph.query_all(None).unwrap();
-        assert_eq!(all.len(), 1);
-        assert_eq!(all[0].agent_id, "agent-a");
+        for intent in [&parent, &child, &grandchild] {
+            assert!(graph.resolve(intent, 0.0).unwrap().conflicts.is_empty());
+        }
+        let batch = graph
+            .resolve_batch(std::slice::from_ref(&grandchild), 0.0)
+            .unwrap();
+        assert!(batch[0].conflicts.is_empty());
+        assert!(graph
+            .would_conflict_with(&grandchild, 0.0)
+            .unwrap()
+            .is_empty());
+
+        // An unrelated agent providing the same interface still conflicts
+        let stranger = IntentNode::new("agent-d", "Accounts").with_provides(spec());
+        assert!(!graph.resolve(&stranger, 0.0).unwrap().conflicts.is_empty());
     }
 
     #[test]
-    fn test_query_by_agent() {
+    fn test_dry_run_batch_flags_conflicts_without_writing() {
         let graph = make_graph();
-        graph.publish(&IntentNode::new("agent-a", "Auth")).unwrap();
+        let spec = |name| vec![InterfaceSpec::new(name, InterfaceKind::Model, "id: UUID")];
         graph
-            .publish(&IntentNode::new("agent-b", "Recipes"))
+            .publish(
+                &IntentNode::new("agent-a", "User model")
+                    .with_provides(spec("User"))
+                    .with_evidence(vec![Evidence::code_committed("landed")]),
+            )
             .unwrap();
-        graph
-            .publish(&IntentNode::new("agent-a", "Auth v2"))
+
+        let conflicting = IntentNode::new("agent-b", "Accounts").with_provides(spec("User"));
+        let report = graph
+            .dry_run_batch(std::slice::from_ref(&conflicting), 0.0)
             .unwrap();
+        assert!(!report.is_clean());
+        assert!(!report.new_conflicts.is_empty());
+        assert!(report
+            .new_conflicts
+            .iter()
+            .all(|c| c.my_intent_id == conflicting.id || c.their_intent_id == conflicting.id));
+        assert_eq!(graph.count().unwrap(), 1);
 
-        let a_intents = graph.query_by_agent("agent-a").unwrap();
-        assert_eq!(a_intents.len(), 2);
+        let parent = IntentNode::new("agent-b", "Invoices").with_provides(spec("Invoice"));
+        let mut child = IntentNode::new("agent-b", "Invoices v2").with_provides(spec("Invoice"));
+        child.parent_id = Some(parent.id.clone());
+        let report = graph.dry_run_batch(&[parent, child], 0.0).unwrap();
+        assert!(
+            report.new_conflicts.is_empty(),
+            "{:?}",
+            report.new_conflicts
+        );
+        assert!(report.issues.is_empty());
+        assert!(report.is_clean());
+        assert_eq!(graph.count().unwrap(), 1);
+
+        let blank = IntentNode::new("agent-b", " ");
+        let report = graph.dry_run_batch(&[blank.clone(), blank], 0.0).unwrap();
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.issues.len(), 2);
+        assert!(!report.is_clean());
     }
 
     #[test]
-    fn test_stability_filter() {
+    fn test_interface_provenance_finds_earliest_provider() {
         let graph = make_graph();
+        let t0 = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let at = |mut intent: IntentNode, mins: i64| {
+            intent.timestamp = t0 + chrono::Duration::minutes(mins);
+            intent
+        };
+        let spec = |name: &str| vec![InterfaceSpec::new(name, InterfaceKind::Model, "id: UUID")];
 
-        // Low stability intent
-        let low = IntentNode::new("agent-a", "exploring");
-        graph.publish(&low).unwrap();
+        let later = at(
+            IntentNode::new("agent-b", "Accounts")
+                .with_provides(spec("UserModel"))
+                .with_evidence(vec![Evidence::code_committed("landed")]),
+            20,
+        );
+        let first = at(
+            IntentNode::new("agent-a", "Auth").with_provides(spec("User")),
+            10,
+        );
+        let consumer = at(
+            IntentNode::new("agent-c", "Billing").with_requires(spec("User")),
+            30,
+        );
+        let unrelated = at(
+            IntentNode::new("agent-d", "Recipes").with_provides(spec("Recipe")),
+            5,
+        );
+        // Published out of timestamp order on purpose
+        for intent in [&later, &consumer, &first, &unrelated] {
+            graph.publish(intent).unwrap();
+        }
 
-        // High stability intent
-        let high = IntentNode::new("agent-b", "committed").with_evidence(vec![
-            Evidence::code_committed("initial"),
-            Evidence::test_pass("tests"),
-            Evidence::consumed_by("agent-c"),
-        ]);
-        graph.publish(&high).unwrap();
+        let ids = |intents: &[IntentNode]| -> Vec<String> {
+            intents.iter().map(|i| i.id.clone()).collect()
+        };
+        let provenance = graph.interface_provenance("users", 0.0).unwrap();
+        assert_eq!(provenance.name, "user");
+        assert_eq!(provenance.earliest_provider.unwrap().id, first.id);
+        assert_eq!(
+            ids(&provenance.providers),
+            vec![first.id.clone(), later.id.clone()]
+        );
+        assert_eq!(ids(&provenance.requirers), vec![consumer.id.clone()]);
 
-        let high_only = graph.query_all(Some(0.6)).unwrap();
-        assert_eq!(high_only.len(), 1);
-        assert_eq!(high_only[0].agent_id, "agent-b");
+        // Only the committed provider clears a higher floor
+        let stable = graph.interface_provenance("User", 0.5).unwrap();
+        assert_eq!(stable.earliest_provider.unwrap().id, later.id);
+        assert!(stable.requirers.is_empty());
+
+        assert!(graph
+            .interface_provenance("Invoice", 0.0)
+            .unwrap()
+            .earliest_provider
+            .is_none());
     }
 
     #[test]
-    fn test_find_overlapping() {
+    fn test_export_json_filtered() {
         let graph = make_graph();
+        let tagged = |name: &str, tags: Vec<&str>| {
+            vec![InterfaceSpec::new(name, InterfaceKind::Model, "id: UUID").with_tags(tags)]
+        };
+        let auth_v1 =
+            IntentNode::new("agent-a", "Auth v1").with_provides(tagged("User", vec!["auth"]));
+        let auth_v2 = IntentNode::new("agent-a", "Auth v2")
+            .with_provides(tagged("Session", vec!["sso"]))
+            .with_parent(&auth_v1.id);
+        let billing = IntentNode::new("agent-b", "Billing")
+            .with_provides(tagged("Invoice", vec!["billing"]))
+            .with_evidence(vec![Evidence::code_committed("landed")]);
+        for intent in [&auth_v1, &auth_v2, &billing] {
+            graph.publish(intent).unwrap();
+        }
 
-        // Agent A provides UserModel
-        let a = IntentNode::new("agent-a", "Auth module").with_provides(vec![InterfaceSpec::new(
-            "User",
-            InterfaceKind::Model,
-            "id: UUID, email: str",
+        let export = |filter: ExportFilter| -> Vec<String> {
+            crate::api::intents_from_json(&graph.export_json_filtered(filter).unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|i| i.id)
+                .collect()
+        };
+
+        assert_eq!(export(ExportFilter::new()).len(), 3);
+        assert_eq!(
+            export(ExportFilter::new().with_agents(vec!["agent-b"])),
+            vec![billing.id.clone()]
+        );
+        assert_eq!(
+            export(ExportFilter::new().with_tags(vec!["billing", "missing"])),
+            vec![billing.id.clone()]
+        );
+        assert_eq!(
+            export(ExportFilter::new().with_min_stability(0.5)),
+            vec![billing.id.clone()]
+        );
+        assert!(export(
+            ExportFilter::new()
+                .with_agents(vec!["agent-a"])
+                .with_tags(vec!["billing"])
         )
-        .with_tags(vec!["user", "auth", "model"])]);
-        graph.publish(&a).unwrap();
+        .is_empty());
 
-        // Agent B requires something user-related
-        let my_specs = vec![
-            InterfaceSpec::new("UserRef", InterfaceKind::Model, "user_id: UUID")
-                .with_tags(vec!["user", "recipe", "model"]),
-        ];
+        // The parent comes along so the slice loads into an empty graph
+        let json = graph
+            .export_json_filtered(ExportFilter::new().with_tags(vec!["sso"]))
+            .unwrap();
+        let slice = crate::api::intents_from_json(&json).unwrap();
+        let ids: Vec<&str> = slice.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec![auth_v1.id.as_str(), auth_v2.id.as_str()]);
+        let target = make_graph();
+        for intent in &slice {
+            target.publish(intent).unwrap();
+        }
+        assert_eq!(target.count().unwrap(), 2);
+    }
 
-        let overlapping = graph.find_overlapping(&my_specs, "agent-b", 0.0).unwrap();
-        assert_eq!(overlapping.len(), 1);
-        assert_eq!(overlapping[0].agent_id, "agent-a");
+    #[test]
+    fn test_unsatisfied_requirements() {
+        let graph = make_graph();
+        let consumer =
+            IntentNode::new("agent-a", "Billing").with_requires(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID, email: str",
+            )]);
+        graph.publish(&consumer).unwrap();
+
+        let gaps = graph.unsatisfied_requirements(0.0).unwrap();
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].0.id, consumer.id);
+        assert_eq!(gaps[0].1.name, "User");
+
+        // Same agent providing it doesn't count
+        graph
+            .publish(&IntentNode::new("agent-a", "Own user").with_provides(vec![
+                InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID, email: str"),
+            ]))
+            .unwrap();
+        assert_eq!(graph.unsatisfied_requirements(0.0).unwrap().len(), 1);
+
+        // A provider whose signature lacks a field leaves the gap open
+        graph
+            .publish(&IntentNode::new("agent-b", "Thin user").with_provides(vec![
+                InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID"),
+            ]))
+            .unwrap();
+        assert_eq!(graph.unsatisfied_requirements(0.0).unwrap().len(), 1);
+
+        // A compatible provider closes it
+        graph
+            .publish(
+                &IntentNode::new("agent-c", "Auth").with_provides(vec![InterfaceSpec::new(
+                    "User",
+                    InterfaceKind::Model,
+                    "id: UUID, email: str, name: str",
+                )]),
+            )
+            .unwrap();
+        assert!(graph.unsatisfied_requirements(0.0).unwrap().is_empty());
     }
 
     #[test]
-    fn test_resolve_consume_instead() {
+    fn test_dependency_edges() {
         let graph = make_graph();
 
-        // Agent A provides User model with high stability
-        let a = IntentNode::new("agent-a", "Auth module")
-            .with_provides(vec![InterfaceSpec::new(
+        let provider =
+            IntentNode::new("agent-a", "Auth module").with_provides(vec![InterfaceSpec::new(
                 "User",
                 InterfaceKind::Model,
                 "id: UUID, email: str",
-            )
-            .with_tags(vec!["user", "auth", "model"])])
-            .with_evidence(vec![
-                Evidence::code_committed("committed"),
-                Evidence::test_pass("passing"),
-            ]);
-        graph.publish(&a).unwrap();
+            )]);
+        graph.publish(&provider).unwrap();
 
-        // Agent C also wants to provide a User model but is less committed
-        let c =
-            IntentNode::new("agent-c", "Meal planning").with_provides(vec![InterfaceSpec::new(
+        let consumer =
+            IntentNode::new("agent-b", "Recipe module").with_requires(vec![InterfaceSpec::new(
+                "UserModel",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+        graph.publish(&consumer).unwrap();
+
+        let unrelated =
+            IntentNode::new("agent-c", "Billing").with_provides(vec![InterfaceSpec::new(
+                "Invoice",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+        graph.publish(&unrelated).unwrap();
+
+        // Neither the consumer's own provision nor an incompatible one counts
+        let own =
+            IntentNode::new("agent-b", "Recipe users").with_provides(vec![InterfaceSpec::new(
                 "User",
                 InterfaceKind::Model,
-                "id: UUID, name: str",
+                "id: UUID",
+            )]);
+        graph.publish(&own).unwrap();
+        let incompatible =
+            IntentNode::new("agent-d", "Legacy users").with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: int",
+            )]);
+        graph.publish(&incompatible).unwrap();
+
+        let edges = graph.dependency_edges(0.0).unwrap();
+        assert_eq!(edges, vec![(consumer.id.clone(), provider.id.clone())]);
+
+        // Stability floor removes the exploring intents entirely
+        assert!(graph.dependency_edges(0.5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let graph = make_graph();
+
+        let provider =
+            IntentNode::new("agent-a", "Auth \"core\"").with_provides(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+        graph.publish(&provider).unwrap();
+
+        let consumer =
+            IntentNode::new("agent-b", "Recipes").with_requires(vec![InterfaceSpec::new(
+                "User",
+                InterfaceKind::Model,
+                "id: UUID",
+            )]);
+        graph.publish(&consumer).unwrap();
+
+        let dot = graph.to_dot(0.0).unwrap();
+        assert!(dot.starts_with("digraph intents {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains(&format!(
+            "\"{}\" [label=\"agent-a\\nAuth \\\"core\\\"\"];",
+            provider.id
+        )));
+        assert!(dot.contains(&format!("\"{}\" -> \"{}\";", consumer.id, provider.id)));
+        assert_eq!(dot.matches(" -> ").count(), 1);
+    }
+
+    #[test]
+    fn test_exclusive_guard_serializes_writers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.db");
+        let first = IntentGraph::persistent(path.to_str().unwrap()).unwrap();
+        let second = IntentGraph::persistent(path.to_str().unwrap()).unwrap();
+        second.conn.busy_timeout(std::time::Duration::ZERO).unwrap();
+
+        let guard = first.begin_exclusive().unwrap();
+        guard
+            .publish(&IntentNode::new("agent-a", "Auth module"))
+            .unwrap();
+        let err = second.begin_exclusive().err().unwrap();
+        assert_eq!(
+            err.sqlite_error_code(),
+            Some(rusqlite::ErrorCode::DatabaseBusy)
+        );
+        drop(guard);
+
+        let guard = second.begin_exclusive().unwrap();
+        assert_eq!(guard.count().unwrap(), 1);
+        guard.commit().unwrap();
+    }
+
+    #[test]
+    fn test_exclusive_guard_rolls_back_on_panic_or_error() {
+        let graph = make_graph();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let guard = graph.begin_exclusive().unwrap();
+            guard
+                .publish(&IntentNode::new("agent-a", "Auth module"))
+                .unwrap();
+            panic!("coordinator crashed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(graph.count().unwrap(), 0);
+
+        let intent = IntentNode::new("agent-a", "Auth module");
+        {
+            let guard = graph.begin_exclusive().unwrap();
+            guard.publish(&intent).unwrap();
+            assert!(guard.publish(&intent).is_err());
+        }
+        assert_eq!(graph.count().unwrap(), 0);
+
+        let guard = graph.begin_exclusive().unwrap();
+        guard.publish(&intent).unwrap();
+        guard.rollback().unwrap();
+        assert_eq!(graph.count().unwrap(), 0);
+        graph.begin_exclusive().unwrap().publish(&intent).unwrap();
+        assert_eq!(graph.count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_backfill_with_explicit_timestamps() {
+        let graph = make_graph();
+        let t0 = "2024-03-01T09:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let later = IntentNode::new("agent-a", "Add billing")
+            .with_timestamp(t0 + chrono::Duration::days(2));
+        let earlier = IntentNode::new("agent-b", "Add users").with_timestamp(t0);
+        graph.publish(&later).unwrap();
+        graph.publish(&earlier).unwrap();
+
+        let all = graph.query_all(None).unwrap();
+        let order: Vec<&str> = all.iter().map(|i| i.intent.as_str()).collect();
+        assert_eq!(order, vec!["Add users", "Add billing"]);
+        assert_eq!(all[0].timestamp, t0);
+        let since = graph
+            .query_since(t0 + chrono::Duration::days(1), None)
+            .unwrap();
+        assert_eq!(since.len(), 1);
+        assert_eq!(since[0].id, later.id);
+    }
+
+    #[test]
+    fn test_malformed_timestamp_fails_loudly() {
+        let graph = make_graph();
+        let intent = IntentNode::new("agent-a", "Auth module");
+        graph.publish(&intent).unwrap();
+        assert_eq!(
+            graph.get(&intent.id).unwrap().unwrap().timestamp,
+            intent.timestamp
+        );
+
+        graph
+            .conn
+            .execute(
+                "UPDATE intents SET timestamp = 'last tuesday' WHERE id = ?1",
+                params![intent.id],
             )
-            .with_tags(vec!["user", "meal", "model"])]);
+            .unwrap();
+        assert!(matches!(
+            graph.get(&intent.id),
+            Err(rusqlite::Error::FromSqlConversionFailure(2, _, _))
+        ));
+        assert!(graph.query_all(None).is_err());
+        assert!(graph.query_by_agent("agent-a").is_err());
+    }
 
-        let result = graph.resolve(&c, 0.0).unwrap();
-        assert!(!result.adjustments.is_empty());
-        assert_eq!(result.adjustments[0].kind, AdjustmentKind::ConsumeInstead);
+    #[test]
+    fn test_reopen_rebuilds_index_past_malformed_timestamp() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.db");
+        let path = path.to_str().unwrap();
+        let spec = || vec![InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID")];
+        let good = IntentNode::new("agent-a", "Users").with_provides(spec());
+        let bad = IntentNode::new("agent-b", "Accounts").with_requires(spec());
+        {
+            let graph = IntentGraph::persistent(path).unwrap();
+            graph.publish(&good).unwrap();
+            graph.publish(&bad).unwrap();
+            // A legacy file: stale index and one unparseable timestamp
+            graph
+                .conn
+                .execute_batch(&format!(
+                    "DELETE FROM intent_interfaces;
+                     UPDATE intents SET timestamp = 'last tuesday' WHERE id = '{}';
+                     PRAGMA user_version = 0;",
+                    bad.id
+                ))
+                .unwrap();
+        }
+
+        let graph = IntentGraph::persistent(path).unwrap();
+        assert_eq!(graph.index_health().unwrap().interface_rows, 2);
+        assert_eq!(graph.get(&good.id).unwrap().unwrap().id, good.id);
+        assert!(graph.get(&bad.id).is_err());
     }
 
     #[test]
-    fn test_resolve_adopt_constraint() {
+    fn test_requirement_satisfaction_direction() {
         let graph = make_graph();
+        let narrow = InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID");
+        let wide = InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID, email: str");
+        assert!(narrow.is_satisfied_by(&wide));
+        assert!(wide.satisfies(&narrow));
+        assert!(!wide.is_satisfied_by(&narrow));
+        assert!(!narrow.satisfies(&wide));
 
-        // Agent A publishes a constraint about User model
-        let a = IntentNode::new("agent-a", "Auth module")
+        // Providing more than required is fine; requiring more is not
+        let provider = IntentNode::new("agent-a", "Users")
+            .with_provides(vec![wide.clone()])
+            .with_evidence(vec![Evidence::code_committed("landed")]);
+        graph.publish(&provider).unwrap();
+        let consumer = IntentNode::new("agent-b", "Profiles").with_requires(vec![narrow]);
+        assert!(graph
+            .resolve(&consumer, 0.0)
+            .unwrap()
+            .adjustments
+            .is_empty());
+
+        let graph = make_graph();
+        let provider = IntentNode::new("agent-a", "Users")
             .with_provides(vec![InterfaceSpec::new(
                 "User",
                 InterfaceKind::Model,
-                "id: UUID, email: str",
-            )
-            .with_tags(vec!["user", "auth"])])
-            .with_constraints(vec![Constraint::new(
-                "User model",
-                "must have email: str as unique field",
-            )
-            .with_affects(vec!["user", "account"])])
-            .with_evidence(vec![Evidence::code_committed("committed")]);
-        graph.publish(&a).unwrap();
+                "id: UUID",
+            )])
+            .with_evidence(vec![Evidence::code_committed("landed")]);
+        graph.publish(&provider).unwrap();
+        let consumer = IntentNode::new("agent-b", "Profiles").with_requires(vec![wide]);
+        let kinds: Vec<AdjustmentKind> = graph
+            .resolve(&consumer, 0.0)
+            .unwrap()
+            .adjustments
+            .into_iter()
+            .map(|a| a.kind)
+            .collect();
+        assert_eq!(kinds, vec![AdjustmentKind::AdaptSignature]);
+    }
 
-        // Agent B works on something user-related
-        let b =
-            IntentNode::new("agent-b", "Recipe module").with_provides(vec![InterfaceSpec::new(
-                "Recipe",
+    #[test]
+    fn test_reresolve_affected_after_evidence_flips_winner() {
+        let graph = make_graph();
+        let spec = || vec![InterfaceSpec::new("User", InterfaceKind::Model, "id: UUID")];
+        let a = IntentNode::new("agent-a", "User model")
+            .with_provides(spec())
+            .with_evidence(vec![Evidence::test_pass("passing")]);
+        let b = IntentNode::new("agent-b", "Accounts").with_provides(spec());
+        let unrelated =
+            IntentNode::new("agent-c", "Billing").with_provides(vec![InterfaceSpec::new(
+                "Invoice",
                 InterfaceKind::Model,
-                "id: UUID, author_id: UUID",
+                "",
+            )]);
+        for intent in [&a, &b, &unrelated] {
+            graph.publish(intent).unwrap();
+        }
+        let stored = |id: &str| graph.get(id).unwrap().unwrap().computed_stability.unwrap();
+        let previous = stored(&b.id);
+        assert_eq!(
+            graph.resolve(&a, 0.0).unwrap().conflicts[0].winner,
+            Winner::Me
+        );
+        let before = graph.resolve(&b, 0.0).unwrap();
+        assert_eq!(before.adjustments[0].kind, AdjustmentKind::ConsumeInstead);
+
+        graph
+            .add_evidence(
+                &b.id,
+                &[
+                    Evidence::code_committed("landed"),
+                    Evidence::test_pass("passing"),
+                ],
             )
-            .with_tags(vec!["recipe", "user", "model"])]);
+            .unwrap();
+        // Nothing moves unless the score does
+        assert!(graph
+            .reresolve_affected(&b.id, stored(&b.id), 0.0)
+            .unwrap()
+            .is_empty());
 
-        let result = graph.resolve(&b, 0.0).unwrap();
-        assert!(!result.adopted_constraints.is_empty());
+        let results = graph.reresolve_affected(&b.id, previous, 0.0).unwrap();
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec![b.id.as_str(), a.id.as_str()]);
+        assert_eq!(results[0].1.conflicts[0].winner, Winner::Me);
+        assert!(results[1].1.conflicts.is_empty());
+        assert_eq!(
+            results[1].1.adjustments[0].kind,
+            AdjustmentKind::ConsumeInstead
+        );
+
+        assert!(matches!(
+            graph.reresolve_affected("nope", 0.0, 0.0),
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        ));
     }
 
     #[test]
-    fn test_graph_summary() {
+    fn test_stability_histogram_buckets() {
         let graph = make_graph();
-        graph.publish(&IntentNode::new("agent-a", "Auth")).unwrap();
+        for (i, stability) in [0.1, 0.29, 0.3, 0.5, 0.7, 0.95].into_iter().enumerate() {
+            graph
+                .publish_with_stability(&IntentNode::new(&format!("agent-{i}"), "x"), stability)
+                .unwrap();
+        }
+        assert_eq!(
+            graph.stability_histogram(&[0.3, 0.7]).unwrap(),
+            vec![2, 2, 2]
+        );
+        assert_eq!(graph.stability_histogram(&[]).unwrap(), vec![6]);
+        assert_eq!(
+            graph.stability_histogram(&[0.0, 1.0]).unwrap(),
+            vec![0, 6, 0]
+        );
+        assert_eq!(
+            make_graph().stability_histogram(&[0.5]).unwrap(),
+            vec![0, 0]
+        );
+
+        for bad in [&[0.7, 0.3][..], &[0.3, 0.3], &[-0.1], &[1.5], &[f64::NAN]] {
+            assert!(matches!(
+                graph.stability_histogram(bad),
+                Err(rusqlite::Error::ToSqlConversionFailure(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_would_conflict_with_lists_conflicting_intents() {
+        let graph = make_graph();
+        let spec = |name| vec![InterfaceSpec::new(name, InterfaceKind::Model, "id: UUID")];
+        let provider = IntentNode::new("agent-a", "User model").with_provides(spec("User"));
+        let other = IntentNode::new("agent-c", "Billing").with_provides(spec("Invoice"));
+        graph.publish(&provider).unwrap();
+        graph.publish(&other).unwrap();
+
+        let duplicate = IntentNode::new("agent-b", "Accounts").with_provides(spec("User"));
+        let found = graph.would_conflict_with(&duplicate, 0.0).unwrap();
+        let ids: Vec<&str> = found.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec![provider.id.as_str()]);
+
+        let clean = IntentNode::new("agent-b", "Search").with_provides(spec("SearchIndex"));
+        assert!(graph.would_conflict_with(&clean, 0.0).unwrap().is_empty());
+        assert_eq!(graph.count().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_checkpoint_truncates_wal() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.db");
+        let graph = IntentGraph::persistent(path.to_str().unwrap()).unwrap();
         graph
-            .publish(&IntentNode::new("agent-b", "Recipes"))
+            .conn
+            .pragma_update(None, "journal_mode", "WAL")
             .unwrap();
-        graph.publish(&IntentNode::new("agent-c", "Meals")).unwrap();
+        for i in 0..200 {
+            graph
+                .publish(&IntentNode::new(&format!("agent-{i}"), "Bulk import"))
+                .unwrap();
+        }
+        let wal = dir.path().join("graph.db-wal");
+        assert!(std::fs::metadata(&wal).unwrap().len() > 0);
 
-        let summary = graph.summary().unwrap();
-        assert_eq!(summary.total_intents, 3);
-        assert_eq!(summary.agent_count, 3);
+        graph.checkpoint().unwrap();
+        assert_eq!(std::fs::metadata(&wal).unwrap().len(), 0);
+        assert_eq!(graph.count().unwrap(), 200);
+        assert_eq!(graph.query_by_agent("agent-7").unwrap().len(), 1);
+
+        make_graph().checkpoint().unwrap();
     }
 
     #[test]
-    fn test_no_self_overlap() {
+    fn test_query_raw_is_read_only() {
         let graph = make_graph();
+        for agent in ["agent-a", "agent-a", "agent-b"] {
+            graph.publish(&IntentNode::new(agent, "Work")).unwrap();
+        }
 
-        let a = IntentNode::new("agent-a", "Auth module").with_provides(vec![InterfaceSpec::new(
-            "User",
-            InterfaceKind::Model,
-            "id: UUID",
-        )
-        .with_tags(vec!["user", "model"])]);
-        graph.publish(&a).unwrap();
+        let rows = graph
+            .query_raw(
+                "  select agent_id, COUNT(*) AS n FROM intents
+                 WHERE agent_id != ?1 GROUP BY agent_id ORDER BY agent_id",
+                &[&"nobody"],
+            )
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["agent_id"], "agent-a");
+        assert_eq!(rows[0]["n"], 2);
+        assert_eq!(rows[1]["n"], 1);
+        let version = graph.query_raw("PRAGMA user_version", &[]).unwrap();
+        assert_eq!(version.len(), 1);
 
-        // Querying overlap for agent-a's own specs should exclude itself
-        let overlapping = graph.find_overlapping(&a.provides, "agent-a", 0.0).unwrap();
-        assert!(overlapping.is_empty());
+        for sql in [
+            "INSERT INTO intents (id) VALUES ('x')",
+            "DELETE FROM intents",
+            "PRAGMA user_version = 99",
+            "WITH x AS (SELECT 1) DELETE FROM intents",
+        ] {
+            assert!(
+                matches!(
+                    graph.query_raw(sql, &[]),
+                    Err(rusqlite::Error::InvalidQuery)
+                ),
+                "{}",
+                sql
+            );
+        }
+        assert_eq!(graph.count().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_query_by_module_prefix_matches_nested_paths() {
+        let graph = make_graph();
+        let spec = |name, module| {
+            InterfaceSpec::new(name, InterfaceKind::Function, "").with_module(module)
+        };
+        let login = IntentNode::new("agent-a", "Login")
+            .with_provides(vec![spec("login", "src/auth/session/login.py")]);
+        let tokens = IntentNode::new("agent-b", "Token checks")
+            .with_requires(vec![spec("verify_token", "src/auth/tokens.py")]);
+        let billing = IntentNode::new("agent-c", "Billing")
+            .with_provides(vec![spec("charge", "src/billing/auth_hold.py")]);
+        let unplaced =
+            IntentNode::new("agent-d", "Somewhere").with_provides(vec![InterfaceSpec::new(
+                "helper",
+                InterfaceKind::Function,
+                "",
+            )]);
+        for intent in [&login, &tokens, &billing, &unplaced] {
+            graph.publish(intent).unwrap();
+        }
+
+        let ids = |prefix| -> Vec<String> {
+            graph
+                .query_by_module_prefix(prefix, 0.0)
+                .unwrap()
+                .into_iter()
+                .map(|i| i.id)
+                .collect()
+        };
+        assert_eq!(ids("src/auth/"), vec![login.id.clone(), tokens.id.clone()]);
+        assert_eq!(ids("src/auth/session"), vec![login.id.clone()]);
+        assert!(ids("auth").is_empty());
+        assert_eq!(ids("").len(), 3);
+    }
+
+    #[test]
+    fn test_consolidation_candidates_group_compatible_duplicates() {
+        let graph = make_graph();
+        let provider = |agent: &str, name: &str, signature: &str| {
+            let intent = IntentNode::new(agent, "Provide").with_provides(vec![InterfaceSpec::new(
+                name,
+                InterfaceKind::Model,
+                signature,
+            )]);
+            graph.publish(&intent).unwrap();
+            intent.id
+        };
+        let narrow = provider("agent-a", "User", "id: UUID");
+        let wide = provider("agent-b", "User", "id: UUID, email: str");
+        provider("agent-c", "User", "id: int");
+        provider("agent-d", "Recipe", "id: UUID");
+        let invoice = provider("agent-e", "Invoice", "total: int");
+        let invoices = provider("agent-f", "Invoices", "total: int");
+
+        let groups: Vec<Vec<String>> = graph
+            .consolidation_candidates(0.0)
+            .unwrap()
+            .into_iter()
+            .map(|g| g.into_iter().map(|i| i.id).collect())
+            .collect();
+        assert_eq!(groups, vec![vec![narrow, wide], vec![invoice, invoices]]);
+    }
+
+    #[test]
+    fn test_compatibility_matrix_scores_each_direction() {
+        let graph = make_graph();
+        let spec = |name, signature| InterfaceSpec::new(name, InterfaceKind::Model, signature);
+        graph
+            .publish(
+                &IntentNode::new("agent-a", "Users and orgs").with_provides(vec![
+                    spec("User", "id: UUID, email: str"),
+                    spec("Organization", "id: UUID"),
+                ]),
+            )
+            .unwrap();
+        graph
+            .publish(
+                &IntentNode::new("agent-b", "Profiles")
+                    .with_requires(vec![
+                        spec("User", "id: UUID"),
+                        spec("Organization", "id: UUID"),
+                    ])
+                    .with_provides(vec![spec("Profile", "id: UUID")]),
+            )
+            .unwrap();
+        graph
+            .publish(
+                &IntentNode::new("agent-c", "Reports")
+                    .with_requires(vec![spec("User", "id: int"), spec("Profile", "id: UUID")]),
+            )
+            .unwrap();
+
+        let matrix = graph.compatibility_matrix(0.0).unwrap();
+        let score = |consumer: &str, provider: &str| {
+            matrix
+                .iter()
+                .find(|(c, p, _)| c == consumer && p == provider)
+                .map(|(_, _, s)| *s)
+        };
+        assert_eq!(score("agent-b", "agent-a"), Some(1.0));
+        assert_eq!(score("agent-b", "agent-c"), Some(0.0));
+        // agent-a's User has the wrong id type for agent-c
+        assert_eq!(score("agent-c", "agent-a"), Some(0.0));
+        assert_eq!(score("agent-c", "agent-b"), Some(0.5));
+        // agent-a requires nothing
+        assert_eq!(score("agent-a", "agent-b"), None);
+        assert_eq!(matrix.len(), 4);
     }
 }
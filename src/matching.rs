@@ -3,6 +3,12 @@
 //! Provides normalization and comparison functions for interface names,
 //! type signatures, and constraint targets. Mirrors the Python matching module.
 
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
 /// Known suffixes to strip for name normalization.
 const NAME_SUFFIXES: &[&str] = &[
     "Model",
@@ -11,38 +17,73 @@ const NAME_SUFFIXES: &[&str] = &[
     "Controller",
     "Spec",
     "Interface",
+    "Impl",
 ];
 
+/// Plural-looking words that are already singular (or have no singular).
+const SINGULAR_EXCEPTIONS: &[&str] = &["series", "species", "news"];
+
 /// Normalize an interface name for comparison.
 ///
-/// Lowercase, strip known suffixes, split CamelCase into tokens.
+/// Lowercase, strip known suffixes (repeatedly, so stacked conventions like
+/// `UserServiceImpl` reduce fully), split CamelCase into tokens, and
+/// singularize each token (see [`singularize`]).
 pub fn normalize_name(name: &str) -> String {
     if name.is_empty() {
         return String::new();
     }
 
-    // Strip known suffixes
+    // Strip known suffixes until none remain, never reducing the name to empty
     let mut stripped = name;
-    for suffix in NAME_SUFFIXES {
-        if stripped.ends_with(suffix) && stripped.len() > suffix.len() {
-            stripped = &stripped[..stripped.len() - suffix.len()];
-            break;
-        }
+    while let Some(suffix) = NAME_SUFFIXES
+        .iter()
+        .find(|suffix| stripped.ends_with(*suffix) && stripped.len() > suffix.len())
+    {
+        stripped = &stripped[..stripped.len() - suffix.len()];
     }
 
     // Split CamelCase into tokens
     let tokens = split_camel_case(stripped);
     if tokens.is_empty() {
-        return stripped.to_lowercase();
+        return singularize(&stripped.to_lowercase());
     }
 
     tokens
         .iter()
-        .map(|t| t.to_lowercase())
+        .map(|t| singularize(&t.to_lowercase()))
         .collect::<Vec<_>>()
         .join(" ")
 }
 
+/// Conservatively reduce a lowercase English plural to its singular.
+///
+/// Handles `-ies` → `-y` (`categories`), `-es` after a sibilant (`boxes`,
+/// `batches`, `addresses`), and a plain trailing `s` (`users`). Of the
+/// `-ches` words only `-tches` drops the `es`, since `caches` and `niches`
+/// are plurals of words already ending in `e`. Words of
+/// three letters or fewer and words ending in `ss`, `us` or `is` (`class`,
+/// `status`, `analysis`) are left alone, so a miss is preferred over a
+/// mangled name.
+pub fn singularize(word: &str) -> String {
+    if word.len() <= 3 || SINGULAR_EXCEPTIONS.contains(&word) {
+        return word.to_string();
+    }
+    if let Some(stem) = word.strip_suffix("ies") {
+        if stem.len() >= 2 {
+            return format!("{}y", stem);
+        }
+    }
+    for sibilant in ["sses", "shes", "tches", "xes", "zzes"] {
+        if word.ends_with(sibilant) {
+            return word[..word.len() - 2].to_string();
+        }
+    }
+    if word.ends_with('s') && !["ss", "us", "is"].iter().any(|end| word.ends_with(end)) {
+        return word[..word.len() - 1].to_string();
+    }
+    word.to_string()
+}
+
 /// Split a CamelCase string into tokens.
 fn split_camel_case(s: &str) -> Vec<String> {
     let mut tokens = Vec::new();
@@ -68,11 +109,70 @@ fn split_camel_case(s: &str) -> Vec<String> {
     tokens
 }
 
+/// How loosely [`names_overlap_with`] matches normalized names, from most
+/// precise to most permissive. Each mode accepts everything the previous
+/// one does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NameMatchMode {
+    /// Normalized names are equal
+    Exact,
+    /// One normalized name starts with the other
+    Prefix,
+    /// One normalized name contains the other as whole tokens (`user` ↔
+    /// `power user` included, `id` ↔ `grid` not); see [`contains_tokens`]
+    #[default]
+    Substring,
+    /// Substring, or within an edit distance of a quarter of the longer name
+    Fuzzy,
+}
+
+impl NameMatchMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NameMatchMode::Exact => "exact",
+            NameMatchMode::Prefix => "prefix",
+            NameMatchMode::Substring => "substring",
+            NameMatchMode::Fuzzy => "fuzzy",
+        }
+    }
+
+    /// Parse the lowercase name produced by [`as_str`](Self::as_str).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "exact" => Some(NameMatchMode::Exact),
+            "prefix" => Some(NameMatchMode::Prefix),
+            "substring" => Some(NameMatchMode::Substring),
+            "fuzzy" => Some(NameMatchMode::Fuzzy),
+            _ => None,
+        }
+    }
+}
+
+/// Shortest normalized name that counts as contained in another under
+/// [`NameMatchMode::Substring`]. Anything shorter (`id`, `to`) appears in
+/// too many unrelated names to mean anything.
+pub const MIN_CONTAINMENT_LEN: usize = 3;
+
+/// Whether normalized name `needle` appears in `haystack` as a run of whole
+/// tokens and is at least `min_len` characters long.
+///
+/// `user` is contained in `power user profile`; `id` is not contained in
+/// `grid`, and neither is `user` in `superuser`.
+pub fn contains_tokens(haystack: &str, needle: &str, min_len: usize) -> bool {
+    needle.chars().count() >= min_len
+        && format!(" {} ", haystack).contains(&format!(" {} ", needle))
+}
+
 /// Check if two names refer to the same concept.
 ///
 /// Returns true if normalized names are equal, one is a prefix
-/// of the other, or one contains the other.
+/// of the other, or one contains the other as whole tokens.
 pub fn names_overlap(a: &str, b: &str) -> bool {
+    names_overlap_with(a, b, NameMatchMode::Substring)
+}
+
+/// [`names_overlap`] at the given precision; see [`NameMatchMode`].
+pub fn names_overlap_with(a: &str, b: &str, mode: NameMatchMode) -> bool {
     if a.is_empty() || b.is_empty() {
         return false;
     }
@@ -83,31 +183,152 @@ pub fn names_overlap(a: &str, b: &str) -> bool {
     if na == nb {
         return true;
     }
+    if mode == NameMatchMode::Exact {
+        return false;
+    }
 
     // Prefix match
     if na.starts_with(&*nb) || nb.starts_with(&*na) {
         return true;
     }
+    if mode == NameMatchMode::Prefix {
+        return false;
+    }
 
     // Containment match
-    na.contains(&*nb) || nb.contains(&*na)
+    if contains_tokens(&na, &nb, MIN_CONTAINMENT_LEN)
+        || contains_tokens(&nb, &na, MIN_CONTAINMENT_LEN)
+    {
+        return true;
+    }
+    mode == NameMatchMode::Fuzzy
+        && edit_distance(&na, &nb) * 4 <= na.chars().count().max(nb.chars().count())
+}
+
+/// Levenshtein distance in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// How strongly two names overlap, in [0.0, 1.0].
+///
+/// 1.0 for equal normalized names, the length ratio of the shorter to the
+/// longer normalized name for prefix/containment matches, 0.0 otherwise.
+pub fn name_overlap_strength(a: &str, b: &str) -> f64 {
+    name_overlap_strength_with(a, b, NameMatchMode::Substring)
+}
+
+/// [`name_overlap_strength`] at the given precision. Matches only
+/// [`NameMatchMode::Fuzzy`] accepts score one minus the edit distance over
+/// the longer name's length.
+pub fn name_overlap_strength_with(a: &str, b: &str, mode: NameMatchMode) -> f64 {
+    if !names_overlap_with(a, b, mode) {
+        return 0.0;
+    }
+    let na = normalize_name(a);
+    let nb = normalize_name(b);
+    let (shorter, longer) = if na.len() <= nb.len() {
+        (&na, &nb)
+    } else {
+        (&nb, &na)
+    };
+    if longer.contains(shorter.as_str()) {
+        shorter.len() as f64 / longer.len() as f64
+    } else {
+        1.0 - edit_distance(&na, &nb) as f64 / longer.chars().count() as f64
+    }
+}
+
+/// How close two interfaces' module paths are; see [`module_affinity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleAffinity {
+    /// Equal paths, or one nested inside the other
+    Same,
+    /// Either path is empty, or they share a leading part and then diverge
+    Neutral,
+    /// No leading part in common
+    Distant,
+}
+
+/// Compare two module paths segment by segment.
+///
+/// Paths split on `/`, `\`, `.` and `:`, so `app/auth/models.py`,
+/// `app.auth.models` and `app::auth::models` all read the same way. An
+/// empty path says nothing about where an interface lives and is always
+/// [`ModuleAffinity::Neutral`].
+pub fn module_affinity(a: &str, b: &str) -> ModuleAffinity {
+    let segments = |path: &str| -> Vec<String> {
+        path.split(['/', '\\', '.', ':'])
+            .filter(|s| !s.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (sa, sb) = (segments(a), segments(b));
+    if sa.is_empty() || sb.is_empty() {
+        return ModuleAffinity::Neutral;
+    }
+
+    let common = sa.iter().zip(&sb).take_while(|(x, y)| x == y).count();
+    if common == sa.len().min(sb.len()) {
+        ModuleAffinity::Same
+    } else if common == 0 {
+        ModuleAffinity::Distant
+    } else {
+        ModuleAffinity::Neutral
+    }
+}
+
+/// Jaccard similarity of two tag sets (|intersection| / |union|).
+///
+/// Duplicate tags are counted once. Returns 0.0 when both sets are empty.
+pub fn tag_similarity(a: &[String], b: &[String]) -> f64 {
+    let set_a: HashSet<&str> = a.iter().map(|t| t.as_str()).collect();
+    let set_b: HashSet<&str> = b.iter().map(|t| t.as_str()).collect();
+    let union = set_a.union(&set_b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    set_a.intersection(&set_b).count() as f64 / union as f64
 }
 
 /// Normalize a type string for comparison.
 ///
 /// Handles aliases (UUID<->uuid, String<->str, i64<->int),
-/// Optional\[X\] -> X, list\[X\]<->Vec\<X\><->List\[X\].
+/// Optional\[X\] -> X, list\[X\]<->Vec\<X\><->List\[X\]<->&\[X\]<->\[X; N\],
+/// and byte buffers (Vec\<u8\>, &\[u8\], \[u8; N\]) -> bytes. References and
+/// the smart pointers Box, Rc, and Arc are transparent.
 pub fn normalize_type(t: &str) -> String {
     let t = t.trim();
+    if let Some(referent) = t.strip_prefix('&') {
+        let referent = referent.trim_start();
+        return normalize_type(referent.strip_prefix("mut ").unwrap_or(referent));
+    }
+    let t = strip_qualifier(t);
     if t.is_empty() {
         return String::new();
     }
 
-    let mut t = t.to_string();
+    // Box<X>, Rc<X>, Arc<X> -> X
+    for pointer in ["Box<", "Rc<", "Arc<"] {
+        if let Some(inner) = t.strip_prefix(pointer).and_then(|r| r.strip_suffix('>')) {
+            return normalize_type(inner);
+        }
+    }
 
     // Handle Optional[X] -> X
     if t.starts_with("Optional[") && t.ends_with(']') {
-        t = t[9..t.len() - 1].to_string();
+        return normalize_type(&t[9..t.len() - 1]);
     }
 
     // Handle X | None or None | X
@@ -117,16 +338,20 @@ pub fn normalize_type(t: &str) -> String {
             .map(|p| p.trim())
             .filter(|p| *p != "None")
             .collect();
-        if let Some(first) = parts.first() {
-            t = first.to_string();
-        } else {
-            return String::new();
-        }
+        return match parts.first() {
+            Some(first) => normalize_type(first),
+            None => String::new(),
+        };
     }
 
+    let t = t.to_string();
+
     // Handle generic containers
     if let Some(inner) = extract_container_inner(&t) {
         let normalized_inner = normalize_type(&inner);
+        if normalized_inner == "u8" {
+            return "bytes".to_string();
+        }
         return format!("list[{}]", normalized_inner);
     }
 
@@ -137,11 +362,28 @@ pub fn normalize_type(t: &str) -> String {
         "int" | "i32" | "i64" | "i128" | "u32" | "u64" => "int".to_string(),
         "float" | "f32" | "f64" => "float".to_string(),
         "bool" | "boolean" => "bool".to_string(),
+        "bytes" | "bytearray" => "bytes".to_string(),
         _ => t.to_lowercase(),
     }
 }
 
-/// Extract the inner type from container types like list[X], List[X], Vec<X>.
+/// Drop a module qualifier from the head of a type: `typing.Optional[str]`
+/// becomes `Optional[str]`, `crate::models::User` becomes `User`. Only the
+/// outermost name is touched; generic arguments are normalized recursively.
+fn strip_qualifier(t: &str) -> &str {
+    let head_end = t.find(['[', '<']).unwrap_or(t.len());
+    let head = &t[..head_end];
+    if head.contains(char::is_whitespace) {
+        return t;
+    }
+    let rust_path = head.rfind("::").map(|i| i + 2);
+    let python_path = head.rfind('.').map(|i| i + 1);
+    let start = rust_path.max(python_path).unwrap_or(0);
+    &t[start..]
+}
+
+/// Extract the inner type from container types like list[X], List[X], Vec<X>,
+/// and Rust slices and arrays ([X], [X; N]).
 fn extract_container_inner(t: &str) -> Option<String> {
     if (t.starts_with("list[") || t.starts_with("List[")) && t.ends_with(']') {
         return Some(t[5..t.len() - 1].trim().to_string());
@@ -149,6 +391,10 @@ fn extract_container_inner(t: &str) -> Option<String> {
     if t.starts_with("Vec<") && t.ends_with('>') {
         return Some(t[4..t.len() - 1].trim().to_string());
     }
+    if let Some(body) = t.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+        let element = body.rsplit_once(';').map_or(body, |(element, _)| element);
+        return Some(element.trim().to_string());
+    }
     None
 }
 
@@ -167,30 +413,251 @@ pub fn parse_signature(sig: &str) -> Vec<(String, String)> {
         .collect()
 }
 
+/// The comma-separated parts of `sig` that [`parse_signature`] can't read as
+/// `field: type` — it drops parts without a colon, and an empty field or type
+/// never matches anything. Note that commas inside generics (`Dict[str, int]`)
+/// split the field too.
+pub fn unparsed_signature_parts(sig: &str) -> Vec<String> {
+    if sig.trim().is_empty() {
+        return Vec::new();
+    }
+
+    sig.split(',')
+        .map(str::trim)
+        .filter(|part| match part.split_once(':') {
+            Some((field, type_str)) => field.trim().is_empty() || type_str.trim().is_empty(),
+            None => true,
+        })
+        .map(String::from)
+        .collect()
+}
+
+/// A parsed endpoint signature, e.g. `POST /users (body: User) -> 201 User`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointSignature {
+    /// Uppercased HTTP method
+    pub method: String,
+    pub path: String,
+    /// Request body type, from the parenthesized `(body: Type)` section
+    pub body: Option<String>,
+    /// Status code after `->`, if given
+    pub status: Option<u16>,
+    /// Response type after `->` (and the status, if any)
+    pub response: Option<String>,
+}
+
+/// Parse an endpoint signature of the form
+/// `METHOD /path [(body: Type)] [-> [status] Type]`.
+///
+/// Returns `None` when the string does not start with a method and an
+/// absolute path, so callers can fall back to field-list parsing.
+pub fn parse_endpoint_signature(sig: &str) -> Option<EndpointSignature> {
+    let sig = sig.trim();
+    let (head, response_part) = match sig.split_once("->") {
+        Some((head, response)) => (head.trim(), Some(response.trim())),
+        None => (sig, None),
+    };
+
+    let (method, rest) = head.split_once(char::is_whitespace)?;
+    if method.is_empty() || !method.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let rest = rest.trim();
+    let path_end = rest.find(['(', ' ']).unwrap_or(rest.len());
+    let path = rest[..path_end].trim();
+    if !path.starts_with('/') {
+        return None;
+    }
+
+    let body = rest[path_end..]
+        .trim()
+        .strip_prefix('(')
+        .and_then(|b| b.strip_suffix(')'))
+        .map(|b| match b.split_once(':') {
+            Some((_, ty)) => ty.trim().to_string(),
+            None => b.trim().to_string(),
+        })
+        .filter(|b| !b.is_empty());
+
+    let (status, response) = match response_part {
+        Some(r) => {
+            let (first, remainder) = r.split_once(char::is_whitespace).unwrap_or((r, ""));
+            match first.parse::<u16>() {
+                Ok(code) => (Some(code), remainder.trim()),
+                Err(_) => (None, r),
+            }
+        }
+        None => (None, ""),
+    };
+
+    Some(EndpointSignature {
+        method: method.to_ascii_uppercase(),
+        path: path.to_string(),
+        body,
+        status,
+        response: (!response.is_empty()).then(|| response.to_string()),
+    })
+}
+
+/// Canonical path for comparison: trailing slash dropped and path parameters
+/// (`{id}`, `:id`, `<id>`) collapsed to `{}`.
+fn normalize_endpoint_path(path: &str) -> String {
+    let segments: Vec<&str> = path
+        .trim_end_matches('/')
+        .split('/')
+        .map(|seg| {
+            let is_param = (seg.starts_with('{') && seg.ends_with('}'))
+                || (seg.starts_with('<') && seg.ends_with('>'))
+                || seg.starts_with(':');
+            if is_param {
+                "{}"
+            } else {
+                seg
+            }
+        })
+        .collect();
+    segments.join("/").to_lowercase()
+}
+
+/// Endpoint compatibility: method and path must match; body, status, and
+/// response must agree (after type normalization) wherever `a` specifies
+/// them. Falls back to [`signatures_compatible`] if either side does not
+/// parse as an endpoint.
+pub fn endpoints_compatible(a: &str, b: &str) -> bool {
+    let (Some(ea), Some(eb)) = (parse_endpoint_signature(a), parse_endpoint_signature(b)) else {
+        return signatures_compatible(a, b);
+    };
+
+    let types_agree = |mine: &Option<String>, theirs: &Option<String>| match (mine, theirs) {
+        (None, _) => true,
+        (Some(m), Some(t)) => normalize_type(m) == normalize_type(t),
+        (Some(_), None) => false,
+    };
+
+    ea.method == eb.method
+        && normalize_endpoint_path(&ea.path) == normalize_endpoint_path(&eb.path)
+        && types_agree(&ea.body, &eb.body)
+        && types_agree(&ea.response, &eb.response)
+        && (ea.status.is_none() || ea.status == eb.status)
+}
+
+/// Most signatures [`normalized_signature`] remembers per thread. The cache
+/// is emptied when full; a graph rarely holds this many distinct signatures.
+const SIGNATURE_CACHE_CAPACITY: usize = 1024;
+
+/// Parsed `(field, normalized type)` pairs, shared between cache and callers.
+type NormalizedFields = Rc<[(String, String)]>;
+
+thread_local! {
+    static SIGNATURE_CACHE: RefCell<HashMap<String, NormalizedFields>> =
+        RefCell::new(HashMap::new());
+}
+
+/// [`parse_signature`] with every type passed through [`normalize_type`],
+/// memoized on the raw string. `resolve` compares the same provider
+/// signatures over and over, so this is where the parsing time goes.
+fn normalized_signature(sig: &str) -> NormalizedFields {
+    SIGNATURE_CACHE.with(|cache| {
+        if let Some(fields) = cache.borrow().get(sig) {
+            return Rc::clone(fields);
+        }
+        let fields: NormalizedFields = parse_signature(sig)
+            .into_iter()
+            .map(|(field, type_str)| (field, normalize_type(&type_str)))
+            .collect();
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= SIGNATURE_CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(sig.to_string(), Rc::clone(&fields));
+        fields
+    })
+}
+
+/// Empty this thread's cache of normalized signatures. Results never go
+/// stale, so this only matters for measuring the uncached cost.
+pub fn clear_signature_cache() {
+    SIGNATURE_CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
 /// Check if signature b is compatible with signature a.
 ///
 /// Compatible if b's fields are a superset of a's fields with normalized types.
 /// Empty a is compatible with anything.
 pub fn signatures_compatible(a: &str, b: &str) -> bool {
-    let fields_a = parse_signature(a);
-    let fields_b = parse_signature(b);
-
+    let fields_a = normalized_signature(a);
     if fields_a.is_empty() {
         return true;
     }
+    let fields_b = normalized_signature(b);
+
+    fields_a.iter().all(|(field, type_a)| {
+        fields_b
+            .iter()
+            .find(|(f, _)| f == field)
+            .is_some_and(|(_, type_b)| type_a == type_b)
+    })
+}
+
+/// Field-level difference between an expected signature and a provided one;
+/// see [`signature_diff`]. Types are normalized with [`normalize_type`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SignatureDiff {
+    /// Expected fields the provider lacks, as (field, expected type)
+    pub missing: Vec<(String, String)>,
+    /// Fields on both sides whose types disagree
+    pub mismatched: Vec<TypeMismatch>,
+    /// Provided fields nobody asked for, as (field, provided type)
+    pub extra: Vec<(String, String)>,
+}
+
+impl SignatureDiff {
+    /// Same verdict as [`signatures_compatible`]: extra fields are fine.
+    pub fn is_compatible(&self) -> bool {
+        self.missing.is_empty() && self.mismatched.is_empty()
+    }
+}
 
-    for (field, type_a) in &fields_a {
+/// A field present in both signatures with different normalized types.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeMismatch {
+    pub field: String,
+    pub expected: String,
+    pub found: String,
+}
+
+/// Diff provided signature `b` against expected signature `a`.
+///
+/// The detailed counterpart of [`signatures_compatible`]: it reports which
+/// fields of `a` are missing from `b`, which disagree on type, and which
+/// fields `b` adds. Fields keep their order of appearance.
+pub fn signature_diff(a: &str, b: &str) -> SignatureDiff {
+    let fields_a = normalized_signature(a);
+    let fields_b = normalized_signature(b);
+    let mut diff = SignatureDiff::default();
+
+    for (field, expected) in fields_a.iter() {
         match fields_b.iter().find(|(f, _)| f == field) {
-            Some((_, type_b)) => {
-                if normalize_type(type_a) != normalize_type(type_b) {
-                    return false;
+            Some((_, found)) => {
+                if expected != found {
+                    diff.mismatched.push(TypeMismatch {
+                        field: field.clone(),
+                        expected: expected.clone(),
+                        found: found.clone(),
+                    });
                 }
             }
-            None => return false,
+            None => diff.missing.push((field.clone(), expected.clone())),
         }
     }
 
-    true
+    for (field, found) in fields_b.iter() {
+        if !fields_a.iter().any(|(f, _)| f == field) {
+            diff.extra.push((field.clone(), found.clone()));
+        }
+    }
+
+    diff
 }
 
 /// Normalize a constraint target for comparison.
@@ -221,6 +688,88 @@ pub fn normalize_constraint_target(target: &str) -> String {
     t.trim().to_string()
 }
 
+/// Normalize constraint requirement text for comparison.
+///
+/// Lowercase, collapse whitespace, strip trailing punctuation.
+pub fn normalize_requirement(requirement: &str) -> String {
+    let t = requirement
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    t.trim_end_matches(['.', ',', ';', ':', '!', '?'])
+        .trim_end()
+        .to_string()
+}
+
+/// Split a requirement into its free-form prose and any embedded `field: type`
+/// fragments, with types passed through [`normalize_type`].
+///
+/// "must have author_id: UUID, title: String" yields prose "must have" and
+/// fields `[("author_id", "uuid"), ("title", "str")]`.
+pub fn parse_requirement_fields(requirement: &str) -> (String, Vec<(String, String)>) {
+    let mut prose = Vec::new();
+    let mut fields = Vec::new();
+
+    for piece in requirement.split(',') {
+        let Some((before, after)) = piece.split_once(':') else {
+            prose.push(piece.to_string());
+            continue;
+        };
+        let mut words: Vec<&str> = before.split_whitespace().collect();
+        let mut rest = after.split_whitespace();
+        match (words.pop(), rest.next()) {
+            (Some(field), Some(type_str)) => {
+                fields.push((field.to_lowercase(), normalize_type(type_str)));
+                prose.push(words.join(" "));
+                prose.push(rest.collect::<Vec<_>>().join(" "));
+            }
+            _ => prose.push(piece.to_string()),
+        }
+    }
+
+    (normalize_requirement(&prose.join(" ")), fields)
+}
+
+/// Check if two constraint requirements can both be honored.
+///
+/// When both embed `field: type` fragments, they are compared structurally:
+/// the prose around them must match and each field must have the same
+/// normalized type, so "author_id: UUID" and "author_id: uuid.UUID" agree.
+/// Otherwise compatible if the normalized texts are equal, or one refines the
+/// other (the shorter is a prefix of the longer, ending on a word boundary —
+/// e.g. "must have email" vs "must have email: str").
+pub fn requirements_compatible(a: &str, b: &str) -> bool {
+    let (prose_a, mut fields_a) = parse_requirement_fields(a);
+    let (prose_b, mut fields_b) = parse_requirement_fields(b);
+    if !fields_a.is_empty() && !fields_b.is_empty() {
+        fields_a.sort();
+        fields_b.sort();
+        return prose_a == prose_b && fields_a == fields_b;
+    }
+
+    let na = normalize_requirement(a);
+    let nb = normalize_requirement(b);
+
+    if na == nb {
+        return true;
+    }
+
+    let (shorter, longer) = if na.len() <= nb.len() {
+        (&na, &nb)
+    } else {
+        (&nb, &na)
+    };
+    if shorter.is_empty() {
+        return false;
+    }
+    longer.starts_with(shorter.as_str())
+        && longer[shorter.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| !c.is_alphanumeric() && c != '_')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,6 +784,20 @@ mod tests {
         assert_eq!(normalize_name("DatabaseInterface"), "database");
     }
 
+    #[test]
+    fn test_normalize_name_stacked_suffixes() {
+        assert_eq!(normalize_name("UserModelService"), "user");
+        assert_eq!(normalize_name("AbstractUserServiceImpl"), "abstract user");
+        assert_eq!(normalize_name("RecipeHandlerInterface"), "recipe");
+    }
+
+    #[test]
+    fn test_normalize_name_suffix_only() {
+        // A name made only of suffixes keeps its last remaining part
+        assert_eq!(normalize_name("ModelService"), "model");
+        assert_eq!(normalize_name("Service"), "service");
+    }
+
     #[test]
     fn test_normalize_name_camel_case() {
         assert_eq!(normalize_name("UserProfile"), "user profile");
@@ -247,6 +810,41 @@ mod tests {
         assert_eq!(normalize_name("user"), "user");
     }
 
+    #[test]
+    fn test_normalize_name_singularizes() {
+        assert_eq!(normalize_name("Users"), "user");
+        assert_eq!(normalize_name("Categories"), "category");
+        assert_eq!(normalize_name("UserAddresses"), "user address");
+        assert_eq!(normalize_name("Boxes"), "box");
+        assert_eq!(normalize_name("OrderBatches"), "order batch");
+        assert_eq!(normalize_name("OrdersService"), "order");
+    }
+
+    #[test]
+    fn test_singularize_keeps_e_of_ches_words() {
+        assert_eq!(singularize("caches"), "cache");
+        assert_eq!(singularize("niches"), "niche");
+        assert_eq!(singularize("matches"), "match");
+    }
+
+    #[test]
+    fn test_normalize_name_leaves_singulars_alone() {
+        assert_eq!(normalize_name("Status"), "status");
+        assert_eq!(normalize_name("OrderStatus"), "order status");
+        assert_eq!(normalize_name("Address"), "address");
+        assert_eq!(normalize_name("Analysis"), "analysis");
+        assert_eq!(normalize_name("TimeSeries"), "time series");
+        assert_eq!(normalize_name("Bus"), "bus");
+        assert_eq!(normalize_name("Ties"), "tie");
+    }
+
+    #[test]
+    fn test_names_overlap_plural_singular() {
+        assert!(names_overlap("Users", "User"));
+        assert!(names_overlap("Categories", "Category"));
+        assert!(names_overlap("CategoryModel", "Categories"));
+    }
+
     #[test]
     fn test_normalize_name_empty() {
         assert_eq!(normalize_name(""), "");
@@ -263,6 +861,23 @@ mod tests {
         assert!(names_overlap("User", "UserProfile"));
     }
 
+    #[test]
+    fn test_names_overlap_containment_needs_whole_tokens() {
+        // "id" is too short and only a raw substring of "grid"
+        assert!(!names_overlap("Id", "Grid"));
+        assert!(!names_overlap("Id", "Valid"));
+        assert!(!names_overlap("User", "Superuser"));
+        assert!(names_overlap("User", "PowerUserProfile"));
+        // Short names still match exactly or by prefix
+        assert!(names_overlap("Id", "Ids"));
+        assert!(names_overlap("Id", "IdToken"));
+
+        assert!(contains_tokens("user profile", "user", MIN_CONTAINMENT_LEN));
+        assert!(!contains_tokens("grid", "id", 0));
+        assert!(!contains_tokens("order id", "id", MIN_CONTAINMENT_LEN));
+        assert!(contains_tokens("order id", "id", 2));
+    }
+
     #[test]
     fn test_names_overlap_no_match() {
         assert!(!names_overlap("User", "Recipe"));
@@ -290,6 +905,92 @@ mod tests {
         assert_eq!(normalize_type("str | None"), "str");
     }
 
+    #[test]
+    fn test_normalize_type_qualified() {
+        assert_eq!(normalize_type("models.User"), normalize_type("User"));
+        assert_eq!(normalize_type(" User "), normalize_type("User"));
+        assert_eq!(normalize_type("crate::models::User"), "user");
+        assert_eq!(normalize_type("uuid.UUID"), "uuid");
+        assert_eq!(normalize_type("typing.Optional[str]"), "str");
+        assert_eq!(normalize_type("typing.Optional[ models.User ]"), "user");
+        assert_eq!(normalize_type("typing.List[str]"), "list[str]");
+        assert_eq!(
+            normalize_type("std::vec::Vec<std::string::String>"),
+            "list[str]"
+        );
+        assert_eq!(normalize_type("models.User | None"), "user");
+    }
+
+    #[test]
+    fn test_normalize_type_rust_collections() {
+        for bytes in ["Vec<u8>", "&[u8]", "[u8; 32]", "bytes", "&mut [u8]"] {
+            assert_eq!(normalize_type(bytes), "bytes", "{bytes}");
+        }
+        assert_eq!(normalize_type("&[String]"), "list[str]");
+        assert_eq!(normalize_type("[i64; 4]"), "list[int]");
+        assert_eq!(normalize_type("Box<User>"), "user");
+        assert_eq!(normalize_type("Arc<Vec<i64>>"), "list[int]");
+        assert_eq!(normalize_type("std::rc::Rc<str>"), "str");
+        assert_eq!(normalize_type("&str"), "str");
+
+        // Rust byte buffers and slices line up with their Python spellings
+        assert!(signatures_compatible("data: bytes", "data: Vec<u8>"));
+        assert!(signatures_compatible("ids: list[int]", "ids: &[i64]"));
+        assert!(!signatures_compatible("ids: list[int]", "ids: &[u8]"));
+    }
+
+    #[test]
+    fn test_parse_endpoint_signature() {
+        let ep = parse_endpoint_signature("POST /users (body: User) -> 201 User").unwrap();
+        assert_eq!(ep.method, "POST");
+        assert_eq!(ep.path, "/users");
+        assert_eq!(ep.body.as_deref(), Some("User"));
+        assert_eq!(ep.status, Some(201));
+        assert_eq!(ep.response.as_deref(), Some("User"));
+
+        let ep = parse_endpoint_signature("get /users/{id} -> User").unwrap();
+        assert_eq!(ep.method, "GET");
+        assert_eq!(ep.body, None);
+        assert_eq!(ep.status, None);
+        assert_eq!(ep.response.as_deref(), Some("User"));
+
+        assert!(parse_endpoint_signature("id: UUID, email: str").is_none());
+    }
+
+    #[test]
+    fn test_endpoints_compatible() {
+        let sig = "POST /users (body: User) -> 201 User";
+        assert!(endpoints_compatible(
+            sig,
+            "POST /users/ (body: models.User) -> 201 User"
+        ));
+        assert!(endpoints_compatible("POST /users", sig));
+        assert!(endpoints_compatible(
+            "GET /users/{id} -> User",
+            "GET /users/:user_id -> User"
+        ));
+        assert!(!endpoints_compatible(
+            sig,
+            "PUT /users (body: User) -> 201 User"
+        ));
+        assert!(!endpoints_compatible(
+            sig,
+            "POST /accounts (body: User) -> 201 User"
+        ));
+    }
+
+    #[test]
+    fn test_endpoints_differing_response_incompatible() {
+        assert!(!endpoints_compatible(
+            "POST /users (body: User) -> 201 User",
+            "POST /users (body: User) -> 201 Account"
+        ));
+        assert!(!endpoints_compatible(
+            "POST /users (body: User) -> 201 User",
+            "POST /users (body: User) -> 200 User"
+        ));
+    }
+
     #[test]
     fn test_normalize_type_containers() {
         assert_eq!(normalize_type("Vec<String>"), "list[str]");
@@ -323,6 +1024,75 @@ mod tests {
         assert!(!signatures_compatible("id: UUID", "id: int"));
     }
 
+    #[test]
+    fn test_unparsed_signature_parts() {
+        assert!(unparsed_signature_parts("id: UUID, email: str").is_empty());
+        assert!(unparsed_signature_parts("").is_empty());
+        assert_eq!(
+            unparsed_signature_parts("id UUID, : str, name:"),
+            vec!["id UUID", ": str", "name:"]
+        );
+        assert_eq!(
+            unparsed_signature_parts("scores: Dict[str, int]"),
+            vec!["int]"]
+        );
+    }
+
+    #[test]
+    fn test_signature_diff_missing_field() {
+        let diff = signature_diff("id: UUID, email: str", "id: uuid, name: str");
+        assert_eq!(diff.missing, vec![("email".to_string(), "str".to_string())]);
+        assert!(diff.mismatched.is_empty());
+        assert_eq!(diff.extra, vec![("name".to_string(), "str".to_string())]);
+        assert!(!diff.is_compatible());
+    }
+
+    #[test]
+    fn test_signature_diff_type_mismatch() {
+        let diff = signature_diff("id: UUID, tags: List[str]", "id: int, tags: list[string]");
+        assert_eq!(
+            diff.mismatched,
+            vec![TypeMismatch {
+                field: "id".to_string(),
+                expected: "uuid".to_string(),
+                found: "int".to_string(),
+            }]
+        );
+        assert!(diff.missing.is_empty());
+        assert!(diff.extra.is_empty());
+        assert!(!diff.is_compatible());
+    }
+
+    #[test]
+    fn test_signature_diff_agrees_with_signatures_compatible() {
+        for (a, b) in [
+            ("id: UUID", "id: UUID, email: str"),
+            ("id: UUID, email: str", "id: UUID"),
+            ("", "id: int"),
+            ("id: Optional[int]", "id: int | None"),
+        ] {
+            assert_eq!(
+                signature_diff(a, b).is_compatible(),
+                signatures_compatible(a, b)
+            );
+        }
+    }
+
+    #[test]
+    fn test_signature_cache_is_bounded_and_transparent() {
+        clear_signature_cache();
+        assert!(signatures_compatible("id: UUID", "id: uuid, email: str"));
+        assert!(signatures_compatible("id: UUID", "id: uuid, email: str"));
+        assert!(!signatures_compatible("id: int", "id: uuid, email: str"));
+
+        for i in 0..SIGNATURE_CACHE_CAPACITY + 10 {
+            signatures_compatible(&format!("f{i}: int"), "f0: int");
+        }
+        let cached = SIGNATURE_CACHE.with(|cache| cache.borrow().len());
+        assert!(cached <= SIGNATURE_CACHE_CAPACITY);
+        assert!(signatures_compatible("f0: int", "f0: int, g: str"));
+    }
+
     #[test]
     fn test_signatures_compatible_empty() {
         assert!(signatures_compatible("", "id: UUID"));
@@ -350,4 +1120,164 @@ mod tests {
     fn test_normalize_constraint_target_empty() {
         assert_eq!(normalize_constraint_target(""), "");
     }
+
+    #[test]
+    fn test_normalize_requirement() {
+        assert_eq!(
+            normalize_requirement("  Must   have EMAIL. "),
+            "must have email"
+        );
+        assert_eq!(normalize_requirement("must have email;"), "must have email");
+    }
+
+    #[test]
+    fn test_requirements_compatible_trivial_differences() {
+        assert!(requirements_compatible(
+            "must have email",
+            "Must have  email."
+        ));
+        assert!(requirements_compatible(
+            "must have email",
+            "must have email: str"
+        ));
+    }
+
+    #[test]
+    fn test_parse_requirement_fields() {
+        let (prose, fields) = parse_requirement_fields("Must have author_id: UUID, title: String");
+        assert_eq!(prose, "must have");
+        assert_eq!(
+            fields,
+            vec![
+                ("author_id".to_string(), "uuid".to_string()),
+                ("title".to_string(), "str".to_string())
+            ]
+        );
+
+        let (prose, fields) = parse_requirement_fields("must be unique");
+        assert_eq!(prose, "must be unique");
+        assert!(fields.is_empty());
+    }
+
+    #[test]
+    fn test_requirements_compatible_type_aliases() {
+        assert!(requirements_compatible(
+            "must have author_id: UUID",
+            "must have author_id: uuid.UUID"
+        ));
+        assert!(requirements_compatible(
+            "must have title: String, count: i64",
+            "must have count: int, title: str"
+        ));
+        assert!(!requirements_compatible(
+            "must have author_id: UUID",
+            "must have author_id: int"
+        ));
+        assert!(!requirements_compatible(
+            "must have author_id: UUID",
+            "must drop author_id: UUID"
+        ));
+    }
+
+    #[test]
+    fn test_requirements_incompatible() {
+        assert!(!requirements_compatible(
+            "must use UUID primary keys",
+            "must use integer primary keys"
+        ));
+        assert!(!requirements_compatible(
+            "must have email",
+            "must have emails"
+        ));
+        assert!(!requirements_compatible("", "must have email"));
+    }
+
+    #[test]
+    fn test_name_overlap_strength() {
+        assert!((name_overlap_strength("UserModel", "User") - 1.0).abs() < f64::EPSILON);
+        // "user" inside "user profile"
+        assert!((name_overlap_strength("User", "UserProfile") - 4.0 / 12.0).abs() < 1e-9);
+        assert_eq!(name_overlap_strength("User", "Recipe"), 0.0);
+    }
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_names_overlap_with_modes() {
+        use NameMatchMode::*;
+        // (a, b, [Exact, Prefix, Substring, Fuzzy])
+        let cases = [
+            ("UserModel", "User", [true, true, true, true]),
+            ("User", "UserProfile", [false, true, true, true]),
+            ("User", "PowerUser", [false, false, true, true]),
+            ("Category", "Catgory", [false, false, false, true]),
+            ("User", "Order", [false, false, false, false]),
+        ];
+        for (a, b, expected) in cases {
+            for (mode, want) in [Exact, Prefix, Substring, Fuzzy].into_iter().zip(expected) {
+                assert_eq!(
+                    names_overlap_with(a, b, mode),
+                    want,
+                    "{} vs {} {:?}",
+                    a,
+                    b,
+                    mode
+                );
+            }
+            assert_eq!(names_overlap(a, b), names_overlap_with(a, b, Substring));
+        }
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_module_affinity() {
+        assert_eq!(
+            module_affinity("app/auth", "app/auth"),
+            ModuleAffinity::Same
+        );
+        assert_eq!(
+            module_affinity("app/auth", "app.auth.models"),
+            ModuleAffinity::Same
+        );
+        assert_eq!(
+            module_affinity("app::auth", "App/Auth/views.py"),
+            ModuleAffinity::Same
+        );
+        assert_eq!(
+            module_affinity("app/auth", "app/billing"),
+            ModuleAffinity::Neutral
+        );
+        assert_eq!(
+            module_affinity("app/auth", "lib/billing"),
+            ModuleAffinity::Distant
+        );
+        assert_eq!(module_affinity("", "lib/billing"), ModuleAffinity::Neutral);
+        assert_eq!(module_affinity("./", "app"), ModuleAffinity::Neutral);
+    }
+
+    #[test]
+    fn test_tag_similarity() {
+        let a = tags(&["user", "auth", "model"]);
+        assert!((tag_similarity(&a, &a) - 1.0).abs() < f64::EPSILON);
+        assert_eq!(tag_similarity(&a, &tags(&["recipe", "meal"])), 0.0);
+        // 2 shared out of 4 distinct
+        let partial = tag_similarity(&a, &tags(&["user", "auth", "profile"]));
+        assert!((partial - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_tag_similarity_empty_and_duplicates() {
+        assert_eq!(tag_similarity(&[], &[]), 0.0);
+        assert_eq!(tag_similarity(&tags(&["user"]), &[]), 0.0);
+        let dup = tag_similarity(&tags(&["user", "user"]), &tags(&["user"]));
+        assert!((dup - 1.0).abs() < f64::EPSILON);
+    }
 }
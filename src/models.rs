@@ -2,51 +2,101 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::matching;
+use crate::clock::{Clock, SystemClock};
+use crate::matching::{self, NameMatchMode};
 
 /// A single unit of semantic intent in the shared graph.
 /// Published by agents as they make architectural decisions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntentNode {
+    /// Empty when deserialized without one; the graph assigns an id from
+    /// its [`IdGenerator`](crate::ids::IdGenerator) on publish.
+    #[serde(default)]
     pub id: String,
     pub agent_id: String,
+    #[serde(default = "Utc::now")]
     pub timestamp: DateTime<Utc>,
 
     /// Human-readable description of the decision
     pub intent: String,
 
     /// Interfaces this decision provides to other scopes
+    #[serde(default)]
     pub provides: Vec<InterfaceSpec>,
 
     /// Interfaces this decision requires from other scopes
+    #[serde(default)]
     pub requires: Vec<InterfaceSpec>,
 
     /// Constraints this decision imposes on other agents
+    #[serde(default)]
     pub constraints: Vec<Constraint>,
 
-    /// Confidence that this decision is final (0.0 = exploring, 1.0 = committed)
+    /// Confidence that this decision is final (0.0 = exploring, 1.0 = committed),
+    /// as declared by the author. Informational only: overlap filtering and
+    /// resolution rank intents by the stability computed from their evidence,
+    /// so an agent can't win a conflict by declaring itself certain.
+    #[serde(default = "default_stability")]
     pub stability: f64,
 
     /// Evidence supporting the stability score
+    #[serde(default)]
     pub evidence: Vec<Evidence>,
 
     /// Parent intent ID (if this refines a previous intent)
+    #[serde(default)]
     pub parent_id: Option<String>,
+
+    /// Workflow labels on the intent itself (e.g. "sprint-12",
+    /// "needs-review"), as opposed to tags on its interfaces. Not used in
+    /// matching.
+    #[serde(default)]
+    pub labels: Vec<String>,
+
+    /// Stability the graph stored for this intent, when it was read from
+    /// one; `None` for intents built in memory. Not serialized: it is the
+    /// graph's score, not part of the decision.
+    #[serde(skip)]
+    pub computed_stability: Option<f64>,
+}
+
+fn new_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Declared stability of a new intent that doesn't state one: exploring.
+pub const DEFAULT_STABILITY: f64 = 0.3;
+
+fn default_stability() -> f64 {
+    DEFAULT_STABILITY
 }
 
 impl IntentNode {
     pub fn new(agent_id: &str, intent: &str) -> Self {
+        Self::new_with_clock(agent_id, intent, &SystemClock)
+    }
+
+    /// Like [`new`](Self::new), but starting at `default` instead of
+    /// [`DEFAULT_STABILITY`], for teams whose agents start less (or more) committed.
+    pub fn new_with_default_stability(agent_id: &str, intent: &str, default: f64) -> Self {
+        Self::new(agent_id, intent).with_stability(default)
+    }
+
+    /// Like [`new`](Self::new), but timestamped by the given clock.
+    pub fn new_with_clock(agent_id: &str, intent: &str, clock: &dyn Clock) -> Self {
         Self {
-            id: Uuid::new_v4().to_string(),
+            id: new_id(),
             agent_id: agent_id.to_string(),
-            timestamp: Utc::now(),
+            timestamp: clock.now(),
             intent: intent.to_string(),
             provides: Vec::new(),
             requires: Vec::new(),
             constraints: Vec::new(),
-            stability: 0.3, // Default: exploring
+            stability: default_stability(), // Default: exploring
             evidence: Vec::new(),
             parent_id: None,
+            labels: Vec::new(),
+            computed_stability: None,
         }
     }
 
@@ -79,6 +129,18 @@ impl IntentNode {
         self.parent_id = Some(parent_id.to_string());
         self
     }
+
+    pub fn with_labels(mut self, labels: Vec<&str>) -> Self {
+        self.labels = labels.into_iter().map(String::from).collect();
+        self
+    }
+
+    /// Stamp the intent with a past (or otherwise explicit) time, for
+    /// backfilling historical decisions in their real order.
+    pub fn with_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
 }
 
 /// A typed interface that an agent provides or requires.
@@ -95,10 +157,20 @@ pub struct InterfaceSpec {
     pub signature: String,
 
     /// Module/file path where this interface lives
+    #[serde(default)]
     pub module_path: String,
 
     /// Semantic tags for fuzzy matching (e.g., ["crud", "recipe", "create"])
+    #[serde(default)]
     pub tags: Vec<String>,
+
+    /// Still provided, but consumers should move off it
+    #[serde(default)]
+    pub deprecated: bool,
+
+    /// Name of the interface that supersedes this one, if any
+    #[serde(default)]
+    pub replaced_by: Option<String>,
 }
 
 impl InterfaceSpec {
@@ -109,6 +181,8 @@ impl InterfaceSpec {
             signature: signature.to_string(),
             module_path: String::new(),
             tags: Vec::new(),
+            deprecated: false,
+            replaced_by: None,
         }
     }
 
@@ -122,20 +196,138 @@ impl InterfaceSpec {
         self
     }
 
+    /// Mark this interface deprecated, optionally naming its replacement.
+    pub fn with_deprecated(mut self, replaced_by: Option<&str>) -> Self {
+        self.deprecated = true;
+        self.replaced_by = replaced_by.map(String::from);
+        self
+    }
+
+    /// Human-readable migration target for a deprecated interface.
+    pub fn migration_hint(&self) -> String {
+        match &self.replaced_by {
+            Some(replacement) => format!("migrate to '{}'", replacement),
+            None => "no replacement declared".to_string(),
+        }
+    }
+
     /// Structural overlap: name overlap or shared tags
     pub fn structurally_overlaps(&self, other: &InterfaceSpec) -> bool {
-        if matching::names_overlap(&self.name, &other.name) {
-            return true;
-        }
-        // Check tag overlap — at least 2 shared tags indicates likely overlap
+        self.structurally_overlaps_with(other, NameMatchMode::default())
+    }
+
+    /// [`structurally_overlaps`](Self::structurally_overlaps) with names
+    /// matched at the given precision.
+    pub fn structurally_overlaps_with(&self, other: &InterfaceSpec, mode: NameMatchMode) -> bool {
+        self.names_overlap_with(other, mode) || self.tags_overlap(other)
+    }
+
+    /// Name overlap, weighed by module paths: a weak (prefix or containment)
+    /// name match between [`Distant`](matching::ModuleAffinity::Distant)
+    /// modules doesn't count. Equal normalized names always do.
+    pub fn names_overlap(&self, other: &InterfaceSpec) -> bool {
+        self.names_overlap_with(other, NameMatchMode::default())
+    }
+
+    /// [`names_overlap`](Self::names_overlap) at the given precision.
+    pub fn names_overlap_with(&self, other: &InterfaceSpec, mode: NameMatchMode) -> bool {
+        let strength = matching::name_overlap_strength_with(&self.name, &other.name, mode);
+        strength >= 1.0
+            || (strength > 0.0
+                && matching::module_affinity(&self.module_path, &other.module_path)
+                    != matching::ModuleAffinity::Distant)
+    }
+
+    /// Tag overlap: at least 2 shared tags, or a single shared tag between
+    /// interfaces in the [`Same`](matching::ModuleAffinity::Same) module.
+    fn tags_overlap(&self, other: &InterfaceSpec) -> bool {
         let shared_tags = self.tags.iter().filter(|t| other.tags.contains(t)).count();
         shared_tags >= 2
+            || (shared_tags == 1
+                && matching::module_affinity(&self.module_path, &other.module_path)
+                    == matching::ModuleAffinity::Same)
+    }
+
+    /// Explain why this spec structurally overlaps another, if it does.
+    ///
+    /// Name overlap takes precedence over tag overlap, mirroring
+    /// [`structurally_overlaps`](Self::structurally_overlaps).
+    pub fn explain_overlap(&self, other: &InterfaceSpec) -> Option<OverlapReason> {
+        self.explain_overlap_with(other, NameMatchMode::default())
+    }
+
+    /// [`explain_overlap`](Self::explain_overlap) with names matched at the
+    /// given precision.
+    pub fn explain_overlap_with(
+        &self,
+        other: &InterfaceSpec,
+        mode: NameMatchMode,
+    ) -> Option<OverlapReason> {
+        if self.names_overlap_with(other, mode) {
+            return Some(OverlapReason {
+                my_spec: self.name.clone(),
+                their_spec: other.name.clone(),
+                kind: OverlapKind::Name,
+                strength: matching::name_overlap_strength_with(&self.name, &other.name, mode),
+            });
+        }
+        if self.tags_overlap(other) {
+            return Some(OverlapReason {
+                my_spec: self.name.clone(),
+                their_spec: other.name.clone(),
+                kind: OverlapKind::Tags,
+                strength: self.tag_similarity(other),
+            });
+        }
+        None
     }
 
-    /// Signature compatibility: superset check with type normalization
+    /// Jaccard similarity of the two specs' tag sets, 0.0 when both are empty
+    pub fn tag_similarity(&self, other: &InterfaceSpec) -> f64 {
+        matching::tag_similarity(&self.tags, &other.tags)
+    }
+
+    /// Signature compatibility: superset check with type normalization.
+    /// Endpoints compare method, path, and body/response types instead.
     pub fn signature_compatible(&self, other: &InterfaceSpec) -> bool {
+        if self.kind == InterfaceKind::Endpoint && other.kind == InterfaceKind::Endpoint {
+            return matching::endpoints_compatible(&self.signature, &other.signature);
+        }
         matching::signatures_compatible(&self.signature, &other.signature)
     }
+
+    /// Whether this requirement is met by `provision`'s signature: the
+    /// provider must offer at least every field asked for. Extra provided
+    /// fields are fine; the reverse is not.
+    pub fn is_satisfied_by(&self, provision: &InterfaceSpec) -> bool {
+        self.signature_compatible(provision)
+    }
+
+    /// Whether this provision meets `requirement`; the same check as
+    /// [`is_satisfied_by`](Self::is_satisfied_by), read from the provider's side.
+    pub fn satisfies(&self, requirement: &InterfaceSpec) -> bool {
+        requirement.is_satisfied_by(self)
+    }
+}
+
+/// Why two interface specs were judged to structurally overlap.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OverlapReason {
+    /// Name of the queried spec
+    pub my_spec: String,
+    /// Name of the matching spec on the other intent
+    pub their_spec: String,
+    pub kind: OverlapKind,
+    /// Match strength in [0.0, 1.0] (name length ratio, or tag Jaccard)
+    pub strength: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum OverlapKind {
+    /// Normalized names are equal, prefixed, or contained
+    Name,
+    /// At least two shared tags, or one between interfaces in the same module
+    Tags,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -148,8 +340,61 @@ pub enum InterfaceKind {
     Config,
 }
 
+impl InterfaceKind {
+    /// Lowercase name used in the interface index and the Python dict format.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InterfaceKind::Function => "function",
+            InterfaceKind::Class => "class",
+            InterfaceKind::Model => "model",
+            InterfaceKind::Endpoint => "endpoint",
+            InterfaceKind::Migration => "migration",
+            InterfaceKind::Config => "config",
+        }
+    }
+
+    /// Parse the lowercase name produced by [`as_str`](Self::as_str).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "function" => Some(InterfaceKind::Function),
+            "class" => Some(InterfaceKind::Class),
+            "model" => Some(InterfaceKind::Model),
+            "endpoint" => Some(InterfaceKind::Endpoint),
+            "migration" => Some(InterfaceKind::Migration),
+            "config" => Some(InterfaceKind::Config),
+            _ => None,
+        }
+    }
+}
+
+/// Which list of an intent an interface spec sits in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Provides,
+    Requires,
+}
+
+impl Role {
+    /// Name used in the interface index and the Python API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Provides => "provides",
+            Role::Requires => "requires",
+        }
+    }
+
+    /// Parse the name produced by [`as_str`](Self::as_str).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "provides" => Some(Role::Provides),
+            "requires" => Some(Role::Requires),
+            _ => None,
+        }
+    }
+}
+
 /// A constraint that an agent's decision imposes on other scopes.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Constraint {
     /// What this constraint affects (e.g., "User model", "database schema")
     pub target: String,
@@ -158,10 +403,22 @@ pub struct Constraint {
     pub requirement: String,
 
     /// How severe is this constraint
+    #[serde(default)]
     pub severity: ConstraintSeverity,
 
     /// Tags for matching which agents this affects
+    #[serde(default)]
     pub affects_tags: Vec<String>,
+
+    /// Bind every intent providing or requiring an interface of this kind,
+    /// regardless of tags (e.g., "all Migrations must be reversible")
+    #[serde(default)]
+    pub applies_to_kind: Option<InterfaceKind>,
+
+    /// Only bind intents from these agents (e.g., the billing team's); empty
+    /// means every agent. Narrows tag and kind matching, never widens it.
+    #[serde(default)]
+    pub applies_to_agents: Vec<String>,
 }
 
 impl Constraint {
@@ -171,6 +428,8 @@ impl Constraint {
             requirement: requirement.to_string(),
             severity: ConstraintSeverity::Required,
             affects_tags: Vec::new(),
+            applies_to_kind: None,
+            applies_to_agents: Vec::new(),
         }
     }
 
@@ -184,8 +443,43 @@ impl Constraint {
         self
     }
 
-    /// Check if this constraint applies to a given intent based on tag overlap
+    pub fn with_kind(mut self, kind: InterfaceKind) -> Self {
+        self.applies_to_kind = Some(kind);
+        self
+    }
+
+    pub fn with_agents(mut self, agents: Vec<&str>) -> Self {
+        self.applies_to_agents = agents.into_iter().map(String::from).collect();
+        self
+    }
+
+    /// Check if this constraint applies to a given intent: the intent's agent
+    /// must be in scope (see [`binds_agent`](Self::binds_agent)) and its
+    /// interfaces must match (see [`matches_interfaces`](Self::matches_interfaces))
     pub fn applies_to(&self, intent: &IntentNode) -> bool {
+        self.binds_agent(&intent.agent_id) && self.matches_interfaces(intent)
+    }
+
+    /// Whether `agent_id` is in [`applies_to_agents`](Self::applies_to_agents),
+    /// or that list is empty. Compares ids exactly.
+    pub fn binds_agent(&self, agent_id: &str) -> bool {
+        self.applies_to_agents.is_empty() || self.applies_to_agents.iter().any(|a| a == agent_id)
+    }
+
+    /// Tag overlap or, for kind-targeted constraints, the kinds of the
+    /// intent's interfaces; ignores agent scoping
+    pub fn matches_interfaces(&self, intent: &IntentNode) -> bool {
+        if let Some(kind) = &self.applies_to_kind {
+            if intent
+                .provides
+                .iter()
+                .chain(intent.requires.iter())
+                .any(|spec| &spec.kind == kind)
+            {
+                return true;
+            }
+        }
+
         let all_intent_tags: Vec<&str> = intent
             .provides
             .iter()
@@ -198,30 +492,131 @@ impl Constraint {
             .any(|t| all_intent_tags.contains(&t.as_str()))
     }
 
-    /// Check if two constraints conflict (normalized target comparison)
+    /// Check if two constraints conflict (normalized target comparison, and
+    /// requirements that neither match nor refine one another)
     pub fn conflicts_with(&self, other: &Constraint) -> bool {
         matching::normalize_constraint_target(&self.target)
             == matching::normalize_constraint_target(&other.target)
-            && self.requirement != other.requirement
+            && !matching::requirements_compatible(&self.requirement, &other.requirement)
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub enum ConstraintSeverity {
     /// Nice to have — other agents should consider but can ignore
     Preferred,
     /// Must comply — violating this will cause integration failures
+    #[default]
     Required,
     /// Critical — violating this will cause data loss or security issues
     Critical,
 }
 
+impl ConstraintSeverity {
+    /// Lowercase name used in the Python dict format.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConstraintSeverity::Preferred => "preferred",
+            ConstraintSeverity::Required => "required",
+            ConstraintSeverity::Critical => "critical",
+        }
+    }
+
+    /// Parse the lowercase name produced by [`as_str`](Self::as_str).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "preferred" => Some(ConstraintSeverity::Preferred),
+            "required" => Some(ConstraintSeverity::Required),
+            "critical" => Some(ConstraintSeverity::Critical),
+            _ => None,
+        }
+    }
+}
+
+/// Which constraint severities `resolve` turns into `AdoptConstraint`
+/// adjustments. Constraints below the bar are reported as notes instead.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum AdoptPolicy {
+    /// Adopt every applicable, non-conflicting constraint
+    #[default]
+    AdoptAll,
+    /// Adopt `Required` and `Critical` constraints; note `Preferred` ones
+    RequiredAndAbove,
+    /// Adopt only `Critical` constraints
+    CriticalOnly,
+}
+
+impl AdoptPolicy {
+    /// Whether a constraint of this severity should be adopted.
+    pub fn admits(&self, severity: &ConstraintSeverity) -> bool {
+        match self {
+            AdoptPolicy::AdoptAll => true,
+            AdoptPolicy::RequiredAndAbove => *severity != ConstraintSeverity::Preferred,
+            AdoptPolicy::CriticalOnly => *severity == ConstraintSeverity::Critical,
+        }
+    }
+
+    /// Lowercase name used in the Python API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdoptPolicy::AdoptAll => "adopt_all",
+            AdoptPolicy::RequiredAndAbove => "required_and_above",
+            AdoptPolicy::CriticalOnly => "critical_only",
+        }
+    }
+
+    /// Parse the lowercase name produced by [`as_str`](Self::as_str).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "adopt_all" => Some(AdoptPolicy::AdoptAll),
+            "required_and_above" => Some(AdoptPolicy::RequiredAndAbove),
+            "critical_only" => Some(AdoptPolicy::CriticalOnly),
+            _ => None,
+        }
+    }
+}
+
+/// How [`IntentGraph::query_by_tags`](crate::graph::IntentGraph::query_by_tags)
+/// combines several tags.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TagMatch {
+    /// The intent carries every requested tag
+    #[default]
+    All,
+    /// The intent carries at least one requested tag
+    Any,
+}
+
+impl TagMatch {
+    /// Lowercase name used in the Python API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TagMatch::All => "all",
+            TagMatch::Any => "any",
+        }
+    }
+
+    /// Parse the lowercase name produced by [`as_str`](Self::as_str).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "all" => Some(TagMatch::All),
+            "any" => Some(TagMatch::Any),
+            _ => None,
+        }
+    }
+}
+
 /// Evidence that supports or undermines an intent's stability score.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Evidence {
     pub kind: EvidenceKind,
     pub description: String,
+    #[serde(default = "Utc::now")]
     pub timestamp: DateTime<Utc>,
+    /// The agent this evidence came from, when one is known. Set by
+    /// [`consumed_by`](Self::consumed_by); the description stays for humans.
+    #[serde(default)]
+    pub source_agent: Option<String>,
 }
 
 impl Evidence {
@@ -230,6 +625,7 @@ impl Evidence {
             kind: EvidenceKind::TestPass,
             description: description.to_string(),
             timestamp: Utc::now(),
+            source_agent: None,
         }
     }
 
@@ -238,6 +634,7 @@ impl Evidence {
             kind: EvidenceKind::CodeCommitted,
             description: description.to_string(),
             timestamp: Utc::now(),
+            source_agent: None,
         }
     }
 
@@ -246,6 +643,7 @@ impl Evidence {
             kind: EvidenceKind::ConsumedByOther,
             description: format!("Consumed by agent {}", agent_id),
             timestamp: Utc::now(),
+            source_agent: Some(agent_id.trim().to_string()),
         }
     }
 
@@ -254,6 +652,7 @@ impl Evidence {
             kind: EvidenceKind::Conflict,
             description: description.to_string(),
             timestamp: Utc::now(),
+            source_agent: None,
         }
     }
 
@@ -262,8 +661,30 @@ impl Evidence {
             kind: EvidenceKind::ManualApproval,
             description: "Manually approved".to_string(),
             timestamp: Utc::now(),
+            source_agent: None,
         }
     }
+
+    /// Who consumed the intent, for `ConsumedByOther` evidence: the
+    /// [`source_agent`](Self::source_agent) when set, otherwise recovered from
+    /// the description of evidence recorded before that field existed (the
+    /// agent id from "Consumed by agent X", or the whole trimmed text).
+    /// `None` for other kinds.
+    pub fn consumer(&self) -> Option<&str> {
+        if self.kind != EvidenceKind::ConsumedByOther {
+            return None;
+        }
+        if let Some(agent) = self.source_agent.as_deref() {
+            return Some(agent);
+        }
+        let description = self.description.trim();
+        Some(
+            description
+                .strip_prefix("Consumed by agent ")
+                .map(str::trim)
+                .unwrap_or(description),
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -283,6 +704,15 @@ pub struct ResolutionResult {
     pub adjustments: Vec<Adjustment>,
     pub conflicts: Vec<ConflictReport>,
     pub adopted_constraints: Vec<Constraint>,
+    /// Constraints not adopted because they conflict with one of the
+    /// resolving intent's own, paired with the id of the intent imposing
+    /// each. Every one also has an entry in `conflicts`.
+    #[serde(default)]
+    pub rejected_constraints: Vec<(Constraint, String)>,
+    /// Informational findings that did not warrant an adjustment, such as
+    /// constraints skipped by the [`AdoptPolicy`].
+    #[serde(default)]
+    pub notes: Vec<String>,
 }
 
 impl ResolutionResult {
@@ -293,6 +723,40 @@ impl ResolutionResult {
     pub fn has_adjustments(&self) -> bool {
         !self.adjustments.is_empty()
     }
+
+    /// The tri-state callers usually branch on. Conflicts win over
+    /// adjustments: a result with both is [`Outcome::Conflicted`].
+    pub fn outcome(&self) -> Outcome {
+        if !self.conflicts.is_empty() {
+            Outcome::Conflicted
+        } else if self.has_adjustments() {
+            Outcome::AdjustmentsOnly
+        } else {
+            Outcome::Clean
+        }
+    }
+}
+
+/// Overall result of a resolution; see [`ResolutionResult::outcome`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Outcome {
+    /// No adjustments and no conflicts: publish as-is
+    Clean,
+    /// Adjustments to make, but nothing conflicting
+    AdjustmentsOnly,
+    /// At least one conflict, with or without adjustments
+    Conflicted,
+}
+
+impl Outcome {
+    /// Snake-case name used in the Python API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Clean => "clean",
+            Outcome::AdjustmentsOnly => "adjustments_only",
+            Outcome::Conflicted => "conflicted",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -300,6 +764,44 @@ pub struct Adjustment {
     pub kind: AdjustmentKind,
     pub description: String,
     pub source_intent_id: String,
+    /// Field-level breakdown for `AdaptSignature`; `None` for other kinds
+    #[serde(default)]
+    pub signature_diff: Option<matching::SignatureDiff>,
+    /// What to rewrite for `AdaptSignature`; `None` for other kinds
+    #[serde(default)]
+    pub adaptation: Option<SignatureAdaptation>,
+}
+
+/// The rewrite an `AdaptSignature` adjustment asks for: set the requirement
+/// named `interface_name` to `target_signature`, the provider's signature.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignatureAdaptation {
+    /// Name of the resolving intent's requirement
+    pub interface_name: String,
+    pub current_signature: String,
+    pub target_signature: String,
+    /// Required fields the provider lacks, as (field, required type)
+    pub missing_fields: Vec<(String, String)>,
+    /// Fields whose normalized types disagree
+    pub type_mismatches: Vec<matching::TypeMismatch>,
+}
+
+impl SignatureAdaptation {
+    /// Adapt `requirement` to `provision`, with the mismatches from `diff`
+    /// (as computed by [`matching::signature_diff`]).
+    pub fn new(
+        requirement: &InterfaceSpec,
+        provision: &InterfaceSpec,
+        diff: &matching::SignatureDiff,
+    ) -> Self {
+        Self {
+            interface_name: requirement.name.clone(),
+            current_signature: requirement.signature.clone(),
+            target_signature: provision.signature.clone(),
+            missing_fields: diff.missing.clone(),
+            type_mismatches: diff.mismatched.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -312,6 +814,10 @@ pub enum AdjustmentKind {
     YieldTo,
     /// Modify interface signature for compatibility
     AdaptSignature,
+    /// Move off a deprecated interface, to its replacement if one is declared
+    MigrateOff,
+    /// Require a provision that looks like a rename of an unprovided requirement
+    FollowRename,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -319,6 +825,163 @@ pub struct ConflictReport {
     pub my_intent_id: String,
     pub their_intent_id: String,
     pub description: String,
+    #[serde(default)]
+    pub my_stability: f64,
     pub their_stability: f64,
+    /// Which side the stability comparison favors
+    #[serde(default)]
+    pub winner: Winner,
     pub resolution_suggestion: String,
 }
+
+/// Outcome of comparing the two sides' stability in a [`ConflictReport`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum Winner {
+    /// The resolving intent is more stable
+    Me,
+    /// The other intent is more stable
+    Them,
+    /// Both scores are exactly equal
+    #[default]
+    Tie,
+}
+
+impl Winner {
+    pub fn from_scores(mine: f64, theirs: f64) -> Self {
+        if mine > theirs {
+            Winner::Me
+        } else if theirs > mine {
+            Winner::Them
+        } else {
+            Winner::Tie
+        }
+    }
+
+    /// Lowercase name used in the Python API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Winner::Me => "me",
+            Winner::Them => "them",
+            Winner::Tie => "tie",
+        }
+    }
+}
+
+/// Structural difference between two intents, e.g. a refinement and its
+/// parent. Produced by [`diff_intents`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct IntentDiff {
+    pub provides: SpecDiff,
+    pub requires: SpecDiff,
+    pub constraints: ConstraintDiff,
+}
+
+impl IntentDiff {
+    pub fn is_empty(&self) -> bool {
+        self.provides.is_empty() && self.requires.is_empty() && self.constraints.is_empty()
+    }
+}
+
+/// Interface specs added, removed, or changed between two intents.
+/// Specs are matched by normalized name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SpecDiff {
+    pub added: Vec<InterfaceSpec>,
+    pub removed: Vec<InterfaceSpec>,
+    pub changed: Vec<SpecChange>,
+}
+
+impl SpecDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A spec present on both sides with some field changed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpecChange {
+    pub before: InterfaceSpec,
+    pub after: InterfaceSpec,
+    /// Whether `after` still satisfies `before`'s signature
+    /// (see [`InterfaceSpec::signature_compatible`])
+    pub signature_compatible: bool,
+}
+
+/// Constraints added, removed, or changed between two intents.
+/// Constraints are matched by normalized target.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConstraintDiff {
+    pub added: Vec<Constraint>,
+    pub removed: Vec<Constraint>,
+    pub changed: Vec<(Constraint, Constraint)>,
+}
+
+impl ConstraintDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Diff `b` against `a`: "added" means present in `b` but not `a`.
+///
+/// Entries sharing a key are paired in order, so an intent declaring the
+/// same name twice diffs entry by entry.
+pub fn diff_intents(a: &IntentNode, b: &IntentNode) -> IntentDiff {
+    let spec_diff = |before: &[InterfaceSpec], after: &[InterfaceSpec]| {
+        let (added, removed, pairs) =
+            diff_by_key(before, after, |s| matching::normalize_name(&s.name));
+        SpecDiff {
+            added,
+            removed,
+            changed: pairs
+                .into_iter()
+                .map(|(before, after)| SpecChange {
+                    signature_compatible: before.signature_compatible(&after),
+                    before,
+                    after,
+                })
+                .collect(),
+        }
+    };
+
+    let (added, removed, changed) = diff_by_key(&a.constraints, &b.constraints, |c| {
+        matching::normalize_constraint_target(&c.target)
+    });
+
+    IntentDiff {
+        provides: spec_diff(&a.provides, &b.provides),
+        requires: spec_diff(&a.requires, &b.requires),
+        constraints: ConstraintDiff {
+            added,
+            removed,
+            changed,
+        },
+    }
+}
+
+/// Split two lists into (added, removed, changed pairs) by matching key.
+/// Pairs that compare equal are dropped.
+fn diff_by_key<T: Clone + PartialEq>(
+    before: &[T],
+    after: &[T],
+    key: impl Fn(&T) -> String,
+) -> (Vec<T>, Vec<T>, Vec<(T, T)>) {
+    let mut unmatched: Vec<Option<&T>> = before.iter().map(Some).collect();
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+
+    for new in after {
+        let k = key(new);
+        let slot = unmatched
+            .iter_mut()
+            .find(|slot| slot.is_some_and(|old| key(old) == k));
+        match slot.and_then(Option::take) {
+            Some(old) if old != new => changed.push((old.clone(), new.clone())),
+            Some(_) => {}
+            None => added.push(new.clone()),
+        }
+    }
+
+    let removed = unmatched.into_iter().flatten().cloned().collect();
+    (added, removed, changed)
+}
@@ -0,0 +1,2191 @@
+//! PyO3 bindings exposing the core as the `convergent._core` extension module.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use pyo3::prelude::*;
+use pyo3::types::{PyDateTime, PyDict, PyList};
+
+use crate::clock::{Clock, SystemClock};
+use crate::graph::{
+    ExportFilter, GraphSource, IntentGraph, OverlapOptions, PublishLimits, PublishRejected,
+    ResolveOptions,
+};
+use crate::ids::{IdGenerator, MonotonicGenerator, UuidGenerator};
+use crate::matching::NameMatchMode;
+use crate::models::*;
+use crate::stability::{Saturation, StabilityScorer};
+
+/// Default stability floor for `resolve`/`find_overlapping` when called from
+/// Python without an explicit `min_stability`. Resolving against 0.0 lets
+/// freshly-published exploring intents (base score 0.3) push adjustments onto
+/// everyone else; 0.5 means only decisions with some real evidence behind them
+/// (e.g. committed code) take part. The Rust API keeps the floor explicit.
+const DEFAULT_MIN_STABILITY: f64 = 0.5;
+
+/// Python-facing wrapper for IntentGraph
+#[pyclass(name = "IntentGraph", unsendable)]
+struct PyIntentGraph {
+    inner: IntentGraph,
+    /// Declared stability for published dicts that omit `stability`
+    default_stability: f64,
+}
+
+#[pymethods]
+impl PyIntentGraph {
+    /// `default_stability` is the declared stability given to published
+    /// dicts that omit `stability`; it must lie in [0.0, 1.0].
+    ///
+    /// `name_match` ("exact", "prefix", "substring", "fuzzy") sets how
+    /// precisely interface names must match to overlap.
+    ///
+    /// `normalize_agent_ids` trims and lowercases agent ids on publish and
+    /// in queries, so `"Agent-A"` and `"agent-a "` are one agent.
+    ///
+    /// `id_generator` picks ids for dicts without an `id`: "uuid4" (random)
+    /// or "monotonic" (`intent-000000000001`, ... continuing after the
+    /// intents already in the graph).
+    ///
+    /// `scorer` is a `StabilityScorer` to compute stability with; pass the
+    /// same one to several graphs to give them one scoring policy.
+    ///
+    /// `limits` overrides the publish size limits, e.g.
+    /// `{"max_signature_len": 4096, "max_tags": 16, "max_specs": 64}`; omitted
+    /// keys keep their (generous) defaults. Publishing an intent over a limit
+    /// raises `ValidationError`.
+    #[new]
+    #[pyo3(signature = (path=None, default_stability=DEFAULT_STABILITY, name_match="substring", normalize_agent_ids=false, id_generator="uuid4", scorer=None, limits=None))]
+    fn new(
+        path: Option<&str>,
+        default_stability: f64,
+        name_match: &str,
+        normalize_agent_ids: bool,
+        id_generator: &str,
+        scorer: Option<PyRef<'_, PyStabilityScorer>>,
+        limits: Option<std::collections::HashMap<String, usize>>,
+    ) -> PyResult<Self> {
+        if !(0.0..=1.0).contains(&default_stability) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "default_stability must be in [0.0, 1.0], got {}",
+                default_stability
+            )));
+        }
+        let name_match = parse_name_match(name_match)?;
+        if !matches!(id_generator, "uuid4" | "monotonic") {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown id_generator: '{}'. Expected one of: uuid4, monotonic",
+                id_generator
+            )));
+        }
+        let mut publish_limits = PublishLimits::default();
+        for (key, value) in limits.unwrap_or_default() {
+            match key.as_str() {
+                "max_signature_len" => publish_limits.max_signature_len = value,
+                "max_tags" => publish_limits.max_tags = value,
+                "max_specs" => publish_limits.max_specs = value,
+                _ => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Unknown limit: '{}'. Expected one of: max_signature_len, max_tags, max_specs",
+                    key
+                )))
+                }
+            }
+        }
+        let scorer = scorer.map_or_else(StabilityScorer::new, |s| s.inner.clone());
+        let inner = match path {
+            Some(p) => IntentGraph::persistent_with_scorer(p, scorer),
+            None => IntentGraph::in_memory().map(|g| g.with_scorer(scorer)),
+        }
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        let ids: std::sync::Arc<dyn IdGenerator> = if id_generator == "monotonic" {
+            let existing = inner
+                .count()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            std::sync::Arc::new(MonotonicGenerator::starting_at(
+                "intent-",
+                existing as u64 + 1,
+            ))
+        } else {
+            std::sync::Arc::new(UuidGenerator)
+        };
+        Ok(PyIntentGraph {
+            inner: inner
+                .with_name_match(name_match)
+                .with_normalize_agent_ids(normalize_agent_ids)
+                .with_id_generator(ids)
+                .with_publish_limits(publish_limits),
+            default_stability,
+        })
+    }
+
+    /// Where the graph is stored: `{"kind": "memory"}` or
+    /// `{"kind": "file", "path": str, "read_only": bool}`.
+    fn source(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let dict = PyDict::new(py);
+        match self.inner.source() {
+            GraphSource::InMemory => dict.set_item("kind", "memory")?,
+            GraphSource::File { path, read_only } => {
+                dict.set_item("kind", "file")?;
+                dict.set_item("path", path)?;
+                dict.set_item("read_only", read_only)?;
+            }
+        }
+        Ok(dict.into())
+    }
+
+    /// Add a policy check run before every publish. `guard(intent_dict)`
+    /// must return something truthy to allow the intent; a falsy return or
+    /// an exception rejects it, and the publish raises `ValidationError`.
+    fn register_publish_guard(&mut self, guard: Py<PyAny>) {
+        self.inner.register_publish_guard(Box::new(move |intent| {
+            Python::attach(|py| {
+                let dict = intent_to_dict(py, intent).map_err(|e| e.value(py).to_string())?;
+                match guard.bind(py).call1((dict,)).and_then(|v| v.is_truthy()) {
+                    Ok(true) => Ok(()),
+                    Ok(false) => Err("rejected by publish guard".to_string()),
+                    Err(e) => Err(e.value(py).to_string()),
+                }
+            })
+        }));
+    }
+
+    /// Publish an intent node to the graph. Returns computed stability. A
+    /// dict without an `id` gets the one the graph assigned written back.
+    fn publish(&self, intent_dict: &Bound<'_, PyDict>) -> PyResult<f64> {
+        let intent = dict_to_intent(
+            intent_dict,
+            self.inner.clock(),
+            self.inner.id_generator(),
+            self.default_stability,
+        )?;
+        let stability = self.inner.publish(&intent).map_err(publish_err)?;
+        record_id(intent_dict, &intent.id)?;
+        Ok(stability)
+    }
+
+    /// Publish an intent with a precomputed stability, skipping the scorer.
+    /// The value is clamped to [0.0, 1.0] and trusted as-is.
+    fn publish_with_stability(
+        &self,
+        intent_dict: &Bound<'_, PyDict>,
+        computed_stability: f64,
+    ) -> PyResult<()> {
+        let intent = dict_to_intent(
+            intent_dict,
+            self.inner.clock(),
+            self.inner.id_generator(),
+            self.default_stability,
+        )?;
+        let id = self
+            .inner
+            .publish_with_stability(&intent, computed_stability)
+            .map_err(publish_err)?;
+        record_id(intent_dict, &id)
+    }
+
+    /// Publish an intent and return `(stability, conflicts)`, where conflicts
+    /// are overlap clashes with intents already in the graph.
+    #[pyo3(signature = (intent_dict, min_stability=DEFAULT_MIN_STABILITY))]
+    fn publish_and_check(
+        &self,
+        py: Python,
+        intent_dict: &Bound<'_, PyDict>,
+        min_stability: f64,
+    ) -> PyResult<(f64, Py<PyAny>)> {
+        let intent = dict_to_intent(
+            intent_dict,
+            self.inner.clock(),
+            self.inner.id_generator(),
+            self.default_stability,
+        )?;
+        let (stability, conflicts) = self
+            .inner
+            .publish_and_check(&intent, min_stability)
+            .map_err(publish_err)?;
+        record_id(intent_dict, &intent.id)?;
+
+        let list = PyList::empty(py);
+        for conflict in &conflicts {
+            list.append(conflict_to_dict(py, conflict)?)?;
+        }
+        Ok((stability, list.into()))
+    }
+
+    /// Publish an intent and report non-fatal warnings.
+    ///
+    /// Returns `{"id": str, "stability": float, "warnings": [dict]}`, where
+    /// each warning has `section`, `normalized_name`, and `names`.
+    fn publish_verbose(&self, py: Python, intent_dict: &Bound<'_, PyDict>) -> PyResult<Py<PyAny>> {
+        let intent = dict_to_intent(
+            intent_dict,
+            self.inner.clock(),
+            self.inner.id_generator(),
+            self.default_stability,
+        )?;
+        let stats = self.inner.publish_verbose(&intent).map_err(publish_err)?;
+        record_id(intent_dict, &stats.id)?;
+
+        let warnings = PyList::empty(py);
+        for warning in &stats.warnings {
+            let w = PyDict::new(py);
+            w.set_item("section", &warning.section)?;
+            w.set_item("normalized_name", &warning.normalized_name)?;
+            w.set_item("names", &warning.names)?;
+            warnings.append(w)?;
+        }
+
+        let d = PyDict::new(py);
+        d.set_item("id", &stats.id)?;
+        d.set_item("stability", stats.stability)?;
+        d.set_item("warnings", warnings)?;
+        Ok(d.into())
+    }
+
+    /// Check an intent without publishing it. Returns a list of issue dicts
+    /// with `severity` ("error" or "warning"), `kind`, and `message`; empty
+    /// when the intent is clean.
+    fn validate_intent(&self, py: Python, intent_dict: &Bound<'_, PyDict>) -> PyResult<Py<PyAny>> {
+        let intent = dict_to_intent(
+            intent_dict,
+            self.inner.clock(),
+            self.inner.id_generator(),
+            self.default_stability,
+        )?;
+        let issues = self
+            .inner
+            .validate_intent(&intent)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for issue in &issues {
+            let d = PyDict::new(py);
+            d.set_item("severity", issue.severity.as_str())?;
+            d.set_item("kind", issue.kind.as_str())?;
+            d.set_item("message", &issue.message)?;
+            list.append(d)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Distinct interface tags as `{tag: count}`, most used first, where
+    /// count is the number of interfaces carrying the tag.
+    fn all_tags(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let tags = self
+            .inner
+            .all_tags()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        let dict = PyDict::new(py);
+        for (tag, count) in tags {
+            dict.set_item(tag, count)?;
+        }
+        Ok(dict.into())
+    }
+
+    /// Query all intents, optionally filtered by minimum stability.
+    #[pyo3(signature = (min_stability=None))]
+    fn query_all(&self, py: Python, min_stability: Option<f64>) -> PyResult<Py<PyAny>> {
+        let intents = self
+            .inner
+            .query_all(min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in intents {
+            list.append(intent_to_dict(py, &intent)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Query intents from a specific agent.
+    fn query_by_agent(&self, py: Python, agent_id: &str) -> PyResult<Py<PyAny>> {
+        let intents = self
+            .inner
+            .query_by_agent(agent_id)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in intents {
+            list.append(intent_to_dict(py, &intent)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Query intents published strictly after `since`, oldest first.
+    ///
+    /// `since` may be a `datetime` (naive values are taken as UTC) or an
+    /// RFC 3339 string with a `Z` or numeric offset.
+    #[pyo3(signature = (since, min_stability=None))]
+    fn query_since(
+        &self,
+        py: Python,
+        since: &Bound<'_, PyAny>,
+        min_stability: Option<f64>,
+    ) -> PyResult<Py<PyAny>> {
+        let since = parse_timestamp(since)?;
+        let intents = self
+            .inner
+            .query_since(since, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in intents {
+            list.append(intent_to_dict(py, &intent)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Intents published after sequence number `seq`, in publish order, as
+    /// `(intents, cursor)`. Pass `cursor` back in to read only what was
+    /// published since; start from 0. Unlike `query_since`, intents sharing
+    /// a timestamp are never skipped or repeated.
+    #[pyo3(signature = (seq=0, min_stability=None))]
+    fn query_after_seq(
+        &self,
+        py: Python,
+        seq: i64,
+        min_stability: Option<f64>,
+    ) -> PyResult<(Py<PyAny>, i64)> {
+        let (intents, cursor) = self
+            .inner
+            .query_after_seq(seq, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in intents {
+            list.append(intent_to_dict(py, &intent)?)?;
+        }
+        Ok((list.into(), cursor))
+    }
+
+    /// Intents providing a migration published strictly after `since`,
+    /// oldest first. `since` is parsed as for `query_since`.
+    fn migrations_since(&self, py: Python, since: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let since = parse_timestamp(since)?;
+        let intents = self
+            .inner
+            .migrations_since(since)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in intents {
+            list.append(intent_to_dict(py, &intent)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Query intents carrying an intent-level label, oldest first.
+    #[pyo3(signature = (label, min_stability=0.0))]
+    fn query_by_label(&self, py: Python, label: &str, min_stability: f64) -> PyResult<Py<PyAny>> {
+        let intents = self
+            .inner
+            .query_by_label(label, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in intents {
+            list.append(intent_to_dict(py, &intent)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Query intents with a spec whose `module_path` starts with `prefix`,
+    /// oldest first.
+    #[pyo3(signature = (prefix, min_stability=0.0))]
+    fn query_by_module_prefix(
+        &self,
+        py: Python,
+        prefix: &str,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let intents = self
+            .inner
+            .query_by_module_prefix(prefix, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in intents {
+            list.append(intent_to_dict(py, &intent)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Query intents by interface tags. `mode` is "all" (every tag must be
+    /// present) or "any" (at least one).
+    #[pyo3(signature = (tags, mode="all", min_stability=0.0))]
+    fn query_by_tags(
+        &self,
+        py: Python,
+        tags: Vec<String>,
+        mode: &str,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let mode = parse_tag_match(mode)?;
+        let tag_refs: Vec<&str> = tags.iter().map(String::as_str).collect();
+        let intents = self
+            .inner
+            .query_by_tags(&tag_refs, mode, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in intents {
+            list.append(intent_to_dict(py, &intent)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Query intents that provide or require an interface of the given kind.
+    /// `role` optionally restricts the match to "provides" or "requires".
+    #[pyo3(signature = (kind, role=None, min_stability=0.0))]
+    fn query_by_kind(
+        &self,
+        py: Python,
+        kind: &str,
+        role: Option<&str>,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let kind = parse_interface_kind(kind)?;
+        if let Some(r) = role {
+            parse_role(r)?;
+        }
+        let intents = self
+            .inner
+            .query_by_kind(kind, role, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in intents {
+            list.append(intent_to_dict(py, &intent)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Count overlapping intents without building their dicts.
+    #[pyo3(signature = (specs_list, exclude_agent, min_stability=DEFAULT_MIN_STABILITY))]
+    fn count_overlapping(
+        &self,
+        specs_list: &Bound<'_, PyList>,
+        exclude_agent: &str,
+        min_stability: f64,
+    ) -> PyResult<usize> {
+        let specs = list_to_interface_specs(specs_list)?;
+        self.inner
+            .count_overlapping(&specs, exclude_agent, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Find overlapping intents for the given interface specs.
+    ///
+    /// With `include_self=True`, intents from `exclude_agent` are considered
+    /// too, surfacing clashes between one agent's own sub-scopes.
+    ///
+    /// `role` ("provides" or "requires") only matches against that list of
+    /// the other intents; `None` matches both.
+    #[pyo3(signature = (specs_list, exclude_agent, min_stability=DEFAULT_MIN_STABILITY, include_self=false, role=None))]
+    fn find_overlapping(
+        &self,
+        py: Python,
+        specs_list: &Bound<'_, PyList>,
+        exclude_agent: &str,
+        min_stability: f64,
+        include_self: bool,
+        role: Option<&str>,
+    ) -> PyResult<Py<PyAny>> {
+        let specs = list_to_interface_specs(specs_list)?;
+        let role = role.map(parse_role).transpose()?;
+        let intents = self
+            .inner
+            .find_overlapping_with(
+                &specs,
+                exclude_agent,
+                min_stability,
+                OverlapOptions::default()
+                    .with_include_self(include_self)
+                    .with_role_filter(role),
+            )
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in intents {
+            list.append(intent_to_dict(py, &intent)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Find overlapping intents along with why each one matched.
+    /// Returns a list of `{"intent": dict, "reasons": [dict]}`.
+    #[pyo3(signature = (specs_list, exclude_agent, min_stability=DEFAULT_MIN_STABILITY))]
+    fn find_overlapping_explained(
+        &self,
+        py: Python,
+        specs_list: &Bound<'_, PyList>,
+        exclude_agent: &str,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let specs = list_to_interface_specs(specs_list)?;
+        let explained = self
+            .inner
+            .find_overlapping_explained(&specs, exclude_agent, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for (intent, reasons) in explained {
+            let reason_list = PyList::empty(py);
+            for reason in &reasons {
+                let d = PyDict::new(py);
+                d.set_item("my_spec", &reason.my_spec)?;
+                d.set_item("their_spec", &reason.their_spec)?;
+                d.set_item("kind", format!("{:?}", reason.kind))?;
+                d.set_item("strength", reason.strength)?;
+                reason_list.append(d)?;
+            }
+            let d = PyDict::new(py);
+            d.set_item("intent", intent_to_dict(py, &intent)?)?;
+            d.set_item("reasons", reason_list)?;
+            list.append(d)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Resolve an intent against the graph. Returns adjustments and conflicts.
+    ///
+    /// With `both_above=True`, conflicts are only reported when both intents
+    /// clear `min_stability`; a weaker resolving intent gets `YieldTo` instead.
+    ///
+    /// `adopt_policy` ("adopt_all", "required_and_above", "critical_only")
+    /// selects which constraint severities are adopted; skipped constraints
+    /// are listed under `notes`.
+    #[pyo3(signature = (intent_dict, min_stability=DEFAULT_MIN_STABILITY, both_above=false, adopt_policy="adopt_all"))]
+    fn resolve(
+        &self,
+        py: Python,
+        intent_dict: &Bound<'_, PyDict>,
+        min_stability: f64,
+        both_above: bool,
+        adopt_policy: &str,
+    ) -> PyResult<Py<PyAny>> {
+        let intent = dict_to_intent(
+            intent_dict,
+            self.inner.clock(),
+            self.inner.id_generator(),
+            self.default_stability,
+        )?;
+        let options = ResolveOptions::new(min_stability)
+            .with_both_above(both_above)
+            .with_adopt_policy(parse_adopt_policy(adopt_policy)?);
+        let result = self
+            .inner
+            .resolve_with(&intent, &options)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        Ok(resolution_to_dict(py, &result)?.into())
+    }
+
+    /// `resolve`, with a `trace` key listing every comparison made. Each
+    /// entry is a dict with `check`, `their_intent_id`, `mine`, `theirs`,
+    /// `detail`, `outcome`, and a one-line `message`.
+    #[pyo3(signature = (intent_dict, min_stability=DEFAULT_MIN_STABILITY))]
+    fn resolve_traced(
+        &self,
+        py: Python,
+        intent_dict: &Bound<'_, PyDict>,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let intent = dict_to_intent(
+            intent_dict,
+            self.inner.clock(),
+            self.inner.id_generator(),
+            self.default_stability,
+        )?;
+        let (result, trace) = self
+            .inner
+            .resolve_traced(&intent, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let dict = resolution_to_dict(py, &result)?;
+        let steps = PyList::empty(py);
+        for step in &trace {
+            let d = PyDict::new(py);
+            d.set_item("check", step.check.as_str())?;
+            d.set_item("their_intent_id", &step.their_intent_id)?;
+            d.set_item("mine", &step.mine)?;
+            d.set_item("theirs", &step.theirs)?;
+            d.set_item("detail", &step.detail)?;
+            d.set_item("outcome", &step.outcome)?;
+            d.set_item("message", step.to_string())?;
+            steps.append(d)?;
+        }
+        dict.set_item("trace", steps)?;
+        Ok(dict.into())
+    }
+
+    /// Resolve an intent, apply `ConsumeInstead` adjustments whose provider
+    /// stability and overlap strength both reach `auto_apply_threshold`, and
+    /// publish the adjusted intent. Returns the resolution dict; applied
+    /// swaps are listed under `notes`.
+    #[pyo3(signature = (intent_dict, min_stability=DEFAULT_MIN_STABILITY, auto_apply_threshold=0.8))]
+    fn resolve_and_apply(
+        &self,
+        py: Python,
+        intent_dict: &Bound<'_, PyDict>,
+        min_stability: f64,
+        auto_apply_threshold: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let intent = dict_to_intent(
+            intent_dict,
+            self.inner.clock(),
+            self.inner.id_generator(),
+            self.default_stability,
+        )?;
+        let result = self
+            .inner
+            .resolve_and_apply(&intent, min_stability, auto_apply_threshold)
+            .map_err(publish_err)?;
+
+        Ok(resolution_to_dict(py, &result)?.into())
+    }
+
+    /// Intent dicts that `intent_dict` would conflict with if published,
+    /// oldest first. Only interface overlaps are checked; nothing is written.
+    #[pyo3(signature = (intent_dict, min_stability=DEFAULT_MIN_STABILITY))]
+    fn would_conflict_with(
+        &self,
+        py: Python,
+        intent_dict: &Bound<'_, PyDict>,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let intent = dict_to_intent(
+            intent_dict,
+            self.inner.clock(),
+            self.inner.id_generator(),
+            self.default_stability,
+        )?;
+        let intents = self
+            .inner
+            .would_conflict_with(&intent, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for intent in &intents {
+            list.append(intent_to_dict(py, intent)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Resolve several candidate intents against one snapshot of the graph.
+    /// Returns one resolution dict per intent, in order, as `resolve` would.
+    #[pyo3(signature = (intent_dicts, min_stability=DEFAULT_MIN_STABILITY))]
+    fn resolve_batch(
+        &self,
+        py: Python,
+        intent_dicts: &Bound<'_, PyList>,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let mut intents = Vec::with_capacity(intent_dicts.len());
+        for item in intent_dicts.iter() {
+            let dict: &Bound<'_, PyDict> = item.cast()?;
+            intents.push(dict_to_intent(
+                dict,
+                self.inner.clock(),
+                self.inner.id_generator(),
+                self.default_stability,
+            )?);
+        }
+        let results = self
+            .inner
+            .resolve_batch(&intents, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for result in &results {
+            list.append(resolution_to_dict(py, result)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Publish `intent_dicts` into a throwaway copy of the graph and report
+    /// what they would break, as a dict with `clean`, `new_conflicts`
+    /// (conflict dicts), `issues` (validation issue dicts carrying the batch
+    /// `intent_id`), and `rejected` (`{"intent_id", "reason"}`). The graph
+    /// itself is never written.
+    #[pyo3(signature = (intent_dicts, min_stability=DEFAULT_MIN_STABILITY))]
+    fn dry_run_batch(
+        &self,
+        py: Python,
+        intent_dicts: &Bound<'_, PyList>,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let mut intents = Vec::with_capacity(intent_dicts.len());
+        for item in intent_dicts.iter() {
+            let dict: &Bound<'_, PyDict> = item.cast()?;
+            intents.push(dict_to_intent(
+                dict,
+                self.inner.clock(),
+                self.inner.id_generator(),
+                self.default_stability,
+            )?);
+        }
+        let report = self
+            .inner
+            .dry_run_batch(&intents, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("clean", report.is_clean())?;
+        let conflicts = PyList::empty(py);
+        for conflict in &report.new_conflicts {
+            conflicts.append(conflict_to_dict(py, conflict)?)?;
+        }
+        dict.set_item("new_conflicts", conflicts)?;
+        let issues = PyList::empty(py);
+        for (intent_id, issue) in &report.issues {
+            let d = PyDict::new(py);
+            d.set_item("intent_id", intent_id)?;
+            d.set_item("severity", issue.severity.as_str())?;
+            d.set_item("kind", issue.kind.as_str())?;
+            d.set_item("message", &issue.message)?;
+            issues.append(d)?;
+        }
+        dict.set_item("issues", issues)?;
+        let rejected = PyList::empty(py);
+        for r in &report.rejected {
+            let d = PyDict::new(py);
+            d.set_item("intent_id", &r.intent_id)?;
+            d.set_item("reason", &r.reason)?;
+            rejected.append(d)?;
+        }
+        dict.set_item("rejected", rejected)?;
+        Ok(dict.into())
+    }
+
+    /// Adjustments an agent still owes across its intents, as a list of
+    /// adjustment dicts each carrying the owing `intent_id`.
+    #[pyo3(signature = (agent_id, min_stability=DEFAULT_MIN_STABILITY))]
+    fn outstanding_adjustments(
+        &self,
+        py: Python,
+        agent_id: &str,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let outstanding = self
+            .inner
+            .outstanding_adjustments(agent_id, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for (intent_id, adj) in &outstanding {
+            let d = adjustment_to_dict(py, adj)?;
+            d.set_item("intent_id", intent_id)?;
+            list.append(d)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Structural diff of intent `id_b` against `id_a`, as a dict with
+    /// `provides`, `requires`, and `constraints` sections of
+    /// `added`/`removed`/`changed`. Raises `KeyError` for an unknown id.
+    fn diff_intents(&self, py: Python, id_a: &str, id_b: &str) -> PyResult<Py<PyAny>> {
+        let fetch = |id: &str| {
+            self.inner
+                .get(id)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>(id.to_string()))
+        };
+        let diff = diff_intents(&fetch(id_a)?, &fetch(id_b)?);
+        Ok(intent_diff_to_dict(py, &diff)?.into())
+    }
+
+    /// A slice of the graph as a JSON array of intents, restricted to the
+    /// given agents, interface tags, and minimum stability. Ancestors of
+    /// exported intents are included so every `parent_id` resolves.
+    #[pyo3(signature = (agents=None, tags=None, min_stability=0.0))]
+    fn export_json_filtered(
+        &self,
+        agents: Option<Vec<String>>,
+        tags: Option<Vec<String>>,
+        min_stability: f64,
+    ) -> PyResult<String> {
+        let filter = ExportFilter {
+            agents,
+            tags,
+            min_stability,
+        };
+        self.inner
+            .export_json_filtered(filter)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Every intent as compact bincode `bytes`, for fast transfer between graphs.
+    #[cfg(feature = "bincode")]
+    fn export_bincode<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        let bytes = self
+            .inner
+            .export_bincode()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(pyo3::types::PyBytes::new(py, &bytes))
+    }
+
+    /// Publish every intent from `export_bincode` output. Returns the number
+    /// imported. With `trust_scores`, exported stability scores are stored
+    /// as-is instead of rescored; they stand until `recompute_all_stability`.
+    #[cfg(feature = "bincode")]
+    #[pyo3(signature = (data, trust_scores=false))]
+    fn import_bincode(&self, data: &[u8], trust_scores: bool) -> PyResult<usize> {
+        self.inner
+            .import_bincode(data, trust_scores)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Stability of a decision over its lineage as `(timestamp, stability)`
+    /// tuples, oldest first, with RFC 3339 timestamps. Raises `KeyError` for
+    /// an unknown id.
+    fn stability_history(&self, id: &str) -> PyResult<Vec<(String, f64)>> {
+        let history = self.inner.stability_history(id).map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                PyErr::new::<pyo3::exceptions::PyKeyError, _>(id.to_string())
+            }
+            other => PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(other.to_string()),
+        })?;
+        Ok(history
+            .into_iter()
+            .map(|(at, stability)| (at.to_rfc3339(), stability))
+            .collect())
+    }
+
+    /// Fork the graph into an independent in-memory copy for what-if analysis.
+    /// The fork does not stay in sync with this graph.
+    fn fork(&self) -> PyResult<PyIntentGraph> {
+        self.inner
+            .fork()
+            .map(|g| PyIntentGraph {
+                inner: g,
+                default_stability: self.default_stability,
+            })
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Append evidence to a published intent. Returns the recomputed stability.
+    fn add_evidence(&self, id: &str, evidence: &Bound<'_, PyList>) -> PyResult<f64> {
+        let evidence = list_to_evidence(evidence)?;
+        self.inner.add_evidence(id, &evidence).map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                PyErr::new::<pyo3::exceptions::PyKeyError, _>(id.to_string())
+            }
+            other => PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(other.to_string()),
+        })
+    }
+
+    /// Re-resolve intent `id` and every intent overlapping it after its
+    /// stability moved from `previous_stability`, e.g. via `add_evidence`.
+    /// Returns resolution dicts only for those whose outcome changed, the
+    /// changed intent's first; `original_intent` tells them apart. Raises
+    /// `KeyError` for an unknown id.
+    #[pyo3(signature = (id, previous_stability, min_stability=DEFAULT_MIN_STABILITY))]
+    fn reresolve_affected(
+        &self,
+        py: Python,
+        id: &str,
+        previous_stability: f64,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let results = self
+            .inner
+            .reresolve_affected(id, previous_stability, min_stability)
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    PyErr::new::<pyo3::exceptions::PyKeyError, _>(id.to_string())
+                }
+                other => PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(other.to_string()),
+            })?;
+
+        let list = PyList::empty(py);
+        for (_, result) in &results {
+            list.append(resolution_to_dict(py, result)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Rescore every intent and persist the results. Returns the number rescored.
+    fn recompute_all_stability(&self) -> PyResult<usize> {
+        self.inner
+            .recompute_all_stability()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Rescore every intent and return the ids whose stored stability changed.
+    fn recompute_stability_changed(&self) -> PyResult<Vec<String>> {
+        self.inner
+            .recompute_stability_changed()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Get intent count.
+    fn count(&self) -> PyResult<usize> {
+        self.inner
+            .count()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Checkpoint and truncate the write-ahead log of a WAL-mode graph; a
+    /// no-op for in-memory graphs.
+    fn checkpoint(&self) -> PyResult<()> {
+        self.inner
+            .checkpoint()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Run a read-only `SELECT` or `PRAGMA` and return its rows as dicts.
+    /// `params` bind to `?` placeholders. Raises `ValueError` for any other
+    /// statement. The schema is internal and may change between versions.
+    #[pyo3(signature = (sql, params=vec![]))]
+    fn query_raw(
+        &self,
+        py: Python,
+        sql: &str,
+        params: Vec<Bound<'_, PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        let values = params
+            .iter()
+            .map(py_to_sql_value)
+            .collect::<PyResult<Vec<_>>>()?;
+        let refs: Vec<&dyn rusqlite::ToSql> =
+            values.iter().map(|v| v as &dyn rusqlite::ToSql).collect();
+        let rows = self.inner.query_raw(sql, &refs).map_err(|e| match e {
+            rusqlite::Error::InvalidQuery => PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "query_raw only runs read-only SELECT or PRAGMA statements",
+            ),
+            other => PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(other.to_string()),
+        })?;
+        let text = serde_json::to_string(&rows)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(py.import("json")?.call_method1("loads", (text,))?.unbind())
+    }
+
+    /// Delete every intent, keeping the schema and this graph's settings.
+    /// Raises `RuntimeError` on a read-only graph.
+    fn clear(&self) -> PyResult<()> {
+        self.inner
+            .clear()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Context manager holding an exclusive database lock:
+    /// `with graph.exclusive(): ...`. Publishes inside the block commit on
+    /// exit, or roll back if the block raises.
+    fn exclusive(slf: &Bound<'_, Self>) -> PyExclusive {
+        PyExclusive {
+            graph: slf.clone().unbind(),
+        }
+    }
+
+    /// Get graph summary.
+    fn summary(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let s = self
+            .inner
+            .summary()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("total_intents", s.total_intents)?;
+        dict.set_item("agent_count", s.agent_count)?;
+        dict.set_item("agents", s.agents)?;
+        dict.set_item("average_stability", s.average_stability)?;
+        dict.set_item("high_stability_count", s.high_stability_count)?;
+        dict.set_item("conflict_count", s.conflict_count)?;
+        Ok(dict.into())
+    }
+
+    /// Intent counts per stability bucket, split at `edges` (strictly
+    /// increasing, within [0.0, 1.0]); the default gives exploring, forming,
+    /// and committed counts. Raises `ValueError` for invalid edges.
+    #[pyo3(signature = (edges=vec![0.3, 0.7]))]
+    fn stability_histogram(&self, edges: Vec<f64>) -> PyResult<Vec<usize>> {
+        self.inner.stability_histogram(&edges).map_err(|e| match e {
+            rusqlite::Error::ToSqlConversionFailure(msg) => {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(msg.to_string())
+            }
+            other => PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(other.to_string()),
+        })
+    }
+
+    /// Groups (lists of intent dicts, oldest first) of different agents'
+    /// intents providing the same interface with compatible signatures, so a
+    /// coordinator can keep one provider each. Incompatible duplicates are
+    /// conflicts and never grouped.
+    #[pyo3(signature = (min_stability=0.0))]
+    fn consolidation_candidates(&self, py: Python, min_stability: f64) -> PyResult<Py<PyAny>> {
+        let groups = self
+            .inner
+            .consolidation_candidates(min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for group in &groups {
+            let members = PyList::empty(py);
+            for intent in group {
+                members.append(intent_to_dict(py, intent)?)?;
+            }
+            list.append(members)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Nested dict `{consumer: {provider: score}}`, the score being the
+    /// fraction of the consumer agent's requirements the provider satisfies.
+    /// Agents without requirements have no entry.
+    #[pyo3(signature = (min_stability=0.0))]
+    fn compatibility_matrix(&self, py: Python, min_stability: f64) -> PyResult<Py<PyAny>> {
+        let matrix = self
+            .inner
+            .compatibility_matrix(min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        for (consumer, provider, score) in &matrix {
+            let row = match dict.get_item(consumer)? {
+                Some(row) => row.cast_into::<PyDict>()?,
+                None => {
+                    let row = PyDict::new(py);
+                    dict.set_item(consumer, &row)?;
+                    row
+                }
+            };
+            row.set_item(provider, score)?;
+        }
+        Ok(dict.into())
+    }
+
+    /// Per-agent stability leaderboard, highest average first: a list of
+    /// dicts with `agent_id`, `intent_count`, `average_stability`,
+    /// `max_stability`, and `conflict_count` (`None` for graphs too large to
+    /// resolve every intent).
+    fn agent_stability_stats(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let stats = self
+            .inner
+            .agent_stability_stats()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for s in &stats {
+            let d = PyDict::new(py);
+            d.set_item("agent_id", &s.agent_id)?;
+            d.set_item("intent_count", s.intent_count)?;
+            d.set_item("average_stability", s.average_stability)?;
+            d.set_item("max_stability", s.max_stability)?;
+            d.set_item("conflict_count", s.conflict_count)?;
+            list.append(d)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Who introduced an interface and who touches it now, as a dict with
+    /// `name` (normalized), `earliest_provider` (intent dict or `None`),
+    /// `providers`, and `requirers` (lists of intent dicts, oldest first).
+    #[pyo3(signature = (name, min_stability=0.0))]
+    fn interface_provenance(
+        &self,
+        py: Python,
+        name: &str,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let p = self
+            .inner
+            .interface_provenance(name, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("name", &p.name)?;
+        let earliest = p
+            .earliest_provider
+            .as_ref()
+            .map(|intent| intent_to_dict(py, intent))
+            .transpose()?;
+        dict.set_item("earliest_provider", earliest)?;
+        let providers = PyList::empty(py);
+        for intent in &p.providers {
+            providers.append(intent_to_dict(py, intent)?)?;
+        }
+        dict.set_item("providers", providers)?;
+        let requirers = PyList::empty(py);
+        for intent in &p.requirers {
+            requirers.append(intent_to_dict(py, intent)?)?;
+        }
+        dict.set_item("requirers", requirers)?;
+        Ok(dict.into())
+    }
+
+    /// Replay resolution over `order` on a throwaway copy of the graph, as a
+    /// dict with `initial_conflicts`, `final_conflicts`, `converged`, and
+    /// `steps` (a list of `{"intent_id", "applied", "skipped", "conflicts"}`).
+    #[pyo3(signature = (order, min_stability=0.0))]
+    fn simulate_convergence(
+        &self,
+        py: Python,
+        order: Vec<String>,
+        min_stability: f64,
+    ) -> PyResult<Py<PyAny>> {
+        let report = self
+            .inner
+            .simulate_convergence(&order, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("initial_conflicts", report.initial_conflicts)?;
+        dict.set_item("final_conflicts", report.final_conflicts)?;
+        dict.set_item("converged", report.converged())?;
+        let steps = PyList::empty(py);
+        for step in &report.steps {
+            let d = PyDict::new(py);
+            d.set_item("intent_id", &step.intent_id)?;
+            d.set_item("applied", step.applied)?;
+            d.set_item("skipped", step.skipped)?;
+            d.set_item("conflicts", step.conflicts)?;
+            steps.append(d)?;
+        }
+        dict.set_item("steps", steps)?;
+        Ok(dict.into())
+    }
+
+    /// Most-consumed intents as a list of `{"intent": dict, "consumers": int}`,
+    /// highest consumer count first. `consumers` counts distinct agents, from
+    /// `consumed_by` evidence and from intents requiring the interface.
+    #[pyo3(signature = (limit=10, min_stability=0.0))]
+    fn most_consumed(&self, py: Python, limit: usize, min_stability: f64) -> PyResult<Py<PyAny>> {
+        let ranked = self
+            .inner
+            .most_consumed(limit, min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for (intent, consumers) in ranked {
+            let d = PyDict::new(py);
+            d.set_item("intent", intent_to_dict(py, &intent)?)?;
+            d.set_item("consumers", consumers)?;
+            list.append(d)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Required interfaces no other agent provides compatibly, as a list of
+    /// `{"intent_id", "agent_id", "interface"}` dicts.
+    #[pyo3(signature = (min_stability=0.0))]
+    fn unsatisfied_requirements(&self, py: Python, min_stability: f64) -> PyResult<Py<PyAny>> {
+        let gaps = self
+            .inner
+            .unsatisfied_requirements(min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for (intent, spec) in gaps {
+            let d = PyDict::new(py);
+            d.set_item("intent_id", &intent.id)?;
+            d.set_item("agent_id", &intent.agent_id)?;
+            d.set_item("interface", spec_to_dict(py, &spec)?)?;
+            list.append(d)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Consistency of the interface index with the intents table.
+    fn index_health(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let h = self
+            .inner
+            .index_health()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("interface_rows", h.interface_rows)?;
+        dict.set_item("orphaned_rows", h.orphaned_rows)?;
+        dict.set_item("intents_missing_entries", h.intents_missing_entries)?;
+        dict.set_item("healthy", h.is_healthy())?;
+        Ok(dict.into())
+    }
+
+    /// Consumer→provider edges as a list of `(consumer_id, provider_id)` tuples.
+    #[pyo3(signature = (min_stability=0.0))]
+    fn dependency_edges(&self, min_stability: f64) -> PyResult<Vec<(String, String)>> {
+        self.inner
+            .dependency_edges(min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Detect cycles in `parent_id` lineage. Returns a list of id paths;
+    /// empty when the lineage is valid.
+    fn validate_lineage(&self) -> PyResult<Vec<Vec<String>>> {
+        self.inner
+            .validate_lineage()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Write every conflict in the graph to `path` as newline-delimited JSON.
+    /// Returns the number of conflicts written.
+    #[pyo3(signature = (path, min_stability=DEFAULT_MIN_STABILITY))]
+    fn write_conflicts_ndjson(&self, path: &str, min_stability: f64) -> PyResult<usize> {
+        let file = std::fs::File::create(path)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyOSError, _>(e.to_string()))?;
+        self.inner
+            .write_conflicts_ndjson(min_stability, std::io::BufWriter::new(file))
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    /// Render the consumes graph as Graphviz DOT source.
+    #[pyo3(signature = (min_stability=0.0))]
+    fn to_dot(&self, min_stability: f64) -> PyResult<String> {
+        self.inner
+            .to_dot(min_stability)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+}
+
+/// Python-facing stability scorer
+#[pyclass(name = "StabilityScorer")]
+struct PyStabilityScorer {
+    inner: StabilityScorer,
+}
+
+#[pymethods]
+impl PyStabilityScorer {
+    /// Create a scorer, optionally from a weights dict or a JSON string.
+    /// Omitted weights keep their defaults; invalid ones raise `ValueError`.
+    ///
+    /// `saturation` is "clamp" (scores past 1.0 read 1.0) or "soft" (high
+    /// scores bend towards 1.0, so stronger evidence still ranks higher).
+    /// `conflict_half_life_hours`, if given, halves each conflict's penalty
+    /// every that many hours after it was recorded; it must be positive.
+    #[new]
+    #[pyo3(signature = (weights=None, saturation="clamp", conflict_half_life_hours=None))]
+    fn new(
+        weights: Option<&Bound<'_, PyAny>>,
+        saturation: &str,
+        conflict_half_life_hours: Option<f64>,
+    ) -> PyResult<Self> {
+        let saturation = Saturation::from_name(saturation).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown saturation: '{}'. Expected one of: clamp, soft",
+                saturation
+            ))
+        })?;
+        let inner = match weights {
+            None => StabilityScorer::new(),
+            Some(w) => {
+                let json = if let Ok(s) = w.extract::<String>() {
+                    s
+                } else {
+                    let map: std::collections::HashMap<String, f64> = w.extract()?;
+                    serde_json::to_string(&map).map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string())
+                    })?
+                };
+                StabilityScorer::from_json(&json).map_err(|e| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Invalid stability weights: {}",
+                        e
+                    ))
+                })?
+            }
+        };
+        let mut inner = inner.with_saturation(saturation);
+        if let Some(hours) = conflict_half_life_hours {
+            if !hours.is_finite() || hours <= 0.0 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "conflict_half_life_hours must be positive, got {}",
+                    hours
+                )));
+            }
+            let half_life = chrono::Duration::milliseconds((hours * 3_600_000.0) as i64);
+            inner = inner.with_conflict_half_life(half_life);
+        }
+        Ok(PyStabilityScorer { inner })
+    }
+
+    /// The scorer's weights as a JSON string.
+    fn weights_to_json(&self) -> PyResult<String> {
+        self.inner
+            .weights_to_json()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    fn compute(&self, intent_dict: &Bound<'_, PyDict>) -> PyResult<f64> {
+        let intent = dict_to_intent(intent_dict, &SystemClock, &UuidGenerator, DEFAULT_STABILITY)?;
+        Ok(self.inner.compute(&intent))
+    }
+}
+
+/// Context manager returned by `IntentGraph.exclusive()`. Python can't hold
+/// the borrowing [`ExclusiveGuard`](crate::graph::ExclusiveGuard), so this
+/// drives the same transaction by hand.
+#[pyclass(name = "ExclusiveLock", unsendable)]
+struct PyExclusive {
+    graph: Py<PyIntentGraph>,
+}
+
+#[pymethods]
+impl PyExclusive {
+    /// Take the lock; raises `RuntimeError` if another connection holds it.
+    fn __enter__(&self, py: Python<'_>) -> PyResult<Py<PyIntentGraph>> {
+        self.graph
+            .borrow(py)
+            .inner
+            .exclusive_begin()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(self.graph.clone_ref(py))
+    }
+
+    /// Commit, or roll back if the block raised. Never suppresses the
+    /// exception.
+    fn __exit__(
+        &self,
+        py: Python<'_>,
+        exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        self.graph
+            .borrow(py)
+            .inner
+            .exclusive_end(exc_type.is_none())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        Ok(false)
+    }
+}
+
+/// Python-facing interface spec, so matching runs the same Rust logic the
+/// graph uses instead of a client-side approximation.
+#[pyclass(name = "InterfaceSpec")]
+struct PyInterfaceSpec {
+    inner: InterfaceSpec,
+}
+
+#[pymethods]
+impl PyInterfaceSpec {
+    /// Create a spec from a spec dict (`name`, `kind`, `signature`, ...).
+    #[new]
+    fn new(spec: &Bound<'_, PyDict>) -> PyResult<Self> {
+        Ok(PyInterfaceSpec {
+            inner: dict_to_spec(spec)?,
+        })
+    }
+
+    /// Whether the names overlap or the tags are shared. `other` is a spec
+    /// dict or an `InterfaceSpec`; `name_match` is as for `IntentGraph`.
+    #[pyo3(signature = (other, name_match="substring"))]
+    fn structurally_overlaps(&self, other: &Bound<'_, PyAny>, name_match: &str) -> PyResult<bool> {
+        let mode = parse_name_match(name_match)?;
+        let other = any_to_spec(other)?;
+        Ok(self.inner.structurally_overlaps_with(&other, mode))
+    }
+
+    /// Whether `other`'s signature satisfies this one's.
+    fn signature_compatible(&self, other: &Bound<'_, PyAny>) -> PyResult<bool> {
+        Ok(self.inner.signature_compatible(&any_to_spec(other)?))
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        spec_to_dict(py, &self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "InterfaceSpec(name={:?}, kind={:?}, signature={:?})",
+            self.inner.name,
+            self.inner.kind.as_str(),
+            self.inner.signature
+        )
+    }
+}
+
+/// A spec dict or an `InterfaceSpec` instance.
+fn any_to_spec(value: &Bound<'_, PyAny>) -> PyResult<InterfaceSpec> {
+    if let Ok(spec) = value.extract::<PyRef<'_, PyInterfaceSpec>>() {
+        return Ok(spec.inner.clone());
+    }
+    dict_to_spec(value.cast()?)
+}
+
+/// Map a publish failure to `ValidationError` when a publish guard refused
+/// the intent or it broke a publish limit, `RuntimeError` otherwise.
+fn publish_err(e: rusqlite::Error) -> PyErr {
+    if let rusqlite::Error::ToSqlConversionFailure(inner) = &e {
+        if let Some(rejected) = inner.downcast_ref::<PublishRejected>() {
+            return ValidationError::new_err(rejected.to_string());
+        }
+    }
+    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+}
+
+// ── Conversion helpers ──────────────────────────────────────────────
+
+pyo3::create_exception!(
+    _core,
+    ValidationError,
+    pyo3::exceptions::PyValueError,
+    "Raised when a publish guard rejects an intent, when an intent exceeds a publish limit, \
+     or in strict mode when an input dict carries keys the core does not read."
+);
+
+/// Whether input dicts may only carry recognized keys; see [`set_strict`].
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// Toggle strict dict parsing for the whole module. When on, intent, spec,
+/// constraint, and evidence dicts with unrecognized keys (e.g. a misspelled
+/// `"provide"`) raise `ValidationError` instead of being silently ignored.
+/// Off by default for compatibility.
+#[pyfunction]
+fn set_strict(enabled: bool) {
+    STRICT.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether strict dict parsing is on.
+#[pyfunction]
+fn is_strict() -> bool {
+    STRICT.load(Ordering::Relaxed)
+}
+
+/// JSON Schema (draft 2020-12) for the intent dicts `publish` and friends
+/// accept, as a dict: required keys, nested spec/constraint/evidence shapes,
+/// and allowed enum values. Unknown keys are allowed unless strict mode is
+/// on, matching what the converters do.
+#[pyfunction]
+fn intent_schema(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+    let open = !STRICT.load(Ordering::Relaxed);
+    let nullable_string = serde_json::json!({"type": ["string", "null"]});
+    let strings = serde_json::json!({"type": "array", "items": {"type": "string"}});
+    let spec = serde_json::json!({
+        "type": "object",
+        "required": ["name", "kind", "signature"],
+        "properties": {
+            "name": {"type": "string"},
+            "kind": {"enum": ["function", "class", "model", "endpoint", "migration", "config"]},
+            "signature": {"type": "string"},
+            "module_path": {"type": "string"},
+            "tags": strings,
+            "deprecated": {"type": "boolean"},
+            "replaced_by": nullable_string,
+        },
+        "additionalProperties": open,
+    });
+    let schema = serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Intent",
+        "type": "object",
+        "required": ["agent_id", "intent"],
+        "properties": {
+            "id": {"type": "string"},
+            "agent_id": {"type": "string"},
+            "intent": {"type": "string"},
+            "provides": {"type": "array", "items": spec},
+            "requires": {"type": "array", "items": spec},
+            "constraints": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["target", "requirement"],
+                    "properties": {
+                        "target": {"type": "string"},
+                        "requirement": {"type": "string"},
+                        "affects_tags": strings,
+                        "severity": {"enum": ["preferred", "required", "critical", null]},
+                        "applies_to_kind": {
+                            "enum": ["function", "class", "model", "endpoint", "migration",
+                                     "config", null]
+                        },
+                        "applies_to_agents": strings,
+                    },
+                    "additionalProperties": open,
+                },
+            },
+            "stability": {"type": "number"},
+            "evidence": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["kind", "description"],
+                    "properties": {
+                        "kind": {
+                            "enum": ["test_pass", "test_fail", "code_committed", "consumed_by",
+                                     "conflict", "manual_approval"]
+                        },
+                        "description": {"type": "string"},
+                        "source_agent": nullable_string,
+                    },
+                    "additionalProperties": open,
+                },
+            },
+            "parent_id": nullable_string,
+            "labels": strings,
+            "timestamp": {"type": ["string", "null"], "format": "date-time"},
+        },
+        "additionalProperties": open,
+    });
+    py.import("json")?
+        .call_method1("loads", (schema.to_string(),))
+}
+
+/// In strict mode, reject keys of `dict` outside `allowed`.
+fn check_keys(dict: &Bound<'_, PyDict>, allowed: &[&str], what: &str) -> PyResult<()> {
+    if !STRICT.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    let mut unexpected = Vec::new();
+    for key in dict.keys() {
+        match key.extract::<String>() {
+            Ok(k) if allowed.contains(&k.as_str()) => {}
+            _ => unexpected.push(key.repr()?.to_string()),
+        }
+    }
+    if unexpected.is_empty() {
+        return Ok(());
+    }
+    unexpected.sort();
+    Err(ValidationError::new_err(format!(
+        "Unexpected keys in {} dict: {}. Expected only: {}",
+        what,
+        unexpected.join(", "),
+        allowed.join(", ")
+    )))
+}
+
+/// After a publish, write the id the intent was stored under back into the
+/// caller's dict when it didn't name one, so it can be passed to `get`,
+/// `add_evidence` and friends.
+fn record_id(dict: &Bound<'_, PyDict>, id: &str) -> PyResult<()> {
+    if !dict.contains("id")? {
+        dict.set_item("id", id)?;
+    }
+    Ok(())
+}
+
+fn dict_to_intent(
+    dict: &Bound<'_, PyDict>,
+    clock: &dyn Clock,
+    ids: &dyn IdGenerator,
+    default_stability: f64,
+) -> PyResult<IntentNode> {
+    check_keys(
+        dict,
+        &[
+            "id",
+            "agent_id",
+            "intent",
+            "provides",
+            "requires",
+            "constraints",
+            "stability",
+            "evidence",
+            "parent_id",
+            "labels",
+            "timestamp",
+        ],
+        "intent",
+    )?;
+    let agent_id: String = dict
+        .get_item("agent_id")?
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("agent_id"))?
+        .extract()?;
+    let intent_text: String = dict
+        .get_item("intent")?
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("intent"))?
+        .extract()?;
+
+    let mut intent = IntentNode::new_with_clock(&agent_id, &intent_text, clock)
+        .with_stability(default_stability);
+
+    intent.id = match dict.get_item("id")? {
+        Some(id) => id.extract()?,
+        None => ids.next_id(),
+    };
+
+    if let Some(provides) = dict.get_item("provides")? {
+        let list: &Bound<'_, PyList> = provides.cast()?;
+        intent.provides = list_to_interface_specs(list)?;
+    }
+
+    if let Some(requires) = dict.get_item("requires")? {
+        let list: &Bound<'_, PyList> = requires.cast()?;
+        intent.requires = list_to_interface_specs(list)?;
+    }
+
+    if let Some(constraints) = dict.get_item("constraints")? {
+        let list: &Bound<'_, PyList> = constraints.cast()?;
+        intent.constraints = list_to_constraints(list)?;
+    }
+
+    if let Some(stability) = dict.get_item("stability")? {
+        intent.stability = stability.extract()?;
+    }
+
+    if let Some(evidence) = dict.get_item("evidence")? {
+        let list: &Bound<'_, PyList> = evidence.cast()?;
+        intent.evidence = list_to_evidence(list)?;
+    }
+
+    if let Some(parent_id) = dict.get_item("parent_id")? {
+        if !parent_id.is_none() {
+            intent.parent_id = Some(parent_id.extract()?);
+        }
+    }
+
+    if let Some(labels) = dict.get_item("labels")? {
+        intent.labels = labels.extract()?;
+    }
+
+    if let Some(timestamp) = dict.get_item("timestamp")? {
+        if !timestamp.is_none() {
+            intent.timestamp = parse_timestamp(&timestamp)?;
+        }
+    }
+
+    Ok(intent)
+}
+
+fn list_to_interface_specs(list: &Bound<'_, PyList>) -> PyResult<Vec<InterfaceSpec>> {
+    let mut specs = Vec::new();
+    for item in list.iter() {
+        specs.push(dict_to_spec(item.cast()?)?);
+    }
+    Ok(specs)
+}
+
+fn dict_to_spec(dict: &Bound<'_, PyDict>) -> PyResult<InterfaceSpec> {
+    check_keys(
+        dict,
+        &[
+            "name",
+            "kind",
+            "signature",
+            "module_path",
+            "tags",
+            "deprecated",
+            "replaced_by",
+        ],
+        "interface spec",
+    )?;
+    let name: String = dict
+        .get_item("name")?
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("name"))?
+        .extract()?;
+    let kind_str: String = dict
+        .get_item("kind")?
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("kind"))?
+        .extract()?;
+    let signature: String = dict
+        .get_item("signature")?
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("signature"))?
+        .extract()?;
+
+    let kind = parse_interface_kind(&kind_str)?;
+
+    let mut spec = InterfaceSpec::new(&name, kind, &signature);
+
+    if let Some(module) = dict.get_item("module_path")? {
+        spec = spec.with_module(&module.extract::<String>()?);
+    }
+
+    if let Some(tags) = dict.get_item("tags")? {
+        let tag_list: Vec<String> = tags.extract()?;
+        let tag_refs: Vec<&str> = tag_list.iter().map(|s| s.as_str()).collect();
+        spec = spec.with_tags(tag_refs);
+    }
+
+    if let Some(deprecated) = dict.get_item("deprecated")? {
+        if deprecated.extract::<bool>()? {
+            let replaced_by: Option<String> = match dict.get_item("replaced_by")? {
+                Some(r) => r.extract()?,
+                None => None,
+            };
+            spec = spec.with_deprecated(replaced_by.as_deref());
+        }
+    }
+
+    Ok(spec)
+}
+
+/// Accepts the lowercase name and, case-insensitively, the capitalized form
+/// dicts coming out of the graph carry (`"Model"`), so output reads back in.
+fn parse_interface_kind(kind_str: &str) -> PyResult<InterfaceKind> {
+    InterfaceKind::from_name(&kind_str.to_lowercase()).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Unknown InterfaceKind: '{}'. Expected one of: function, class, model, endpoint, migration, config",
+            kind_str
+        ))
+    })
+}
+
+fn parse_role(role_str: &str) -> PyResult<Role> {
+    Role::from_name(role_str).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Unknown role: '{}'. Expected one of: provides, requires",
+            role_str
+        ))
+    })
+}
+
+fn parse_name_match(mode_str: &str) -> PyResult<NameMatchMode> {
+    NameMatchMode::from_name(mode_str).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Unknown name_match: '{}'. Expected one of: exact, prefix, substring, fuzzy",
+            mode_str
+        ))
+    })
+}
+
+/// Convert a Python query parameter (`None`, `bool`, `int`, `float`, `str`
+/// or `bytes`) to an SQLite value.
+fn py_to_sql_value(value: &Bound<'_, PyAny>) -> PyResult<rusqlite::types::Value> {
+    use rusqlite::types::Value;
+    if value.is_none() {
+        Ok(Value::Null)
+    } else if let Ok(b) = value.cast::<pyo3::types::PyBool>() {
+        Ok(Value::Integer(i64::from(b.is_true())))
+    } else if let Ok(n) = value.extract::<i64>() {
+        Ok(Value::Integer(n))
+    } else if let Ok(x) = value.extract::<f64>() {
+        Ok(Value::Real(x))
+    } else if let Ok(text) = value.extract::<String>() {
+        Ok(Value::Text(text))
+    } else if let Ok(bytes) = value.cast::<pyo3::types::PyBytes>() {
+        Ok(Value::Blob(bytes.as_bytes().to_vec()))
+    } else {
+        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "Unsupported query parameter type: {}",
+            value.get_type().name()?
+        )))
+    }
+}
+
+/// Convert a Python timestamp to UTC.
+///
+/// Accepts `datetime` objects (naive ones are taken as UTC) and RFC 3339
+/// strings with a `Z` or numeric offset. Strings without an offset are
+/// rejected rather than guessed at.
+fn parse_timestamp(value: &Bound<'_, PyAny>) -> PyResult<DateTime<Utc>> {
+    if value.is_instance_of::<PyDateTime>() {
+        let iso: String = value.call_method0("isoformat")?.extract()?;
+        let parsed = if value.getattr("tzinfo")?.is_none() {
+            iso.parse::<NaiveDateTime>().map(|naive| naive.and_utc())
+        } else {
+            DateTime::parse_from_rfc3339(&iso).map(|dt| dt.with_timezone(&Utc))
+        };
+        return parsed.map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Cannot convert datetime '{}': {}",
+                iso, e
+            ))
+        });
+    }
+
+    let s: String = value.extract().map_err(|_| {
+        PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+            "Expected a datetime or an RFC 3339 timestamp string",
+        )
+    })?;
+    match DateTime::parse_from_rfc3339(&s) {
+        Ok(dt) => Ok(dt.with_timezone(&Utc)),
+        Err(_) if s.parse::<NaiveDateTime>().is_ok() => {
+            Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Timestamp '{}' has no UTC offset; append 'Z' or an offset such as '+00:00'",
+                s
+            )))
+        }
+        Err(e) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid RFC 3339 timestamp '{}': {}",
+            s, e
+        ))),
+    }
+}
+
+fn parse_constraint_severity(severity_str: &str) -> PyResult<ConstraintSeverity> {
+    ConstraintSeverity::from_name(severity_str).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Unknown ConstraintSeverity: '{}'. Expected one of: preferred, required, critical",
+            severity_str
+        ))
+    })
+}
+
+fn parse_adopt_policy(policy_str: &str) -> PyResult<AdoptPolicy> {
+    AdoptPolicy::from_name(policy_str).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Unknown adopt_policy: '{}'. Expected one of: adopt_all, required_and_above, critical_only",
+            policy_str
+        ))
+    })
+}
+
+fn parse_tag_match(mode_str: &str) -> PyResult<TagMatch> {
+    TagMatch::from_name(mode_str).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Unknown mode: '{}'. Expected one of: all, any",
+            mode_str
+        ))
+    })
+}
+
+fn list_to_constraints(list: &Bound<'_, PyList>) -> PyResult<Vec<Constraint>> {
+    let mut constraints = Vec::new();
+    for item in list.iter() {
+        let dict: &Bound<'_, PyDict> = item.cast()?;
+        check_keys(
+            dict,
+            &[
+                "target",
+                "requirement",
+                "affects_tags",
+                "severity",
+                "applies_to_kind",
+                "applies_to_agents",
+            ],
+            "constraint",
+        )?;
+        let target: String = dict
+            .get_item("target")?
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("target"))?
+            .extract()?;
+        let requirement: String = dict
+            .get_item("requirement")?
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("requirement"))?
+            .extract()?;
+
+        let mut constraint = Constraint::new(&target, &requirement);
+
+        if let Some(affects) = dict.get_item("affects_tags")? {
+            let tag_list: Vec<String> = affects.extract()?;
+            let tag_refs: Vec<&str> = tag_list.iter().map(|s| s.as_str()).collect();
+            constraint = constraint.with_affects(tag_refs);
+        }
+
+        if let Some(severity) = dict.get_item("severity")? {
+            if !severity.is_none() {
+                constraint = constraint
+                    .with_severity(parse_constraint_severity(&severity.extract::<String>()?)?);
+            }
+        }
+
+        if let Some(kind) = dict.get_item("applies_to_kind")? {
+            if !kind.is_none() {
+                constraint =
+                    constraint.with_kind(parse_interface_kind(&kind.extract::<String>()?)?);
+            }
+        }
+
+        if let Some(agents) = dict.get_item("applies_to_agents")? {
+            if !agents.is_none() {
+                constraint.applies_to_agents = agents.extract()?;
+            }
+        }
+
+        constraints.push(constraint);
+    }
+    Ok(constraints)
+}
+
+fn list_to_evidence(list: &Bound<'_, PyList>) -> PyResult<Vec<Evidence>> {
+    let mut evidence = Vec::new();
+    for item in list.iter() {
+        let dict: &Bound<'_, PyDict> = item.cast()?;
+        check_keys(dict, &["kind", "description", "source_agent"], "evidence")?;
+        let kind_str: String = dict
+            .get_item("kind")?
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("kind"))?
+            .extract()?;
+        let description: String = dict
+            .get_item("description")?
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("description"))?
+            .extract()?;
+        let source_agent: Option<String> = match dict.get_item("source_agent")? {
+            Some(v) => v.extract()?,
+            None => None,
+        };
+
+        let mut ev = match kind_str.as_str() {
+            "test_pass" => Evidence::test_pass(&description),
+            "test_fail" => Evidence::conflict(&description), // test_fail treated as negative evidence
+            "code_committed" => Evidence::code_committed(&description),
+            // Without an explicit source_agent the description is the agent id
+            "consumed_by" => match &source_agent {
+                Some(agent) => Evidence {
+                    description,
+                    ..Evidence::consumed_by(agent)
+                },
+                None => Evidence::consumed_by(&description),
+            },
+            "conflict" => Evidence::conflict(&description),
+            "manual_approval" => Evidence::manual_approval(),
+            other => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Unknown EvidenceKind: '{}'. Expected one of: test_pass, test_fail, code_committed, consumed_by, conflict, manual_approval",
+                    other
+                )));
+            }
+        };
+        if source_agent.is_some() {
+            ev.source_agent = source_agent;
+        }
+        evidence.push(ev);
+    }
+    Ok(evidence)
+}
+
+fn intent_to_dict<'py>(py: Python<'py>, intent: &IntentNode) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", &intent.id)?;
+    dict.set_item("agent_id", &intent.agent_id)?;
+    dict.set_item("timestamp", intent.timestamp.to_rfc3339())?;
+    dict.set_item("intent", &intent.intent)?;
+    dict.set_item("stability", intent.stability)?;
+    dict.set_item("parent_id", &intent.parent_id)?;
+    dict.set_item("labels", &intent.labels)?;
+
+    // Serialize provides
+    let provides = PyList::empty(py);
+    for spec in &intent.provides {
+        provides.append(spec_to_dict(py, spec)?)?;
+    }
+    dict.set_item("provides", provides)?;
+
+    // Serialize requires
+    let requires = PyList::empty(py);
+    for spec in &intent.requires {
+        requires.append(spec_to_dict(py, spec)?)?;
+    }
+    dict.set_item("requires", requires)?;
+
+    // Serialize constraints
+    let constraints = PyList::empty(py);
+    for c in &intent.constraints {
+        constraints.append(constraint_to_dict(py, c)?)?;
+    }
+    dict.set_item("constraints", constraints)?;
+
+    Ok(dict)
+}
+
+fn spec_to_dict<'py>(py: Python<'py>, spec: &InterfaceSpec) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    d.set_item("name", &spec.name)?;
+    d.set_item("kind", format!("{:?}", spec.kind))?;
+    d.set_item("signature", &spec.signature)?;
+    d.set_item("module_path", &spec.module_path)?;
+    d.set_item("tags", &spec.tags)?;
+    d.set_item("deprecated", spec.deprecated)?;
+    d.set_item("replaced_by", &spec.replaced_by)?;
+    Ok(d)
+}
+
+fn constraint_to_dict<'py>(py: Python<'py>, c: &Constraint) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    d.set_item("target", &c.target)?;
+    d.set_item("requirement", &c.requirement)?;
+    d.set_item("affects_tags", &c.affects_tags)?;
+    d.set_item("severity", c.severity.as_str())?;
+    d.set_item(
+        "applies_to_kind",
+        c.applies_to_kind.as_ref().map(|k| k.as_str()),
+    )?;
+    d.set_item("applies_to_agents", &c.applies_to_agents)?;
+    Ok(d)
+}
+
+fn spec_diff_to_dict<'py>(py: Python<'py>, diff: &SpecDiff) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    let added = PyList::empty(py);
+    for spec in &diff.added {
+        added.append(spec_to_dict(py, spec)?)?;
+    }
+    let removed = PyList::empty(py);
+    for spec in &diff.removed {
+        removed.append(spec_to_dict(py, spec)?)?;
+    }
+    let changed = PyList::empty(py);
+    for change in &diff.changed {
+        let c = PyDict::new(py);
+        c.set_item("before", spec_to_dict(py, &change.before)?)?;
+        c.set_item("after", spec_to_dict(py, &change.after)?)?;
+        c.set_item("signature_compatible", change.signature_compatible)?;
+        changed.append(c)?;
+    }
+    d.set_item("added", added)?;
+    d.set_item("removed", removed)?;
+    d.set_item("changed", changed)?;
+    Ok(d)
+}
+
+fn intent_diff_to_dict<'py>(py: Python<'py>, diff: &IntentDiff) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("provides", spec_diff_to_dict(py, &diff.provides)?)?;
+    dict.set_item("requires", spec_diff_to_dict(py, &diff.requires)?)?;
+
+    let constraints = PyDict::new(py);
+    let added = PyList::empty(py);
+    for c in &diff.constraints.added {
+        added.append(constraint_to_dict(py, c)?)?;
+    }
+    let removed = PyList::empty(py);
+    for c in &diff.constraints.removed {
+        removed.append(constraint_to_dict(py, c)?)?;
+    }
+    let changed = PyList::empty(py);
+    for (before, after) in &diff.constraints.changed {
+        let c = PyDict::new(py);
+        c.set_item("before", constraint_to_dict(py, before)?)?;
+        c.set_item("after", constraint_to_dict(py, after)?)?;
+        changed.append(c)?;
+    }
+    constraints.set_item("added", added)?;
+    constraints.set_item("removed", removed)?;
+    constraints.set_item("changed", changed)?;
+    dict.set_item("constraints", constraints)?;
+    dict.set_item("is_empty", diff.is_empty())?;
+    Ok(dict)
+}
+
+fn resolution_to_dict<'py>(
+    py: Python<'py>,
+    result: &ResolutionResult,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("original_intent", &result.original_intent)?;
+    dict.set_item("is_clean", result.is_clean())?;
+    dict.set_item("has_adjustments", result.has_adjustments())?;
+    dict.set_item("outcome", result.outcome().as_str())?;
+
+    let adj_list = PyList::empty(py);
+    for adj in &result.adjustments {
+        adj_list.append(adjustment_to_dict(py, adj)?)?;
+    }
+    dict.set_item("adjustments", adj_list)?;
+
+    let conflict_list = PyList::empty(py);
+    for conflict in &result.conflicts {
+        conflict_list.append(conflict_to_dict(py, conflict)?)?;
+    }
+    dict.set_item("conflicts", conflict_list)?;
+
+    let constraint_list = PyList::empty(py);
+    for c in &result.adopted_constraints {
+        let d = PyDict::new(py);
+        d.set_item("target", &c.target)?;
+        d.set_item("requirement", &c.requirement)?;
+        d.set_item("severity", c.severity.as_str())?;
+        constraint_list.append(d)?;
+    }
+    dict.set_item("adopted_constraints", constraint_list)?;
+
+    let rejected_list = PyList::empty(py);
+    for (c, source_intent_id) in &result.rejected_constraints {
+        let d = PyDict::new(py);
+        d.set_item("target", &c.target)?;
+        d.set_item("requirement", &c.requirement)?;
+        d.set_item("severity", c.severity.as_str())?;
+        d.set_item("source_intent_id", source_intent_id)?;
+        rejected_list.append(d)?;
+    }
+    dict.set_item("rejected_constraints", rejected_list)?;
+    dict.set_item("notes", &result.notes)?;
+
+    Ok(dict)
+}
+
+fn adjustment_to_dict<'py>(py: Python<'py>, adj: &Adjustment) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    d.set_item("kind", format!("{:?}", adj.kind))?;
+    d.set_item("description", &adj.description)?;
+    d.set_item("source_intent_id", &adj.source_intent_id)?;
+    match &adj.signature_diff {
+        Some(diff) => d.set_item("signature_diff", signature_diff_to_dict(py, diff)?)?,
+        None => d.set_item("signature_diff", py.None())?,
+    }
+    match &adj.adaptation {
+        Some(adaptation) => {
+            let a = PyDict::new(py);
+            a.set_item("interface_name", &adaptation.interface_name)?;
+            a.set_item("current_signature", &adaptation.current_signature)?;
+            a.set_item("target_signature", &adaptation.target_signature)?;
+            a.set_item("missing_fields", &adaptation.missing_fields)?;
+            a.set_item(
+                "type_mismatches",
+                type_mismatches_to_list(py, &adaptation.type_mismatches)?,
+            )?;
+            d.set_item("adaptation", a)?
+        }
+        None => d.set_item("adaptation", py.None())?,
+    }
+    Ok(d)
+}
+
+fn type_mismatches_to_list<'py>(
+    py: Python<'py>,
+    mismatches: &[crate::matching::TypeMismatch],
+) -> PyResult<Bound<'py, PyList>> {
+    let list = PyList::empty(py);
+    for m in mismatches {
+        let md = PyDict::new(py);
+        md.set_item("field", &m.field)?;
+        md.set_item("expected", &m.expected)?;
+        md.set_item("found", &m.found)?;
+        list.append(md)?;
+    }
+    Ok(list)
+}
+
+fn signature_diff_to_dict<'py>(
+    py: Python<'py>,
+    diff: &crate::matching::SignatureDiff,
+) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    d.set_item("missing", &diff.missing)?;
+    d.set_item("mismatched", type_mismatches_to_list(py, &diff.mismatched)?)?;
+    d.set_item("extra", &diff.extra)?;
+    d.set_item("compatible", diff.is_compatible())?;
+    Ok(d)
+}
+
+fn conflict_to_dict<'py>(
+    py: Python<'py>,
+    conflict: &ConflictReport,
+) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    d.set_item("my_intent_id", &conflict.my_intent_id)?;
+    d.set_item("their_intent_id", &conflict.their_intent_id)?;
+    d.set_item("description", &conflict.description)?;
+    d.set_item("my_stability", conflict.my_stability)?;
+    d.set_item("their_stability", conflict.their_stability)?;
+    d.set_item("winner", conflict.winner.as_str())?;
+    d.set_item("resolution_suggestion", &conflict.resolution_suggestion)?;
+    Ok(d)
+}
+
+// ── matching submodule ──────────────────────────────────────────────
+
+/// Jaccard similarity of two tag lists, 0.0 when both are empty.
+#[pyfunction]
+fn tag_similarity(a: Vec<String>, b: Vec<String>) -> f64 {
+    crate::matching::tag_similarity(&a, &b)
+}
+
+/// Field-level diff of provided signature `b` against expected signature `a`.
+#[pyfunction]
+fn signature_diff<'py>(py: Python<'py>, a: &str, b: &str) -> PyResult<Bound<'py, PyDict>> {
+    signature_diff_to_dict(py, &crate::matching::signature_diff(a, b))
+}
+
+fn matching_module(py: Python<'_>) -> PyResult<Bound<'_, PyModule>> {
+    let m = PyModule::new(py, "matching")?;
+    m.add_function(wrap_pyfunction!(tag_similarity, &m)?)?;
+    m.add_function(wrap_pyfunction!(signature_diff, &m)?)?;
+    Ok(m)
+}
+
+/// Python module definition
+#[pymodule]
+fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyIntentGraph>()?;
+    m.add_class::<PyStabilityScorer>()?;
+    m.add_class::<PyInterfaceSpec>()?;
+    m.add_class::<PyExclusive>()?;
+    m.add("ValidationError", m.py().get_type::<ValidationError>())?;
+    m.add_function(wrap_pyfunction!(set_strict, m)?)?;
+    m.add_function(wrap_pyfunction!(is_strict, m)?)?;
+    m.add_function(wrap_pyfunction!(intent_schema, m)?)?;
+    m.add_submodule(&matching_module(m.py())?)?;
+    Ok(())
+}
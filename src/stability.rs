@@ -1,3 +1,9 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
 use crate::models::{EvidenceKind, IntentNode};
 
 #[cfg(test)]
@@ -5,6 +11,11 @@ use crate::models::Evidence;
 
 /// Weights for stability computation.
 /// These are tunable — start conservative and adjust based on real usage.
+///
+/// Serializable so a team's scoring policy can live in version control; see
+/// [`StabilityScorer::from_json`]. Missing fields take their default values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct StabilityWeights {
     pub base: f64,
     pub test_pass: f64,
@@ -16,6 +27,38 @@ pub struct StabilityWeights {
     pub manual_approval: f64,
 }
 
+impl StabilityWeights {
+    /// Check that every weight is finite and non-negative and that `base` is
+    /// a valid score. Penalties are magnitudes, subtracted by the scorer.
+    pub fn validate(&self) -> Result<(), String> {
+        let fields = [
+            ("base", self.base),
+            ("test_pass", self.test_pass),
+            ("test_pass_cap", self.test_pass_cap),
+            ("code_committed", self.code_committed),
+            ("consumed_by_other", self.consumed_by_other),
+            ("consumed_cap", self.consumed_cap),
+            ("conflict_penalty", self.conflict_penalty),
+            ("manual_approval", self.manual_approval),
+        ];
+        for (name, value) in fields {
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!(
+                    "weight '{}' must be finite and non-negative, got {}",
+                    name, value
+                ));
+            }
+        }
+        if self.base > 1.0 {
+            return Err(format!(
+                "weight 'base' must be at most 1.0, got {}",
+                self.base
+            ));
+        }
+        Ok(())
+    }
+}
+
 impl Default for StabilityWeights {
     fn default() -> Self {
         Self {
@@ -31,24 +74,130 @@ impl Default for StabilityWeights {
     }
 }
 
+/// Scores intents from their evidence. Cheap to clone, so one scoring policy
+/// can be built once and handed to several graphs.
+#[derive(Debug, Clone)]
 pub struct StabilityScorer {
     weights: StabilityWeights,
+    saturation: Saturation,
+    conflict_half_life: Option<Duration>,
+}
+
+/// How [`StabilityScorer::compute`] maps the raw weighted sum into [0.0, 1.0].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Saturation {
+    /// Clamp to [0.0, 1.0]: every raw score past 1.0 reads exactly 1.0
+    #[default]
+    Clamp,
+    /// Leave raw scores up to [`SOFT_SATURATION_KNEE`] unchanged, then bend
+    /// smoothly towards 1.0 without reaching it, so more evidence always
+    /// scores higher. Negative raw scores still clamp to 0.0.
+    Soft,
+}
+
+/// Raw score above which [`Saturation::Soft`] starts bending towards 1.0.
+pub const SOFT_SATURATION_KNEE: f64 = 0.8;
+
+impl Saturation {
+    /// Lowercase name used in the Python API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Saturation::Clamp => "clamp",
+            Saturation::Soft => "soft",
+        }
+    }
+
+    /// Parse the lowercase name produced by [`as_str`](Self::as_str).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "clamp" => Some(Saturation::Clamp),
+            "soft" => Some(Saturation::Soft),
+            _ => None,
+        }
+    }
+
+    fn apply(&self, raw: f64) -> f64 {
+        match self {
+            Saturation::Clamp => raw.clamp(0.0, 1.0),
+            Saturation::Soft if raw <= SOFT_SATURATION_KNEE => raw.max(0.0),
+            Saturation::Soft => {
+                // Exponential approach with slope 1 at the knee, so the curve
+                // joins the linear part smoothly.
+                let headroom = 1.0 - SOFT_SATURATION_KNEE;
+                1.0 - headroom * (-(raw - SOFT_SATURATION_KNEE) / headroom).exp()
+            }
+        }
+    }
 }
 
 impl StabilityScorer {
     pub fn new() -> Self {
+        Self::with_weights(StabilityWeights::default())
+    }
+
+    pub fn with_weights(weights: StabilityWeights) -> Self {
         Self {
-            weights: StabilityWeights::default(),
+            weights,
+            saturation: Saturation::default(),
+            conflict_half_life: None,
         }
     }
 
-    pub fn with_weights(weights: StabilityWeights) -> Self {
-        Self { weights }
+    /// Choose how raw scores saturate; defaults to [`Saturation::Clamp`].
+    pub fn with_saturation(mut self, saturation: Saturation) -> Self {
+        self.saturation = saturation;
+        self
+    }
+
+    pub fn saturation(&self) -> Saturation {
+        self.saturation
+    }
+
+    /// Let each conflict's penalty halve every `half_life` after the
+    /// conflict was recorded, so a decision recovers once its conflicts are
+    /// history. Unset by default: conflicts count in full forever. Other
+    /// evidence never decays. Non-positive half-lives are ignored. A graph
+    /// rescores live while resolving, but its stored scores only decay on
+    /// [`recompute_all_stability`](crate::graph::IntentGraph::recompute_all_stability).
+    pub fn with_conflict_half_life(mut self, half_life: Duration) -> Self {
+        self.conflict_half_life = (half_life > Duration::zero()).then_some(half_life);
+        self
+    }
+
+    pub fn conflict_half_life(&self) -> Option<Duration> {
+        self.conflict_half_life
+    }
+
+    /// Build a scorer from a JSON weights object, e.g. a checked-in policy
+    /// file. Fails on unknown fields or weights rejected by
+    /// [`StabilityWeights::validate`].
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let weights: StabilityWeights = serde_json::from_str(json)?;
+        weights
+            .validate()
+            .map_err(<serde_json::Error as serde::de::Error>::custom)?;
+        Ok(Self::with_weights(weights))
+    }
+
+    /// Serialize this scorer's weights to a JSON object.
+    pub fn weights_to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&self.weights)
+    }
+
+    pub fn weights(&self) -> &StabilityWeights {
+        &self.weights
     }
 
     /// Compute stability score for an intent based on its evidence.
-    /// Returns a value in [0.0, 1.0].
+    /// Returns a value in [0.0, 1.0]. Conflict decay, if configured, is
+    /// measured against the wall clock; see [`compute_at`](Self::compute_at).
     pub fn compute(&self, intent: &IntentNode) -> f64 {
+        self.compute_at(intent, Utc::now())
+    }
+
+    /// [`compute`](Self::compute) as of `now`, which only matters when a
+    /// [conflict half-life](Self::with_conflict_half_life) is set.
+    pub fn compute_at(&self, intent: &IntentNode, now: DateTime<Utc>) -> f64 {
         let mut score = self.weights.base;
 
         let w = &self.weights;
@@ -70,20 +219,29 @@ impl StabilityScorer {
             score += w.code_committed;
         }
 
-        // Other agents depending on this increases confidence (network effect)
+        // Other agents depending on this increases confidence (network effect).
+        // Count each consumer once so repeated evidence can't reach the cap.
         let dependents = intent
             .evidence
             .iter()
-            .filter(|e| e.kind == EvidenceKind::ConsumedByOther)
-            .count() as f64;
+            .filter_map(|e| e.consumer())
+            .collect::<HashSet<_>>()
+            .len() as f64;
         score += (dependents * w.consumed_by_other).min(w.consumed_cap);
 
-        // Conflicts decrease confidence
-        let conflicts = intent
+        // Conflicts decrease confidence, fading with age when configured
+        let conflicts: f64 = intent
             .evidence
             .iter()
             .filter(|e| e.kind == EvidenceKind::Conflict)
-            .count() as f64;
+            .map(|e| match self.conflict_half_life {
+                Some(half_life) => {
+                    let age = (now - e.timestamp).max(Duration::zero());
+                    0.5f64.powf(age.as_seconds_f64() / half_life.as_seconds_f64())
+                }
+                None => 1.0,
+            })
+            .sum();
         score -= conflicts * w.conflict_penalty;
 
         // Manual approval is a strong signal
@@ -103,7 +261,7 @@ impl StabilityScorer {
             .count() as f64;
         score -= test_fails * w.conflict_penalty;
 
-        score.clamp(0.0, 1.0)
+        self.saturation.apply(score)
     }
 
     /// Batch compute stability for multiple intents.
@@ -121,6 +279,90 @@ impl Default for StabilityScorer {
     }
 }
 
+/// Bounded LRU cache of computed stability scores.
+///
+/// Keyed by intent id plus a fingerprint of its evidence, so an intent whose
+/// evidence changes misses rather than returning a stale score. Holds at most
+/// `capacity` entries; the least recently used one is evicted first.
+#[derive(Debug)]
+pub struct StabilityCache {
+    capacity: usize,
+    entries: HashMap<(String, u64), (f64, u64)>,
+    recency: BTreeMap<u64, (String, u64)>,
+    tick: u64,
+}
+
+impl StabilityCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Return the cached score for `intent`, computing it with `scorer` on a miss.
+    pub fn get_or_compute(&mut self, scorer: &StabilityScorer, intent: &IntentNode) -> f64 {
+        let key = (intent.id.clone(), evidence_fingerprint(intent));
+        self.tick += 1;
+
+        if let Some((score, last_used)) = self.entries.get_mut(&key) {
+            self.recency.remove(last_used);
+            *last_used = self.tick;
+            self.recency.insert(self.tick, key);
+            return *score;
+        }
+
+        let score = scorer.compute(intent);
+        if self.capacity == 0 {
+            return score;
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(key, (score, self.tick));
+        score
+    }
+
+    /// Drop every cached score for the given intent id.
+    pub fn invalidate(&mut self, id: &str) {
+        self.entries.retain(|(entry_id, _), _| entry_id != id);
+        self.recency.retain(|_, (entry_id, _)| entry_id != id);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+fn evidence_fingerprint(intent: &IntentNode) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for e in &intent.evidence {
+        std::mem::discriminant(&e.kind).hash(&mut hasher);
+        e.description.hash(&mut hasher);
+        e.timestamp.hash(&mut hasher);
+        e.source_agent.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,6 +374,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_soft_saturation_keeps_strong_intents_ordered() {
+        // Raw 1.1: base, approval, commit, and the full test-pass cap
+        let mut strong = vec![
+            Evidence::manual_approval(),
+            Evidence::code_committed("landed"),
+        ];
+        strong.extend((0..6).map(|i| Evidence::test_pass(&format!("test {}", i))));
+        let mut stronger = strong.clone();
+        stronger.push(Evidence::consumed_by("agent-b"));
+        stronger.push(Evidence::consumed_by("agent-c"));
+
+        let clamp = StabilityScorer::new();
+        assert_eq!(clamp.compute(&make_intent(strong.clone())), 1.0);
+        assert_eq!(clamp.compute(&make_intent(stronger.clone())), 1.0);
+
+        let soft = StabilityScorer::new().with_saturation(Saturation::Soft);
+        let a = soft.compute(&make_intent(strong));
+        let b = soft.compute(&make_intent(stronger));
+        assert!(0.95 < a && a < b && b < 1.0, "{} {}", a, b);
+
+        // Scores below the knee are untouched
+        assert!((soft.compute(&make_intent(vec![])) - 0.3).abs() < f64::EPSILON);
+        let conflicted = make_intent(vec![
+            Evidence::conflict("a"),
+            Evidence::conflict("b"),
+            Evidence::conflict("c"),
+        ]);
+        assert_eq!(soft.compute(&conflicted), 0.0);
+    }
+
+    #[test]
+    fn test_conflict_penalty_decays_with_half_life() {
+        let now = Utc::now();
+        let conflict_at = |age: Duration| {
+            let mut e = Evidence::conflict("clashed with agent-b");
+            e.timestamp = now - age;
+            make_intent(vec![e])
+        };
+        let recent = conflict_at(Duration::zero());
+        let one_half_life = conflict_at(Duration::days(7));
+        let ancient = conflict_at(Duration::days(365));
+
+        let decaying = StabilityScorer::new().with_conflict_half_life(Duration::days(7));
+        assert!((decaying.compute_at(&recent, now) - 0.15).abs() < 1e-9);
+        assert!((decaying.compute_at(&one_half_life, now) - 0.225).abs() < 1e-9);
+        assert!((decaying.compute_at(&ancient, now) - 0.3).abs() < 1e-9);
+
+        // Unset keeps the full penalty regardless of age
+        let plain = StabilityScorer::new();
+        assert!((plain.compute_at(&ancient, now) - 0.15).abs() < 1e-9);
+        assert_eq!(
+            StabilityScorer::new()
+                .with_conflict_half_life(Duration::zero())
+                .conflict_half_life(),
+            None
+        );
+    }
+
     #[test]
     fn test_base_stability() {
         let scorer = StabilityScorer::new();
@@ -199,6 +500,56 @@ mod tests {
         assert!((score - 0.7).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_consumed_by_counts_distinct_consumers() {
+        let scorer = StabilityScorer::new();
+        let once = scorer.compute(&make_intent(vec![Evidence::consumed_by("agent-b")]));
+        let repeated = scorer.compute(&make_intent(vec![
+            Evidence::consumed_by("agent-b"),
+            Evidence::consumed_by("agent-b"),
+            Evidence::consumed_by(" agent-b"),
+        ]));
+        // base 0.3 + one consumer 0.1
+        assert!((once - 0.4).abs() < f64::EPSILON);
+        assert!((repeated - once).abs() < f64::EPSILON);
+
+        let distinct = scorer.compute(&make_intent(vec![
+            Evidence::consumed_by("agent-b"),
+            Evidence::consumed_by("agent-b"),
+            Evidence::consumed_by("agent-c"),
+        ]));
+        // base 0.3 + two consumers 0.2 (the cap)
+        assert!((distinct - 0.5).abs() < f64::EPSILON);
+
+        // Evidence without a source agent (recorded before the field existed)
+        // counts once per distinct description
+        let mut free_form = Evidence::consumed_by("x");
+        free_form.description = "billing service".to_string();
+        free_form.source_agent = None;
+        let score = scorer.compute(&make_intent(vec![free_form.clone(), free_form]));
+        assert!((score - 0.4).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_consumed_by_prefers_source_agent() {
+        let scorer = StabilityScorer::new();
+        // Same human-readable description, two different structured consumers
+        let mut by_b = Evidence::consumed_by("agent-b");
+        let mut by_c = Evidence::consumed_by("agent-c");
+        by_b.description = "Wired into checkout".to_string();
+        by_c.description = "Wired into checkout".to_string();
+        assert_eq!(by_b.consumer(), Some("agent-b"));
+
+        let score = scorer.compute(&make_intent(vec![by_b.clone(), by_c]));
+        assert!((score - 0.5).abs() < f64::EPSILON);
+
+        // Different descriptions, same consumer
+        let mut again = by_b.clone();
+        again.description = "Used again".to_string();
+        let score = scorer.compute(&make_intent(vec![by_b, again]));
+        assert!((score - 0.4).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_high_stability_scenario() {
         let scorer = StabilityScorer::new();
@@ -240,4 +591,85 @@ mod tests {
         let score = scorer.compute(&intent);
         assert!((score - 0.0).abs() < f64::EPSILON);
     }
+
+    #[test]
+    fn test_cache_hits_and_evidence_misses() {
+        let scorer = StabilityScorer::new();
+        let mut cache = StabilityCache::new(4);
+        let intent = make_intent(vec![Evidence::code_committed("committed")]);
+
+        assert!((cache.get_or_compute(&scorer, &intent) - 0.5).abs() < f64::EPSILON);
+        assert!((cache.get_or_compute(&scorer, &intent) - 0.5).abs() < f64::EPSILON);
+        assert_eq!(cache.len(), 1);
+
+        // New evidence changes the key, so the stale score is not returned
+        let mut more = intent.clone();
+        more.evidence.push(Evidence::test_pass("unit"));
+        assert!((cache.get_or_compute(&scorer, &more) - 0.55).abs() < f64::EPSILON);
+        assert_eq!(cache.len(), 2);
+
+        cache.invalidate(&intent.id);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cache_keys_on_evidence_source_agent() {
+        let scorer = StabilityScorer::new();
+        let mut cache = StabilityCache::new(4);
+        let by_b = Evidence::consumed_by("agent-b");
+        let intent = make_intent(vec![by_b.clone(), by_b.clone()]);
+        assert!((cache.get_or_compute(&scorer, &intent) - 0.4).abs() < f64::EPSILON);
+
+        // Only the structured consumer differs, which changes the score
+        let mut by_c = by_b.clone();
+        by_c.source_agent = Some("agent-c".to_string());
+        let mut distinct = intent.clone();
+        distinct.evidence = vec![by_b, by_c];
+        assert!((cache.get_or_compute(&scorer, &distinct) - 0.5).abs() < f64::EPSILON);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let scorer = StabilityScorer::new();
+        let mut cache = StabilityCache::new(2);
+        let a = make_intent(vec![]);
+        let b = make_intent(vec![]);
+        let c = make_intent(vec![]);
+
+        cache.get_or_compute(&scorer, &a);
+        cache.get_or_compute(&scorer, &b);
+        cache.get_or_compute(&scorer, &a); // a is now most recent
+        cache.get_or_compute(&scorer, &c); // evicts b
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.entries.keys().any(|(id, _)| *id == a.id));
+        assert!(!cache.entries.keys().any(|(id, _)| *id == b.id));
+    }
+
+    #[test]
+    fn test_weights_json_round_trip() {
+        let scorer = StabilityScorer::with_weights(StabilityWeights {
+            code_committed: 0.4,
+            ..StabilityWeights::default()
+        });
+        let json = scorer.weights_to_json().unwrap();
+        let loaded = StabilityScorer::from_json(&json).unwrap();
+        assert_eq!(loaded.weights(), scorer.weights());
+    }
+
+    #[test]
+    fn test_loaded_weights_affect_compute() {
+        let scorer = StabilityScorer::from_json(r#"{"code_committed": 0.5}"#).unwrap();
+        let intent = make_intent(vec![Evidence::code_committed("committed")]);
+        // base 0.3 (default) + committed 0.5 = 0.8
+        assert!((scorer.compute(&intent) - 0.8).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_from_json_rejects_invalid_weights() {
+        assert!(StabilityScorer::from_json(r#"{"test_pass": -0.1}"#).is_err());
+        assert!(StabilityScorer::from_json(r#"{"base": 1.5}"#).is_err());
+        assert!(StabilityScorer::from_json(r#"{"tset_pass": 0.1}"#).is_err());
+    }
 }
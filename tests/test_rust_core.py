@@ -0,0 +1,1552 @@
+"""Tests for the raw ``convergent._core`` bindings (dict-level API).
+
+Covers behavior that lives in the PyO3 layer itself — argument defaults,
+dict conversion — rather than the ``RustGraphBackend`` wrapper.
+Skipped automatically if ``convergent._core`` is not importable.
+"""
+
+from __future__ import annotations
+
+import json
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+_core = pytest.importorskip("convergent._core")
+
+# ---------------------------------------------------------------------------
+# Helpers
+# ---------------------------------------------------------------------------
+
+
+def _spec(name: str, kind: str = "model", signature: str = "id: UUID") -> dict:
+    return {"name": name, "kind": kind, "signature": signature, "tags": []}
+
+
+def _intent(
+    agent_id: str,
+    intent: str,
+    provides: list[dict] | None = None,
+    requires: list[dict] | None = None,
+    evidence: list[dict] | None = None,
+) -> dict:
+    return {
+        "agent_id": agent_id,
+        "intent": intent,
+        "provides": provides or [],
+        "requires": requires or [],
+        "evidence": evidence or [],
+    }
+
+
+@pytest.fixture
+def graph():
+    """In-memory Rust intent graph."""
+    return _core.IntentGraph()
+
+
+# ---------------------------------------------------------------------------
+# Default stability floor
+# ---------------------------------------------------------------------------
+
+
+class TestDefaultMinStability:
+    def test_resolve_default_excludes_exploring_intent(self, graph):
+        graph.publish(_intent("agent-a", "exploring", provides=[_spec("User")]))
+        mine = _intent("agent-b", "accounts", provides=[_spec("User")])
+
+        result = graph.resolve(mine)
+        assert result["is_clean"]
+        assert not result["has_adjustments"]
+
+        # An explicit floor of 0.0 still sees the exploring intent
+        explicit = graph.resolve(mine, 0.0)
+        assert not explicit["is_clean"] or explicit["has_adjustments"]
+
+    def test_find_overlapping_default_excludes_exploring_intent(self, graph):
+        graph.publish(_intent("agent-a", "exploring", provides=[_spec("User")]))
+        assert graph.find_overlapping([_spec("User")], "agent-b") == []
+        assert len(graph.find_overlapping([_spec("User")], "agent-b", 0.0)) == 1
+
+    def test_resolve_default_includes_committed_intent(self, graph):
+        graph.publish(
+            _intent(
+                "agent-a",
+                "committed",
+                provides=[_spec("User")],
+                evidence=[
+                    {"kind": "code_committed", "description": "landed"},
+                    {"kind": "test_pass", "description": "unit"},
+                ],
+            )
+        )
+        result = graph.resolve(_intent("agent-b", "accounts", provides=[_spec("User")]))
+        assert result["adjustments"][0]["kind"] == "ConsumeInstead"
+
+
+# ---------------------------------------------------------------------------
+# publish_verbose
+# ---------------------------------------------------------------------------
+
+
+class TestPublishVerbose:
+    def test_reports_normalized_name_collision(self, graph):
+        stats = graph.publish_verbose(
+            _intent("agent-a", "models", provides=[_spec("User"), _spec("UserModel")])
+        )
+        assert graph.count() == 1
+        assert isinstance(stats["stability"], float)
+        assert stats["warnings"] == [
+            {"section": "provides", "normalized_name": "user", "names": ["User", "UserModel"]}
+        ]
+
+    def test_clean_intent_has_no_warnings(self, graph):
+        stats = graph.publish_verbose(_intent("agent-a", "models", provides=[_spec("User")]))
+        assert stats["warnings"] == []
+
+
+# ---------------------------------------------------------------------------
+# count_overlapping
+# ---------------------------------------------------------------------------
+
+
+class TestCountOverlapping:
+    def test_matches_find_overlapping(self, graph):
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")]))
+        graph.publish(_intent("agent-b", "accounts", requires=[_spec("UserModel")]))
+        graph.publish(_intent("agent-c", "orders", provides=[_spec("Order")]))
+
+        specs = [_spec("User")]
+        found = graph.find_overlapping(specs, "agent-x", 0.0)
+        assert graph.count_overlapping(specs, "agent-x", 0.0) == len(found) == 2
+
+
+# ---------------------------------------------------------------------------
+# query_since timestamp parsing
+# ---------------------------------------------------------------------------
+
+
+class TestQuerySince:
+    @pytest.mark.parametrize(
+        "since",
+        [
+            "2000-01-01T00:00:00Z",
+            "2000-01-01T00:00:00+00:00",
+            "2000-01-01T02:00:00+02:00",
+            "1999-12-31T19:00:00-05:00",
+            datetime(2000, 1, 1, tzinfo=timezone.utc),
+            datetime(2000, 1, 1, 2, tzinfo=timezone(timedelta(hours=2))),
+            datetime(2000, 1, 1),
+        ],
+    )
+    def test_accepted_forms_agree(self, graph, since):
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")]))
+        results = graph.query_since(since)
+        assert [r["intent"] for r in results] == ["users"]
+
+    def test_future_excludes_everything(self, graph):
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")]))
+        assert graph.query_since("2999-01-01T00:00:00Z") == []
+        assert graph.query_since(datetime(2999, 1, 1)) == []
+
+    def test_naive_string_rejected(self, graph):
+        with pytest.raises(ValueError, match="no UTC offset"):
+            graph.query_since("2000-01-01T00:00:00")
+
+    def test_malformed_string_rejected(self, graph):
+        with pytest.raises(ValueError, match="Invalid RFC 3339 timestamp"):
+            graph.query_since("yesterday")
+
+
+# ---------------------------------------------------------------------------
+# adopt_policy
+# ---------------------------------------------------------------------------
+
+
+class TestAdoptPolicy:
+    def _publish_constraint(self, graph, severity: str) -> None:
+        provider = _intent(
+            "agent-a",
+            "auth",
+            evidence=[{"kind": "code_committed", "description": "landed"}],
+        )
+        provider["constraints"] = [
+            {
+                "target": "User model",
+                "requirement": "must have email",
+                "affects_tags": ["user"],
+                "severity": severity,
+            }
+        ]
+        graph.publish(provider)
+
+    def _consumer(self) -> dict:
+        spec = _spec("Profile")
+        spec["tags"] = ["user"]
+        return _intent("agent-b", "profiles", provides=[spec])
+
+    def test_default_adopts_preferred(self, graph):
+        self._publish_constraint(graph, "preferred")
+        result = graph.resolve(self._consumer(), 0.0)
+        assert len(result["adopted_constraints"]) == 1
+        assert result["adopted_constraints"][0]["severity"] == "preferred"
+        assert result["notes"] == []
+
+    def test_critical_only_drops_preferred(self, graph):
+        self._publish_constraint(graph, "preferred")
+        result = graph.resolve(self._consumer(), 0.0, adopt_policy="critical_only")
+        assert result["adopted_constraints"] == []
+        assert not result["has_adjustments"]
+        assert len(result["notes"]) == 1
+
+    def test_unknown_policy_rejected(self, graph):
+        with pytest.raises(ValueError, match="adopt_policy"):
+            graph.resolve(self._consumer(), 0.0, adopt_policy="strict")
+
+
+# ---------------------------------------------------------------------------
+# Deprecated interfaces
+# ---------------------------------------------------------------------------
+
+
+class TestDeprecation:
+    def test_round_trip(self, graph):
+        spec = _spec("User")
+        spec.update(deprecated=True, replaced_by="Account")
+        graph.publish(_intent("agent-a", "legacy", provides=[spec]))
+        stored = graph.query_all()[0]["provides"][0]
+        assert stored["deprecated"] is True
+        assert stored["replaced_by"] == "Account"
+
+    def test_consuming_deprecated_suggests_migration(self, graph):
+        spec = _spec("User")
+        spec.update(deprecated=True, replaced_by="Account")
+        graph.publish(_intent("agent-a", "legacy", provides=[spec]))
+
+        result = graph.resolve(_intent("agent-b", "profiles", requires=[_spec("User")]), 0.0)
+        assert [a["kind"] for a in result["adjustments"]] == ["MigrateOff"]
+        assert "Account" in result["adjustments"][0]["description"]
+
+
+# ---------------------------------------------------------------------------
+# include_self
+# ---------------------------------------------------------------------------
+
+
+class TestIncludeSelf:
+    def test_self_overlap_opt_in(self, graph):
+        graph.publish(_intent("agent-a", "left", provides=[_spec("User")]))
+        graph.publish(_intent("agent-a", "right", provides=[_spec("UserModel")]))
+
+        assert graph.find_overlapping([_spec("User")], "agent-a", 0.0) == []
+        found = graph.find_overlapping([_spec("User")], "agent-a", 0.0, include_self=True)
+        assert {i["intent"] for i in found} == {"left", "right"}
+
+
+# ---------------------------------------------------------------------------
+# write_conflicts_ndjson
+# ---------------------------------------------------------------------------
+
+
+class TestConflictsNdjson:
+    def test_writes_one_report_per_line(self, graph, tmp_path):
+        for agent, requirement in [("agent-a", "ids must be UUID"), ("agent-b", "ids must be int")]:
+            spec = _spec(f"{agent}-thing")
+            spec["tags"] = ["user"]
+            intent = _intent(agent, "ids", provides=[spec])
+            intent["constraints"] = [
+                {"target": "User model", "requirement": requirement, "affects_tags": ["user"]}
+            ]
+            graph.publish(intent)
+
+        path = tmp_path / "conflicts.ndjson"
+        written = graph.write_conflicts_ndjson(str(path), 0.0)
+
+        lines = path.read_text().splitlines()
+        assert written == len(lines) == 2
+        reports = [json.loads(line) for line in lines]
+        assert {r["my_intent_id"] for r in reports} == {r["their_intent_id"] for r in reports}
+
+
+# ---------------------------------------------------------------------------
+# StabilityScorer weights
+# ---------------------------------------------------------------------------
+
+
+class TestScorerWeights:
+    def _committed(self) -> dict:
+        return _intent(
+            "agent-a", "done", evidence=[{"kind": "code_committed", "description": "landed"}]
+        )
+
+    def test_dict_and_json_agree(self):
+        from_dict = _core.StabilityScorer({"code_committed": 0.5})
+        from_json = _core.StabilityScorer('{"code_committed": 0.5}')
+        assert from_dict.compute(self._committed()) == pytest.approx(0.8)
+        assert from_json.weights_to_json() == from_dict.weights_to_json()
+
+    def test_round_trip(self):
+        scorer = _core.StabilityScorer({"test_pass": 0.1})
+        again = _core.StabilityScorer(scorer.weights_to_json())
+        assert json.loads(again.weights_to_json()) == json.loads(scorer.weights_to_json())
+
+    def test_negative_weight_rejected(self):
+        with pytest.raises(ValueError, match="test_pass"):
+            _core.StabilityScorer({"test_pass": -1.0})
+
+    def test_soft_saturation_orders_strong_intents(self):
+        tests = [{"kind": "test_pass", "description": f"t{i}"} for i in range(6)]
+        strong = tests + [
+            {"kind": "code_committed", "description": "landed"},
+            {"kind": "manual_approval", "description": "ok"},
+        ]
+        stronger = strong + [{"kind": "consumed_by", "description": "agent-b"}]
+
+        clamp = _core.StabilityScorer()
+        assert clamp.compute(_intent("a", "x", evidence=strong)) == 1.0
+        assert clamp.compute(_intent("a", "x", evidence=stronger)) == 1.0
+
+        soft = _core.StabilityScorer(saturation="soft")
+        a = soft.compute(_intent("a", "x", evidence=strong))
+        b = soft.compute(_intent("a", "x", evidence=stronger))
+        assert 0.9 < a < b < 1.0
+
+    def test_unknown_saturation_rejected(self):
+        with pytest.raises(ValueError, match="saturation"):
+            _core.StabilityScorer(saturation="logistic")
+
+    def test_one_scorer_seeds_several_graphs(self, tmp_path):
+        scorer = _core.StabilityScorer({"code_committed": 0.5})
+        graphs = [
+            _core.IntentGraph(str(tmp_path / "billing.db"), scorer=scorer),
+            _core.IntentGraph(scorer=scorer),
+        ]
+        scores = [g.publish(self._committed()) for g in graphs]
+        assert scores == [pytest.approx(0.8)] * 2
+
+
+# ---------------------------------------------------------------------------
+# validate_lineage
+# ---------------------------------------------------------------------------
+
+
+class TestValidateLineage:
+    def test_self_parent_detected(self, graph):
+        a = _intent("agent-a", "a")
+        a.update(id="a", parent_id="a")
+        graph.publish(a)
+        assert graph.validate_lineage() == [["a"]]
+
+    def test_valid_lineage_is_empty(self, graph):
+        root = _intent("agent-a", "root")
+        root["id"] = "root"
+        child = _intent("agent-a", "child")
+        child["parent_id"] = "root"
+        graph.publish(root)
+        graph.publish(child)
+        assert graph.validate_lineage() == []
+
+
+# ---------------------------------------------------------------------------
+# index_health
+# ---------------------------------------------------------------------------
+
+
+class TestIndexHealth:
+    def test_consistent_graph_is_healthy(self, graph):
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")], requires=[_spec("Db")]))
+        health = graph.index_health()
+        assert health == {
+            "interface_rows": 2,
+            "orphaned_rows": 0,
+            "intents_missing_entries": 0,
+            "healthy": True,
+        }
+
+
+# ---------------------------------------------------------------------------
+# publish_with_stability
+# ---------------------------------------------------------------------------
+
+
+class TestPublishWithStability:
+    def test_supplied_value_is_stored(self, graph):
+        graph.publish_with_stability(_intent("agent-a", "imported"), 0.9)
+        assert len(graph.query_all(0.9)) == 1
+        assert len(graph.query_all(0.95)) == 0
+
+
+# ---------------------------------------------------------------------------
+# most_consumed
+# ---------------------------------------------------------------------------
+
+
+class TestMostConsumed:
+    def test_ranked_by_consumer_count(self, graph):
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")]))
+        graph.publish(_intent("agent-b", "orders", provides=[_spec("Order")]))
+        graph.publish(_intent("agent-c", "c1", requires=[_spec("User"), _spec("Order")]))
+        graph.publish(_intent("agent-d", "c2", requires=[_spec("User")]))
+
+        ranked = graph.most_consumed(10, 0.0)
+        assert [(r["intent"]["intent"], r["consumers"]) for r in ranked] == [
+            ("users", 2),
+            ("orders", 1),
+        ]
+
+
+# ---------------------------------------------------------------------------
+# summary conflict_count
+# ---------------------------------------------------------------------------
+
+
+class TestSummaryConflicts:
+    def test_conflict_count(self, graph):
+        assert graph.summary()["conflict_count"] == 0
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")]))
+        graph.publish(_intent("agent-b", "users too", provides=[_spec("User")]))
+        assert graph.summary()["conflict_count"] == 1
+
+
+# ---------------------------------------------------------------------------
+# publish_and_check
+# ---------------------------------------------------------------------------
+
+
+class TestPublishAndCheck:
+    def test_duplicate_provision_reported(self, graph):
+        stability, conflicts = graph.publish_and_check(
+            _intent("agent-a", "users", provides=[_spec("User")]), 0.0
+        )
+        assert conflicts == []
+
+        stability, conflicts = graph.publish_and_check(
+            _intent("agent-b", "users too", provides=[_spec("User")]), 0.0
+        )
+        assert stability == pytest.approx(0.3)
+        assert len(conflicts) == 1
+        assert graph.count() == 2
+
+
+# ---------------------------------------------------------------------------
+# outstanding_adjustments
+# ---------------------------------------------------------------------------
+
+
+class TestOutstandingAdjustments:
+    def test_duplicate_provision_owes_consume_instead(self, graph):
+        graph.publish(
+            _intent(
+                "agent-a",
+                "users",
+                provides=[_spec("User")],
+                evidence=[{"kind": "code_committed", "description": "landed"}],
+            )
+        )
+        graph.publish(_intent("agent-b", "profiles", provides=[_spec("User")]))
+
+        owed = graph.outstanding_adjustments("agent-b", 0.0)
+        assert [a["kind"] for a in owed] == ["ConsumeInstead"]
+        assert owed[0]["intent_id"]
+        assert graph.outstanding_adjustments("agent-a", 0.0) == []
+
+
+# ---------------------------------------------------------------------------
+# query_by_tags
+# ---------------------------------------------------------------------------
+
+
+class TestQueryByTags:
+    @staticmethod
+    def _tagged(name: str, tags: list[str]) -> dict:
+        spec = _spec(name)
+        spec["tags"] = tags
+        return _intent("agent-a", name, provides=[spec])
+
+    def test_all_and_any(self, graph):
+        graph.publish(self._tagged("User", ["auth", "model"]))
+        graph.publish(self._tagged("Session", ["auth"]))
+        graph.publish(self._tagged("Invoice", ["billing", "model"]))
+
+        def names(tags, **kwargs):
+            return [i["intent"] for i in graph.query_by_tags(tags, **kwargs)]
+
+        assert names(["auth", "model"]) == ["User"]
+        assert names(["auth", "billing"], mode="any") == ["User", "Session", "Invoice"]
+        assert names(["auth", "billing"], mode="all") == []
+
+    def test_unknown_mode_raises(self, graph):
+        with pytest.raises(ValueError):
+            graph.query_by_tags(["auth"], mode="some")
+
+
+# ---------------------------------------------------------------------------
+# diff_intents
+# ---------------------------------------------------------------------------
+
+
+class TestDiffIntents:
+    def test_categorizes_changes(self, graph):
+        parent = _intent(
+            "agent-a", "auth", provides=[_spec("User", signature="id: UUID, email: str")]
+        )
+        parent["constraints"] = [
+            {"target": "User model", "requirement": "must have email: str"},
+            {"target": "Sessions", "requirement": "expire after 24h"},
+        ]
+        graph.publish(parent)
+        parent_id = graph.query_by_agent("agent-a")[0]["id"]
+
+        child = _intent(
+            "agent-a",
+            "auth, refined",
+            provides=[_spec("User", signature="id: UUID"), _spec("Token", signature="value: str")],
+        )
+        child["constraints"] = [{"target": "User model", "requirement": "must have email: str"}]
+        child["parent_id"] = parent_id
+        graph.publish(child)
+        child_id = graph.query_by_agent("agent-a")[1]["id"]
+
+        diff = graph.diff_intents(parent_id, child_id)
+        assert [s["name"] for s in diff["provides"]["added"]] == ["Token"]
+        [change] = diff["provides"]["changed"]
+        assert change["before"]["signature"] == "id: UUID, email: str"
+        assert change["signature_compatible"] is False
+        assert [c["target"] for c in diff["constraints"]["removed"]] == ["Sessions"]
+        assert not diff["is_empty"]
+        assert graph.diff_intents(parent_id, parent_id)["is_empty"]
+
+    def test_unknown_id_raises(self, graph):
+        with pytest.raises(KeyError):
+            graph.diff_intents("missing", "missing")
+
+
+# ---------------------------------------------------------------------------
+# source
+# ---------------------------------------------------------------------------
+
+
+class TestSource:
+    def test_in_memory(self, graph):
+        assert graph.source() == {"kind": "memory"}
+
+    def test_file(self, tmp_path):
+        path = str(tmp_path / "graph.db")
+        graph = _core.IntentGraph(path)
+        assert graph.source() == {"kind": "file", "path": path, "read_only": False}
+
+
+# ---------------------------------------------------------------------------
+# structured conflict winner
+# ---------------------------------------------------------------------------
+
+
+class TestConflictWinner:
+    def test_winner_fields(self, graph):
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")]))
+
+        tie = graph.resolve(_intent("agent-b", "users too", provides=[_spec("User")]), 0.0)
+        [conflict] = tie["conflicts"]
+        assert conflict["winner"] == "tie"
+        assert conflict["my_stability"] == conflict["their_stability"]
+
+        mine = _intent(
+            "agent-b",
+            "users, landed",
+            provides=[_spec("User")],
+            evidence=[{"kind": "code_committed", "description": "landed"}],
+        )
+        [conflict] = graph.resolve(mine, 0.0)["conflicts"]
+        assert conflict["winner"] == "me"
+        assert conflict["my_stability"] > conflict["their_stability"]
+
+
+# ---------------------------------------------------------------------------
+# bincode export/import
+# ---------------------------------------------------------------------------
+
+
+class TestBincode:
+    def test_round_trip(self, graph):
+        if not hasattr(graph, "export_bincode"):
+            pytest.skip("built without the bincode feature")
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")]))
+        graph.publish(_intent("agent-b", "orders", requires=[_spec("User")]))
+
+        data = graph.export_bincode()
+        assert isinstance(data, bytes)
+
+        copy = _core.IntentGraph()
+        assert copy.import_bincode(data) == 2
+        assert copy.query_all() == graph.query_all()
+
+    def test_trust_scores_keeps_exported_stability(self, graph):
+        if not hasattr(graph, "export_bincode"):
+            pytest.skip("built without the bincode feature")
+        graph.publish_with_stability({**_intent("agent-a", "users"), "id": "users-1"}, 0.95)
+        data = graph.export_bincode()
+
+        trusted = _core.IntentGraph()
+        trusted.import_bincode(data, trust_scores=True)
+        assert trusted.query_all(min_stability=0.9)[0]["id"] == "users-1"
+
+        rescored = _core.IntentGraph()
+        rescored.import_bincode(data)
+        assert rescored.query_all(min_stability=0.9) == []
+
+
+# ---------------------------------------------------------------------------
+# stability_history
+# ---------------------------------------------------------------------------
+
+
+class TestStabilityHistory:
+    def test_supersession_chain_increases(self, graph):
+        draft = _intent("agent-a", "auth draft")
+        draft["id"] = "draft"
+        graph.publish(draft)
+
+        refined = _intent("agent-a", "auth, refined")
+        refined["id"] = "refined"
+        refined["parent_id"] = "draft"
+        graph.publish(refined)
+        graph.add_evidence("refined", [{"kind": "code_committed", "description": "landed"}])
+
+        history = graph.stability_history("refined")
+        assert len(history) == 3
+        timestamps = [t for t, _ in history]
+        assert timestamps == sorted(timestamps)
+        scores = [s for _, s in history]
+        assert scores[0] == pytest.approx(0.3)
+        assert scores[-1] > scores[0]
+
+    def test_unknown_id_raises(self, graph):
+        with pytest.raises(KeyError):
+            graph.stability_history("missing")
+
+
+# ---------------------------------------------------------------------------
+# strict dict parsing
+# ---------------------------------------------------------------------------
+
+
+class TestStrictMode:
+    @pytest.fixture(autouse=True)
+    def _reset_strict(self):
+        yield
+        _core.set_strict(False)
+
+    def test_misspelled_key(self, graph):
+        typo = _intent("agent-a", "users")
+        typo["provide"] = [_spec("User")]
+
+        assert not _core.is_strict()
+        graph.publish(typo)  # lenient default ignores the typo
+        assert graph.query_by_agent("agent-a")[0]["provides"] == []
+
+        _core.set_strict(True)
+        with pytest.raises(_core.ValidationError, match="'provide'"):
+            graph.publish(typo)
+        assert issubclass(_core.ValidationError, ValueError)
+
+    def test_nested_spec_keys_checked(self, graph):
+        _core.set_strict(True)
+        spec = _spec("User")
+        spec["signture"] = "id: int"
+        with pytest.raises(_core.ValidationError, match="interface spec"):
+            graph.publish(_intent("agent-a", "users", provides=[spec]))
+
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")]))
+        assert graph.count() == 1
+
+
+# ---------------------------------------------------------------------------
+# Signature diffs
+# ---------------------------------------------------------------------------
+
+
+class TestSignatureDiff:
+    def test_missing_and_extra_fields(self):
+        diff = _core.matching.signature_diff("id: UUID, email: str", "id: uuid, name: str")
+        assert diff["missing"] == [("email", "str")]
+        assert diff["mismatched"] == []
+        assert diff["extra"] == [("name", "str")]
+        assert diff["compatible"] is False
+
+    def test_type_mismatch(self):
+        diff = _core.matching.signature_diff("id: UUID", "id: int")
+        assert diff["mismatched"] == [{"field": "id", "expected": "uuid", "found": "int"}]
+
+    def test_adapt_signature_adjustment(self, graph):
+        graph.publish(
+            _intent(
+                "agent-a",
+                "users",
+                provides=[_spec("User", signature="id: UUID")],
+                evidence=[{"kind": "code_committed", "description": "landed"}],
+            )
+        )
+        result = graph.resolve(
+            _intent("agent-b", "profiles", requires=[_spec("User", signature="id: int")]), 0.0
+        )
+        [adapt] = [a for a in result["adjustments"] if a["kind"] == "AdaptSignature"]
+        assert adapt["signature_diff"]["mismatched"][0]["field"] == "id"
+        assert adapt["adaptation"] == {
+            "interface_name": "User",
+            "current_signature": "id: int",
+            "target_signature": "id: UUID",
+            "missing_fields": [],
+            "type_mismatches": [{"field": "id", "expected": "int", "found": "uuid"}],
+        }
+
+
+# ---------------------------------------------------------------------------
+# Configurable default stability
+# ---------------------------------------------------------------------------
+
+
+class TestDefaultStability:
+    def test_omitted_stability_uses_graph_default(self):
+        graph = _core.IntentGraph(default_stability=0.1)
+        graph.publish(_intent("agent-a", "exploring"))
+        stated = _intent("agent-b", "committed")
+        stated["stability"] = 0.8
+        graph.publish(stated)
+
+        assert graph.query_by_agent("agent-a")[0]["stability"] == pytest.approx(0.1)
+        assert graph.query_by_agent("agent-b")[0]["stability"] == pytest.approx(0.8)
+
+    def test_baseline_default(self, graph):
+        graph.publish(_intent("agent-a", "exploring"))
+        assert graph.query_by_agent("agent-a")[0]["stability"] == pytest.approx(0.3)
+
+    def test_fork_keeps_default(self):
+        fork = _core.IntentGraph(default_stability=0.1).fork()
+        fork.publish(_intent("agent-a", "exploring"))
+        assert fork.query_by_agent("agent-a")[0]["stability"] == pytest.approx(0.1)
+
+    def test_out_of_range_rejected(self):
+        with pytest.raises(ValueError, match="default_stability"):
+            _core.IntentGraph(default_stability=1.5)
+
+
+# ---------------------------------------------------------------------------
+# Batched resolve
+# ---------------------------------------------------------------------------
+
+
+class TestResolveBatch:
+    def test_matches_individual_resolves(self, graph):
+        committed = [{"kind": "code_committed", "description": "landed"}]
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")], evidence=committed))
+        graph.publish(_intent("agent-c", "orders", requires=[_spec("User", signature="id: str")]))
+
+        candidates = [
+            _intent("agent-b", "own users", provides=[_spec("User")]),
+            _intent("agent-b", "consume users", requires=[_spec("User", signature="id: int")]),
+            _intent("agent-b", "unrelated", provides=[_spec("Invoice")]),
+        ]
+        for c in candidates:
+            c["id"] = c["intent"]
+
+        batched = graph.resolve_batch(candidates, 0.0)
+        assert batched == [graph.resolve(c, 0.0) for c in candidates]
+        assert [r["original_intent"] for r in batched] == [c["id"] for c in candidates]
+        assert graph.resolve_batch([], 0.0) == []
+
+
+# ---------------------------------------------------------------------------
+# Pre-publish validation
+# ---------------------------------------------------------------------------
+
+
+class TestValidateIntent:
+    def test_clean_intent(self, graph):
+        assert graph.validate_intent(_intent("agent-a", "users", provides=[_spec("User")])) == []
+
+    def test_issue_dicts(self, graph):
+        intent = _intent(
+            "agent-a",
+            "users",
+            provides=[_spec("User", signature="id UUID"), _spec("UserModel")],
+        )
+        intent["parent_id"] = "missing"
+
+        issues = graph.validate_intent(intent)
+        assert [(i["severity"], i["kind"]) for i in issues] == [
+            ("error", "dangling_parent"),
+            ("warning", "unparseable_signature"),
+            ("warning", "duplicate_name"),
+        ]
+        assert "'missing'" in issues[0]["message"]
+        assert graph.count() == 0
+
+
+# ---------------------------------------------------------------------------
+# Tag inventory
+# ---------------------------------------------------------------------------
+
+
+class TestAllTags:
+    def test_counts(self, graph):
+        assert graph.all_tags() == {}
+        auth = dict(_spec("Session"), tags=["auth", "session"])
+        user = dict(_spec("User"), tags=["authentication", "auth"])
+        graph.publish(_intent("agent-a", "auth", provides=[auth], requires=[user]))
+
+        tags = graph.all_tags()
+        assert tags == {"auth": 2, "authentication": 1, "session": 1}
+        assert list(tags) == ["auth", "authentication", "session"]
+
+
+# ---------------------------------------------------------------------------
+# Publish guards
+# ---------------------------------------------------------------------------
+
+
+class TestPublishGuard:
+    def test_falsy_return_blocks(self, graph):
+        graph.register_publish_guard(lambda intent: intent["stability"] >= 0.2)
+
+        low = _intent("agent-a", "sketch")
+        low["stability"] = 0.1
+        with pytest.raises(_core.ValidationError, match="rejected by publish guard"):
+            graph.publish(low)
+        assert graph.count() == 0
+
+        graph.publish(_intent("agent-a", "auth module"))
+        assert graph.count() == 1
+
+    def test_exception_message_surfaces(self, graph):
+        def no_migrations(intent):
+            if any(s["kind"] == "Migration" for s in intent["provides"]):
+                raise ValueError("migrations require manual_approval evidence")
+            return True
+
+        graph.register_publish_guard(no_migrations)
+        migration = _intent("agent-a", "recipes", provides=[_spec("recipes", kind="migration")])
+        with pytest.raises(_core.ValidationError, match="manual_approval"):
+            graph.publish_verbose(migration)
+
+
+# ---------------------------------------------------------------------------
+# Name-match precision
+# ---------------------------------------------------------------------------
+
+
+class TestNameMatch:
+    def _overlaps(self, name_match):
+        graph = _core.IntentGraph(name_match=name_match)
+        graph.publish(_intent("agent-a", "power users", provides=[_spec("PowerUser")]))
+        return graph.find_overlapping([_spec("User")], "agent-b", 0.0)
+
+    def test_exact_drops_substring_matches(self):
+        assert len(self._overlaps("substring")) == 1
+        assert self._overlaps("exact") == []
+
+    def test_unknown_mode_rejected(self):
+        with pytest.raises(ValueError, match="name_match"):
+            _core.IntentGraph(name_match="loose")
+
+
+# ---------------------------------------------------------------------------
+# Clearing a graph
+# ---------------------------------------------------------------------------
+
+
+class TestClear:
+    def test_clear_then_publish(self):
+        graph = _core.IntentGraph(default_stability=0.1)
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")]))
+        graph.clear()
+        assert graph.count() == 0
+        assert graph.all_tags() == {}
+
+        graph.publish(_intent("agent-a", "users again", provides=[_spec("User")]))
+        [intent] = graph.query_all()
+        assert intent["stability"] == pytest.approx(0.1)
+
+
+# ---------------------------------------------------------------------------
+# Structured evidence source
+# ---------------------------------------------------------------------------
+
+
+class TestEvidenceSourceAgent:
+    def _stability(self, evidence):
+        graph = _core.IntentGraph()
+        return graph.publish(_intent("agent-a", "users", evidence=evidence))
+
+    def test_source_agent_drives_distinct_consumers(self):
+        shared = "Wired into checkout"
+        two = self._stability(
+            [
+                {"kind": "consumed_by", "description": shared, "source_agent": "agent-b"},
+                {"kind": "consumed_by", "description": shared, "source_agent": "agent-c"},
+            ]
+        )
+        one = self._stability(
+            [
+                {"kind": "consumed_by", "description": "first", "source_agent": "agent-b"},
+                {"kind": "consumed_by", "description": "again", "source_agent": "agent-b"},
+            ]
+        )
+        assert two == pytest.approx(0.5)
+        assert one == pytest.approx(0.4)
+
+    def test_description_is_agent_without_source_agent(self):
+        stability = self._stability(
+            [
+                {"kind": "consumed_by", "description": "agent-b"},
+                {"kind": "consumed_by", "description": "agent-b"},
+            ]
+        )
+        assert stability == pytest.approx(0.4)
+
+
+# ---------------------------------------------------------------------------
+# Unsatisfied requirements
+# ---------------------------------------------------------------------------
+
+
+class TestUnsatisfiedRequirements:
+    def test_matching_provider_closes_gap(self, graph):
+        user = _spec("User", signature="id: UUID, email: str")
+        graph.publish(_intent("agent-a", "billing", requires=[user]))
+
+        [gap] = graph.unsatisfied_requirements()
+        assert gap["intent_id"] == graph.query_all()[0]["id"]
+        assert gap["agent_id"] == "agent-a"
+        assert gap["interface"]["name"] == "User"
+
+        thin = _spec("User", signature="id: UUID")
+        graph.publish(_intent("agent-b", "thin user", provides=[thin]))
+        assert len(graph.unsatisfied_requirements()) == 1
+
+        graph.publish(
+            _intent("agent-c", "auth", provides=[_spec("User", signature="id: UUID, email: str")])
+        )
+        assert graph.unsatisfied_requirements() == []
+
+
+# ---------------------------------------------------------------------------
+# Filtered JSON export
+# ---------------------------------------------------------------------------
+
+
+class TestExportJsonFiltered:
+    def _exported(self, graph, **kwargs):
+        return [i["intent"] for i in json.loads(graph.export_json_filtered(**kwargs))]
+
+    def test_filters_by_agent_and_tag(self, graph):
+        auth = _spec("User")
+        auth["tags"] = ["auth"]
+        invoice = _spec("Invoice")
+        invoice["tags"] = ["billing"]
+        graph.publish(_intent("agent-a", "auth", provides=[auth]))
+        graph.publish(_intent("agent-b", "billing", provides=[invoice]))
+
+        assert self._exported(graph) == ["auth", "billing"]
+        assert self._exported(graph, agents=["agent-b"]) == ["billing"]
+        assert self._exported(graph, tags=["auth"]) == ["auth"]
+        assert self._exported(graph, agents=["agent-a"], tags=["billing"]) == []
+        assert self._exported(graph, min_stability=0.9) == []
+
+
+# ---------------------------------------------------------------------------
+# Resolve and apply
+# ---------------------------------------------------------------------------
+
+
+class TestResolveAndApply:
+    def _provider(self, graph):
+        graph.publish(
+            _intent(
+                "agent-a",
+                "users",
+                provides=[_spec("User")],
+                evidence=[{"kind": "code_committed", "description": "landed"}],
+            )
+        )
+
+    def test_confident_consume_is_applied(self, graph):
+        self._provider(graph)
+        result = graph.resolve_and_apply(
+            _intent("agent-b", "accounts", provides=[_spec("User")]), auto_apply_threshold=0.5
+        )
+        assert result["adjustments"][0]["kind"] == "ConsumeInstead"
+        assert len(result["notes"]) == 1
+
+        mine = graph.query_by_agent("agent-b")[0]
+        assert mine["provides"] == []
+        assert [s["name"] for s in mine["requires"]] == ["User"]
+
+    def test_below_threshold_is_only_suggested(self, graph):
+        self._provider(graph)
+        result = graph.resolve_and_apply(
+            _intent("agent-b", "accounts", provides=[_spec("User")]), auto_apply_threshold=0.9
+        )
+        assert result["notes"] == []
+        assert [s["name"] for s in graph.query_by_agent("agent-b")[0]["provides"]] == ["User"]
+
+
+# ---------------------------------------------------------------------------
+# Agent id normalization
+# ---------------------------------------------------------------------------
+
+
+class TestNormalizeAgentIds:
+    def _publish_variants(self, graph):
+        for agent in ["Agent-A", "agent-a ", "agent-a"]:
+            graph.publish(_intent(agent, "auth"))
+
+    def test_variants_collapse_to_one_agent(self):
+        graph = _core.IntentGraph(normalize_agent_ids=True)
+        self._publish_variants(graph)
+        summary = graph.summary()
+        assert summary["agent_count"] == 1
+        assert len(graph.query_by_agent("AGENT-A")) == 3
+
+    def test_raw_ids_kept_by_default(self, graph):
+        self._publish_variants(graph)
+        assert graph.summary()["agent_count"] == 3
+
+
+# ---------------------------------------------------------------------------
+# Resolution outcome
+# ---------------------------------------------------------------------------
+
+
+class TestResolutionOutcome:
+    def test_outcome_states(self, graph):
+        clean = graph.resolve(_intent("agent-a", "users", provides=[_spec("User")]))
+        assert clean["outcome"] == "clean"
+
+        committed = [{"kind": "code_committed", "description": "landed"}]
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")], evidence=committed))
+        adjusted = graph.resolve(_intent("agent-b", "users", provides=[_spec("User")]), 0.0)
+        assert adjusted["outcome"] == "adjustments_only"
+
+        conflicted = graph.resolve(
+            _intent("agent-c", "users", provides=[_spec("User")], evidence=committed), 0.0
+        )
+        assert conflicted["outcome"] == "conflicted"
+
+
+# ---------------------------------------------------------------------------
+# Id generators
+# ---------------------------------------------------------------------------
+
+
+class TestIdGenerator:
+    def test_monotonic_ids_for_dicts_without_id(self):
+        graph = _core.IntentGraph(id_generator="monotonic")
+        graph.publish(_intent("agent-a", "auth"))
+        graph.publish(_intent("agent-a", "billing"))
+        explicit = {**_intent("agent-a", "search"), "id": "search-1"}
+        graph.publish(explicit)
+
+        ids = [i["id"] for i in graph.query_by_agent("agent-a")]
+        assert ids == ["intent-000000000001", "intent-000000000002", "search-1"]
+
+    def test_assigned_id_is_written_back(self):
+        graph = _core.IntentGraph(id_generator="monotonic")
+        first = _intent("agent-a", "auth")
+        graph.publish(first)
+        assert first["id"] == "intent-000000000001"
+        graph.add_evidence(first["id"], [{"kind": "test_pass", "description": "unit"}])
+
+        second = _intent("agent-a", "billing")
+        assert graph.publish_verbose(second)["id"] == second["id"] == "intent-000000000002"
+
+    def test_unknown_generator_rejected(self):
+        with pytest.raises(ValueError, match="id_generator"):
+            _core.IntentGraph(id_generator="ulid")
+
+
+# ---------------------------------------------------------------------------
+# Rejected constraints
+# ---------------------------------------------------------------------------
+
+
+class TestRejectedConstraints:
+    def _constrained(self, agent, requirement):
+        spec = _spec(f"{agent}-thing")
+        spec["tags"] = ["user"]
+        intent = _intent(agent, "ids", provides=[spec])
+        intent["constraints"] = [
+            {"target": "User model", "requirement": requirement, "affects_tags": ["user"]}
+        ]
+        return intent
+
+    def test_conflicting_constraint_is_rejected_not_adopted(self, graph):
+        graph.publish(self._constrained("agent-a", "ids must be UUID"))
+        result = graph.resolve(self._constrained("agent-b", "ids must be int"), 0.0)
+
+        [rejected] = result["rejected_constraints"]
+        assert rejected["requirement"] == "ids must be UUID"
+        assert rejected["source_intent_id"] == graph.query_by_agent("agent-a")[0]["id"]
+        assert result["adopted_constraints"] == []
+
+
+# ---------------------------------------------------------------------------
+# Interface provenance
+# ---------------------------------------------------------------------------
+
+
+class TestInterfaceProvenance:
+    def test_earliest_provider_and_requirers(self, graph):
+        graph.publish(_intent("agent-a", "auth", provides=[_spec("User")]))
+        graph.publish(_intent("agent-c", "billing", requires=[_spec("User")]))
+        graph.publish(_intent("agent-b", "accounts", provides=[_spec("UserModel")]))
+
+        provenance = graph.interface_provenance("User")
+        assert provenance["earliest_provider"]["agent_id"] == "agent-a"
+        assert [p["agent_id"] for p in provenance["providers"]] == ["agent-a", "agent-b"]
+        assert [r["agent_id"] for r in provenance["requirers"]] == ["agent-c"]
+        assert graph.interface_provenance("Invoice")["earliest_provider"] is None
+
+
+class TestSimulateConvergence:
+    def test_order_reaches_conflict_free_state(self, graph):
+        committed = [{"kind": "code_committed", "description": "landed"}]
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")], evidence=committed))
+        graph.publish(_intent("agent-b", "accounts", provides=[_spec("User")]))
+        a = graph.query_by_agent("agent-a")[0]["id"]
+        b = graph.query_by_agent("agent-b")[0]["id"]
+
+        report = graph.simulate_convergence([a, b])
+        assert report["initial_conflicts"] == 1
+        assert report["converged"]
+        assert [s["conflicts"] for s in report["steps"]] == [1, 0]
+        assert report["steps"][1]["applied"] == 1
+
+        assert not graph.simulate_convergence([a])["converged"]
+        assert graph.query_by_agent("agent-b")[0]["provides"] != []
+
+    def test_unknown_id_raises(self, graph):
+        with pytest.raises(RuntimeError):
+            graph.simulate_convergence(["missing"])
+
+
+class TestInterfaceSpec:
+    USER = {
+        "name": "User",
+        "kind": "model",
+        "signature": "id: UUID, email: str",
+        "tags": ["user", "auth", "model"],
+    }
+    USER_REF = {
+        "name": "UserRef",
+        "kind": "model",
+        "signature": "user_id: UUID",
+        "tags": ["user", "recipe", "model"],
+    }
+
+    def test_structural_overlap_matches_graph(self):
+        spec = _core.InterfaceSpec(self.USER_REF)
+        assert spec.structurally_overlaps(self.USER)
+        assert spec.structurally_overlaps(_core.InterfaceSpec(self.USER))
+        assert not spec.structurally_overlaps(_spec("Invoice"))
+
+    def test_name_match_mode(self):
+        spec = _core.InterfaceSpec(_spec("User"))
+        assert spec.structurally_overlaps(_spec("UserProfile"))
+        assert not spec.structurally_overlaps(_spec("UserProfile"), name_match="exact")
+        with pytest.raises(ValueError, match="Unknown name_match"):
+            spec.structurally_overlaps(_spec("User"), name_match="loose")
+
+    def test_signature_compatible(self):
+        required = _core.InterfaceSpec(_spec("User", signature="id: UUID"))
+        assert required.signature_compatible(_spec("User", signature="id: UUID, email: str"))
+        assert not required.signature_compatible(_spec("User", signature="email: str"))
+
+    def test_round_trips_to_dict(self):
+        assert _core.InterfaceSpec(self.USER).to_dict()["tags"] == ["user", "auth", "model"]
+
+    def test_to_dict_reads_back(self):
+        spec = _core.InterfaceSpec(self.USER)
+        again = _core.InterfaceSpec(spec.to_dict())
+        assert again.to_dict() == spec.to_dict()
+        assert repr(again) == repr(spec)
+
+
+class TestMigrationsSince:
+    def test_only_migration_providers_after_cutoff(self, graph):
+        graph.publish(
+            _intent("agent-a", "recipes table", provides=[_spec("CreateRecipes", kind="migration")])
+        )
+        graph.publish(
+            _intent("agent-b", "recipe model", requires=[_spec("CreateRecipes", kind="migration")])
+        )
+        graph.publish(_intent("agent-c", "users", provides=[_spec("User")]))
+
+        assert [m["intent"] for m in graph.migrations_since("2000-01-01T00:00:00Z")] == [
+            "recipes table"
+        ]
+        assert graph.migrations_since(datetime(2999, 1, 1)) == []
+
+
+class TestResolveTraced:
+    def test_trace_names_adjusting_pair(self, graph):
+        graph.publish(
+            _intent(
+                "agent-a",
+                "users",
+                provides=[_spec("User", signature="id: UUID, email: str")],
+                evidence=[{"kind": "code_committed", "description": "landed"}],
+            )
+        )
+        mine = _intent("agent-b", "profiles", requires=[_spec("User", signature="id: int")])
+        result = graph.resolve_traced(mine, min_stability=0.0)
+        assert result["adjustments"][0]["kind"] == "AdaptSignature"
+
+        step = next(s for s in result["trace"] if s["outcome"] == "AdaptSignature")
+        assert step["check"] == "required_provision"
+        assert (step["mine"], step["theirs"]) == ("User", "User")
+        assert "signature compatible=false" in step["message"]
+
+    def test_untraced_resolve_has_no_trace(self, graph):
+        assert "trace" not in graph.resolve(_intent("agent-a", "users"))
+
+
+class TestPublishLimits:
+    def test_oversized_signature_rejected(self):
+        graph = _core.IntentGraph(limits={"max_signature_len": 32})
+        blob = _intent("agent-a", "blob", provides=[_spec("Blob", signature="x: int, " * 10)])
+        with pytest.raises(_core.ValidationError, match="the limit is 32"):
+            graph.publish(blob)
+        assert graph.query_all() == []
+
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")]))
+        assert len(graph.query_all()) == 1
+
+    def test_defaults_admit_normal_intents(self, graph):
+        blob = _intent("agent-a", "blob", provides=[_spec("Blob", signature="x: int, " * 10)])
+        graph.publish(blob)
+        assert len(graph.query_all()) == 1
+
+    def test_unknown_limit_rejected(self):
+        with pytest.raises(ValueError, match="Unknown limit: 'max_size'"):
+            _core.IntentGraph(limits={"max_size": 10})
+
+
+class TestAgentStabilityStats:
+    def test_ranked_by_average_stability(self, graph):
+        committed = [{"kind": "code_committed", "description": "landed"}]
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User")], evidence=committed))
+        graph.publish(_intent("agent-b", "billing", evidence=committed))
+        graph.publish(_intent("agent-b", "invoices"))
+        graph.publish(_intent("agent-c", "accounts", provides=[_spec("User")]))
+
+        stats = graph.agent_stability_stats()
+        assert [s["agent_id"] for s in stats] == ["agent-a", "agent-b", "agent-c"]
+        assert stats[1]["intent_count"] == 2
+        assert stats[1]["max_stability"] == stats[0]["average_stability"]
+        assert stats[2]["average_stability"] == pytest.approx(0.3)
+        assert [s["conflict_count"] for s in stats] == [1, 0, 1]
+
+
+class TestFindOverlappingRole:
+    def test_provides_filter_ignores_requirers(self, graph):
+        graph.publish(_intent("agent-a", "auth", provides=[_spec("User")]))
+        graph.publish(_intent("agent-b", "profiles", requires=[_spec("User")]))
+
+        found = graph.find_overlapping([_spec("User")], "agent-c", 0.0, role="provides")
+        assert [i["agent_id"] for i in found] == ["agent-a"]
+        assert len(graph.find_overlapping([_spec("User")], "agent-c", 0.0)) == 2
+
+    def test_unknown_role_rejected(self, graph):
+        with pytest.raises(ValueError, match="Unknown role: 'provider'"):
+            graph.find_overlapping([_spec("User")], "agent-c", role="provider")
+
+
+class TestIntentLabels:
+    def test_labels_round_trip_and_query(self, graph):
+        labelled = _intent("agent-a", "auth")
+        labelled["labels"] = ["sprint-12", "needs-review"]
+        graph.publish(labelled)
+        graph.publish(_intent("agent-b", "billing"))
+
+        found = graph.query_by_label("needs-review")
+        assert [i["intent"] for i in found] == ["auth"]
+        assert found[0]["labels"] == ["sprint-12", "needs-review"]
+        assert graph.query_by_agent("agent-b")[0]["labels"] == []
+        assert graph.query_by_label("sprint-13") == []
+
+
+class TestRenameDetection:
+    def test_renamed_provision_suggested(self, graph):
+        signature = "id: UUID, email: str"
+        graph.publish(
+            _intent(
+                "agent-a",
+                "customers",
+                provides=[_spec("Customer", signature=signature)],
+                evidence=[{"kind": "code_committed", "description": "landed"}],
+            )
+        )
+        mine = _intent("agent-b", "billing", requires=[_spec("Account", signature=signature)])
+        adjustments = graph.resolve(mine, min_stability=0.0)["adjustments"]
+        assert [a["kind"] for a in adjustments] == ["FollowRename"]
+        assert "'Customer'" in adjustments[0]["description"]
+
+
+class TestExclusiveLock:
+    def test_block_commits_on_exit(self, graph):
+        with graph.exclusive() as locked:
+            locked.publish(_intent("agent-a", "auth"))
+        assert graph.count() == 1
+
+    def test_exception_rolls_back(self, graph):
+        with pytest.raises(KeyError):
+            with graph.exclusive():
+                graph.publish(_intent("agent-a", "auth"))
+                raise KeyError("coordinator failed")
+        assert graph.count() == 0
+        with graph.exclusive():
+            graph.publish(_intent("agent-a", "auth"))
+        assert graph.count() == 1
+
+
+class TestDryRunBatch:
+    def test_conflicting_batch_flagged_clean_batch_passes(self, graph):
+        graph.publish(
+            _intent(
+                "agent-a",
+                "users",
+                provides=[_spec("User")],
+                evidence=[{"kind": "code_committed", "description": "landed"}],
+            )
+        )
+
+        batch = [_intent("agent-b", "accounts", provides=[_spec("User")])]
+        report = graph.dry_run_batch(batch, min_stability=0.0)
+        assert report["clean"] is False
+        assert report["new_conflicts"]
+        assert report["rejected"] == []
+
+        batch = [_intent("agent-b", "billing", provides=[_spec("Invoice")])]
+        report = graph.dry_run_batch(batch, min_stability=0.0)
+        assert report == {"clean": True, "new_conflicts": [], "issues": [], "rejected": []}
+        assert graph.count() == 1
+
+
+class TestReresolveAffected:
+    def test_added_evidence_flips_duplicate_provision(self, graph):
+        users = _intent(
+            "agent-a",
+            "users",
+            provides=[_spec("User")],
+            evidence=[{"kind": "test_pass", "description": "passing"}],
+        )
+        graph.publish({**users, "id": "users-1"})
+        previous = graph.publish(
+            {**_intent("agent-b", "accounts", provides=[_spec("User")]), "id": "acct-1"}
+        )
+
+        graph.add_evidence(
+            "acct-1",
+            [
+                {"kind": "code_committed", "description": "landed"},
+                {"kind": "test_pass", "description": "passing"},
+            ],
+        )
+        results = graph.reresolve_affected("acct-1", previous, min_stability=0.0)
+        assert [r["original_intent"] for r in results] == ["acct-1", "users-1"]
+        assert results[0]["conflicts"][0]["winner"] == "me"
+        assert [a["kind"] for a in results[1]["adjustments"]] == ["ConsumeInstead"]
+
+        with pytest.raises(KeyError):
+            graph.reresolve_affected("nope", 0.0)
+
+
+class TestAgentScopedConstraints:
+    def test_scoped_constraint_binds_only_listed_agent(self, graph):
+        provider = _intent("agent-a", "billing policy")
+        provider["constraints"] = [
+            {
+                "target": "money",
+                "requirement": "amounts in integer cents",
+                "affects_tags": ["billing"],
+                "applies_to_agents": ["billing-bot"],
+            }
+        ]
+        graph.publish(provider)
+
+        def consumer(agent_id: str) -> dict:
+            spec = _spec("Invoice")
+            spec["tags"] = ["billing"]
+            return _intent(agent_id, "invoices", provides=[spec])
+
+        assert len(graph.resolve(consumer("billing-bot"), 0.0)["adopted_constraints"]) == 1
+        assert graph.resolve(consumer("search-bot"), 0.0)["adopted_constraints"] == []
+        stored = graph.query_by_agent("agent-a")[0]["constraints"][0]
+        assert stored["applies_to_agents"] == ["billing-bot"]
+
+
+class TestStabilityHistogram:
+    def test_default_buckets(self, graph):
+        for i, stability in enumerate([0.1, 0.3, 0.5, 0.7, 0.95]):
+            graph.publish_with_stability(_intent(f"agent-{i}", "x"), stability)
+        assert graph.stability_histogram() == [1, 2, 2]
+        assert graph.stability_histogram([0.5]) == [2, 3]
+
+    def test_invalid_edges_rejected(self, graph):
+        with pytest.raises(ValueError, match="strictly increasing"):
+            graph.stability_histogram([0.7, 0.3])
+        with pytest.raises(ValueError, match="outside"):
+            graph.stability_histogram([1.5])
+
+
+class TestWouldConflictWith:
+    def test_duplicate_provision_lists_provider(self, graph):
+        graph.publish({**_intent("agent-a", "users", provides=[_spec("User")]), "id": "users-1"})
+
+        found = graph.would_conflict_with(
+            _intent("agent-b", "accounts", provides=[_spec("User")]), min_stability=0.0
+        )
+        assert [i["id"] for i in found] == ["users-1"]
+        clean = _intent("agent-b", "search", provides=[_spec("SearchIndex")])
+        assert graph.would_conflict_with(clean, min_stability=0.0) == []
+        assert graph.count() == 1
+
+
+class TestCheckpoint:
+    def test_checkpoint_after_bulk_publish(self, tmp_path):
+        graph = _core.IntentGraph(str(tmp_path / "graph.db"))
+        for i in range(50):
+            graph.publish(_intent(f"agent-{i}", "bulk import"))
+        graph.checkpoint()
+        assert graph.count() == 50
+
+    def test_in_memory_is_noop(self, graph):
+        graph.checkpoint()
+
+
+class TestModulePrefix:
+    def test_prefix_matches_nested_paths_only(self, graph):
+        def spec(name: str, module: str) -> dict:
+            return {**_spec(name, kind="function", signature=""), "module_path": module}
+
+        graph.publish(_intent("agent-a", "login", provides=[spec("login", "src/auth/session.py")]))
+        graph.publish(_intent("agent-b", "billing", provides=[spec("charge", "src/billing/x.py")]))
+
+        found = graph.query_by_module_prefix("src/auth/")
+        assert [i["intent"] for i in found] == ["login"]
+        assert graph.query_by_module_prefix("lib/") == []
+
+
+class TestCompatibilityMatrix:
+    def test_full_satisfaction_scores_one(self, graph):
+        graph.publish(_intent("agent-a", "users", provides=[_spec("User"), _spec("Org")]))
+        graph.publish(_intent("agent-b", "profiles", requires=[_spec("User"), _spec("Org")]))
+
+        assert graph.compatibility_matrix() == {"agent-b": {"agent-a": 1.0}}
+
+
+class TestIntentSchema:
+    def test_schema_validates_intent_dicts(self):
+        jsonschema = pytest.importorskip("jsonschema")
+        schema = _core.intent_schema()
+
+        intent = _intent("agent-a", "users", provides=[_spec("User")], requires=[_spec("Org")])
+        jsonschema.validate(intent, schema)
+
+        del intent["agent_id"]
+        with pytest.raises(jsonschema.ValidationError):
+            jsonschema.validate(intent, schema)
+
+    def test_schema_lists_enum_values(self):
+        spec = _core.intent_schema()["properties"]["provides"]["items"]
+        assert "endpoint" in spec["properties"]["kind"]["enum"]
+
+
+class TestExplicitTimestamp:
+    def test_backfilled_intents_keep_their_chronology(self, graph):
+        later = _intent("agent-a", "billing")
+        later["timestamp"] = "2024-03-03T09:00:00Z"
+        earlier = _intent("agent-b", "users")
+        earlier["timestamp"] = datetime(2024, 3, 1, 9, tzinfo=timezone.utc)
+        graph.publish(later)
+        graph.publish(earlier)
+
+        assert [i["intent"] for i in graph.query_all()] == ["users", "billing"]
+        assert graph.query_all()[0]["timestamp"].startswith("2024-03-01T09:00:00")
+        assert [i["intent"] for i in graph.query_since("2024-03-02T00:00:00Z")] == ["billing"]
+
+    def test_offsetless_timestamp_is_rejected(self, graph):
+        intent = _intent("agent-a", "billing")
+        intent["timestamp"] = "2024-03-03T09:00:00"
+        with pytest.raises(ValueError):
+            graph.publish(intent)
+
+
+class TestQueryRaw:
+    def test_select_returns_rows_as_dicts(self, graph):
+        graph.publish(_intent("agent-a", "users"))
+        graph.publish(_intent("agent-b", "billing"))
+
+        rows = graph.query_raw(
+            "SELECT agent_id, intent FROM intents WHERE agent_id = ?", ["agent-b"]
+        )
+        assert rows == [{"agent_id": "agent-b", "intent": "billing"}]
+        assert graph.query_raw("SELECT COUNT(*) AS n FROM intents") == [{"n": 2}]
+
+    def test_writes_are_rejected(self, graph):
+        with pytest.raises(ValueError):
+            graph.query_raw("INSERT INTO intents (id) VALUES ('x')")
+        assert graph.query_raw("SELECT COUNT(*) AS n FROM intents") == [{"n": 0}]
+
+
+class TestQueryAfterSeq:
+    def test_cursor_reads_same_timestamp_intents_once(self, graph):
+        stamp = "2024-03-01T09:00:00Z"
+
+        def publish(*agents):
+            for agent in agents:
+                intent = _intent(agent, "burst")
+                intent["timestamp"] = stamp
+                graph.publish(intent)
+
+        publish("agent-a", "agent-b")
+        first, cursor = graph.query_after_seq()
+        assert [i["agent_id"] for i in first] == ["agent-a", "agent-b"]
+
+        publish("agent-c", "agent-d", "agent-e")
+        second, cursor = graph.query_after_seq(cursor)
+        assert [i["agent_id"] for i in second] == ["agent-c", "agent-d", "agent-e"]
+        assert graph.query_after_seq(cursor) == ([], cursor)
+
+    def test_old_cursor_still_reads_after_clear(self, graph):
+        graph.publish(_intent("agent-a", "before"))
+        _, cursor = graph.query_after_seq()
+        graph.clear()
+        graph.publish(_intent("agent-b", "after"))
+        read, _ = graph.query_after_seq(cursor)
+        assert [i["intent"] for i in read] == ["after"]
+
+
+class TestConsolidationCandidates:
+    def test_only_compatible_duplicates_group(self, graph):
+        graph.publish(_intent("agent-a", "narrow", provides=[_spec("User")]))
+        wide = _spec("User", signature="id: UUID, email: str")
+        graph.publish(_intent("agent-b", "wide", provides=[wide]))
+        clash = _spec("User", signature="id: int")
+        graph.publish(_intent("agent-c", "clash", provides=[clash]))
+
+        groups = graph.consolidation_candidates()
+        assert [[i["intent"] for i in group] for group in groups] == [["narrow", "wide"]]
+
+
+class TestConflictHalfLife:
+    def test_fresh_conflict_still_penalized(self):
+        conflicted = _intent(
+            "agent-a", "work", evidence=[{"kind": "conflict", "description": "clashed"}]
+        )
+        scorer = _core.StabilityScorer(conflict_half_life_hours=24.0)
+        assert scorer.compute(conflicted) == pytest.approx(0.15, abs=1e-4)
+
+    def test_non_positive_half_life_rejected(self):
+        with pytest.raises(ValueError, match="conflict_half_life_hours"):
+            _core.StabilityScorer(conflict_half_life_hours=0.0)